@@ -0,0 +1,74 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+use std::path::PathBuf;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use deepin_translation_utils::linguist::{Context, Location, Message, Translation, TranslationType, Ts};
+
+/// Message counts of the small, medium and large synthetic documents.
+const DOCUMENT_SIZES: [(&str, usize); 3] = [("small", 100), ("medium", 5_000), ("large", 50_000)];
+const MESSAGES_PER_CONTEXT: usize = 50;
+
+fn create_synthetic_ts(messages: usize) -> Ts {
+    let contexts = (0..messages.div_ceil(MESSAGES_PER_CONTEXT)).map(|context_idx| Context {
+        name: format!("Bench{context_idx}"),
+        messages: (0..MESSAGES_PER_CONTEXT.min(messages - context_idx * MESSAGES_PER_CONTEXT)).map(|idx| Message {
+            location: vec![Location { filename: Some(format!("../src/bench{context_idx}.cpp")), line: format!("+{idx}") }],
+            source: format!("Source text {idx} & more"),
+            translation: Translation {
+                type_attr: if idx % 3 == 0 { Some(TranslationType::Unfinished) } else { None },
+                value: if idx % 3 == 0 { None } else { Some(format!("译文 {idx}")) },
+                numerus_forms: Vec::new(),
+            },
+            comment: None,
+            translator_comment: None,
+            numerus: None,
+        }).collect(),
+    }).collect();
+    Ts { language: Some("zh_CN".to_string()), version: "2.1".to_string(), contexts }
+}
+
+fn bench_file_path(name: &str) -> PathBuf {
+    let bench_dir = std::env::temp_dir().join("deepin-translation-utils-bench").join("linguist");
+    std::fs::create_dir_all(&bench_dir).unwrap();
+    bench_dir.join(format!("{name}_zh_CN.ts"))
+}
+
+// Throughput is reported in messages, so criterion output shows messages per second for each size.
+fn bench_linguist(c: &mut Criterion) {
+    let mut parse_group = c.benchmark_group("ts_parse");
+    for (name, messages) in DOCUMENT_SIZES {
+        let file_path = bench_file_path(name);
+        create_synthetic_ts(messages).save_into_file(&file_path).unwrap();
+        parse_group.throughput(Throughput::Elements(messages as u64));
+        parse_group.bench_with_input(BenchmarkId::from_parameter(name), &file_path, |b, file_path| {
+            b.iter(|| Ts::load_from_file(file_path).unwrap())
+        });
+    }
+    parse_group.finish();
+
+    let mut stats_group = c.benchmark_group("ts_stats");
+    for (name, messages) in DOCUMENT_SIZES {
+        let ts = create_synthetic_ts(messages);
+        stats_group.throughput(Throughput::Elements(messages as u64));
+        stats_group.bench_with_input(BenchmarkId::from_parameter(name), &ts, |b, ts| {
+            b.iter(|| ts.get_message_stats())
+        });
+    }
+    stats_group.finish();
+
+    let mut write_group = c.benchmark_group("ts_write");
+    for (name, messages) in DOCUMENT_SIZES {
+        let ts = create_synthetic_ts(messages);
+        let file_path = bench_file_path(&format!("{name}_write"));
+        write_group.throughput(Throughput::Elements(messages as u64));
+        write_group.bench_with_input(BenchmarkId::from_parameter(name), &ts, |b, ts| {
+            b.iter(|| ts.save_into_file(&file_path).unwrap())
+        });
+    }
+    write_group.finish();
+}
+
+criterion_group!(benches, bench_linguist);
+criterion_main!(benches);
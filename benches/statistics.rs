@@ -0,0 +1,59 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+use std::fs;
+use std::path::PathBuf;
+use criterion::{criterion_group, criterion_main, Criterion};
+use deepin_translation_utils::subcmd::cache::{FileFingerprint, StatsCache};
+use deepin_translation_utils::subcmd::statistics::compute_files_stats;
+
+const CORPUS_FILES: usize = 300;
+const MESSAGES_PER_FILE: usize = 200;
+
+/// Create a synthetic corpus of Qt Linguist TS files under system temp dir.
+fn create_synthetic_corpus() -> Vec<PathBuf> {
+    let corpus_dir = std::env::temp_dir().join("deepin-translation-utils-bench").join("statistics");
+    fs::create_dir_all(&corpus_dir).unwrap();
+
+    let mut content = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<!DOCTYPE TS>\n<TS language=\"zh_CN\" version=\"2.1\">\n<context>\n    <name>Bench</name>\n");
+    for idx in 0..MESSAGES_PER_FILE {
+        let translation = if idx % 3 == 0 {
+            "<translation type=\"unfinished\"></translation>".to_string()
+        } else {
+            format!("<translation>译文 {idx}</translation>")
+        };
+        content.push_str(&format!("    <message>\n        <source>Source text {idx}</source>\n        {translation}\n    </message>\n"));
+    }
+    content.push_str("</context>\n</TS>\n");
+
+    (0..CORPUS_FILES).map(|idx| {
+        let file_path = corpus_dir.join(format!("bench_{idx}_zh_CN.ts"));
+        fs::write(&file_path, &content).unwrap();
+        file_path
+    }).collect()
+}
+
+fn bench_compute_files_stats(c: &mut Criterion) {
+    let files = create_synthetic_corpus();
+    let mut group = c.benchmark_group("compute_files_stats");
+    group.sample_size(10);
+    group.bench_function("single thread", |b| {
+        b.iter(|| compute_files_stats(&files, Some(1), |_, _| {}).unwrap())
+    });
+    group.bench_function("all cpus", |b| {
+        b.iter(|| compute_files_stats(&files, None, |_, _| {}).unwrap())
+    });
+    // what a second run over an unchanged corpus costs instead of parsing
+    let mut cache = StatsCache::default();
+    for (file_path, stats) in files.iter().zip(compute_files_stats(&files, None, |_, _| {}).unwrap()) {
+        cache.insert(file_path, FileFingerprint::of(file_path).unwrap(), stats.unwrap());
+    }
+    group.bench_function("cache hits", |b| {
+        b.iter(|| files.iter().all(|file_path| cache.lookup(file_path, &FileFingerprint::of(file_path).unwrap()).is_some()))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_compute_files_stats);
+criterion_main!(benches);
@@ -0,0 +1,52 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+use std::fs;
+use std::path::PathBuf;
+use criterion::{criterion_group, criterion_main, Criterion};
+use deepin_translation_utils::walk::{walk, WalkOptions};
+
+const TREE_DEPTH: usize = 6;
+const DIRS_PER_LEVEL: usize = 3;
+
+/// Create a synthetic project with a few translation files, and a deep `node_modules` tree.
+fn create_synthetic_tree() -> PathBuf {
+    let root = std::env::temp_dir().join("deepin-translation-utils-bench").join("walk");
+    if root.exists() {
+        return root;
+    }
+    fs::create_dir_all(root.join("translations")).unwrap();
+    fs::write(root.join("translations").join("app_zh_CN.ts"), "<TS/>").unwrap();
+
+    let mut level = vec![root.join("node_modules")];
+    for _ in 0..TREE_DEPTH {
+        let mut next_level = Vec::new();
+        for dir in &level {
+            for idx in 0..DIRS_PER_LEVEL {
+                let child = dir.join(format!("pkg{idx}"));
+                fs::create_dir_all(&child).unwrap();
+                fs::write(child.join("index.js"), "").unwrap();
+                next_level.push(child);
+            }
+        }
+        level = next_level;
+    }
+    root
+}
+
+fn bench_walk(c: &mut Criterion) {
+    let root = create_synthetic_tree();
+    let mut group = c.benchmark_group("walk");
+    group.bench_function("default pruning", |b| {
+        b.iter(|| walk(&root, &WalkOptions::default()).count())
+    });
+    group.bench_function("no pruning", |b| {
+        let opts = WalkOptions { pruned_dirs: Vec::new(), ..WalkOptions::default() };
+        b.iter(|| walk(&root, &opts).count())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_walk);
+criterion_main!(benches);
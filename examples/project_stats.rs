@@ -0,0 +1,34 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+// Print translation completeness of every resource of a project, using the library API only.
+//
+// Usage: cargo run --example project_stats --no-default-features -- <project_root>
+
+use std::path::PathBuf;
+
+use deepin_translation_utils::transifex::yaml_file::try_laod_transifex_yaml_file;
+use deepin_translation_utils::{I18nFileKind, MessageStats, gettext, linguist};
+
+fn file_stats(path: &PathBuf) -> Result<MessageStats, Box<dyn std::error::Error>> {
+    Ok(match I18nFileKind::from_ext_hint(path)? {
+        I18nFileKind::Linguist => linguist::Ts::load_from_file(path)?.get_message_stats(),
+        I18nFileKind::Gettext => gettext::Po::load_from_file(path)?.get_message_stats(),
+    })
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let project_root = PathBuf::from(std::env::args().nth(1).unwrap_or_else(|| ".".to_string()));
+    let (yaml_path, tx_yaml) = try_laod_transifex_yaml_file(&project_root)?;
+    println!("Loaded {yaml_path:?}");
+
+    for filter in &tx_yaml.filters {
+        println!("{}", filter.source);
+        for (lang_code, path) in filter.match_target_files(&project_root)? {
+            let stats = file_stats(&path)?;
+            println!("  {lang_code}: {:.2}% ({}/{})", stats.completeness_percentage(), stats.finished, stats.finished + stats.unfinished);
+        }
+    }
+    Ok(())
+}
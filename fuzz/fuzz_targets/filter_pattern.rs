@@ -0,0 +1,17 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use deepin_translation_utils::transifex::target_match::match_lang_code;
+
+// Input is the target file name pattern and the file name, separated by the first newline.
+fuzz_target!(|data: &[u8]| {
+    let Ok(content) = std::str::from_utf8(data) else {
+        return;
+    };
+    let (pattern, file_name) = content.split_once('\n').unwrap_or((content, ""));
+    let _ = match_lang_code(pattern, file_name, data.len() % 2 == 0);
+});
@@ -0,0 +1,12 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use deepin_translation_utils::transifex::yaml_file::TxResourceLookupEntry;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = TxResourceLookupEntry::table_from_reader(data);
+});
@@ -0,0 +1,14 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use deepin_translation_utils::gettext::Po;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(po) = Po::from_reader(data) {
+        let _ = po.get_message_stats();
+    }
+});
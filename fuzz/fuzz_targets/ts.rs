@@ -0,0 +1,14 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use deepin_translation_utils::linguist::Ts;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(ts) = Ts::from_reader(data) {
+        let _ = ts.get_message_stats();
+    }
+});
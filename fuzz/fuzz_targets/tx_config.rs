@@ -0,0 +1,18 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use deepin_translation_utils::transifex::tx_config_file::TxConfig;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(content) = std::str::from_utf8(data) else {
+        return;
+    };
+    if let Ok(tx_config) = TxConfig::from_str(content) {
+        let _ = TxConfig::from_str(&tx_config.to_str());
+        let _ = tx_config.to_transifex_yaml();
+    }
+});
@@ -0,0 +1,17 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use deepin_translation_utils::transifex::yaml_file::TransifexYaml;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(content) = std::str::from_utf8(data) else {
+        return;
+    };
+    if let Ok(tx_yaml) = TransifexYaml::from_str(content) {
+        let _ = tx_yaml.to_tx_config(String::new(), vec![]);
+    }
+});
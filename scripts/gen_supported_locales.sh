@@ -0,0 +1,24 @@
+#!/bin/sh
+# SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+#
+# SPDX-License-Identifier: MIT
+
+# Regenerate the language code table in src/transifex/supported_locales.rs from Transifex's
+# published language list (https://transifex.github.io/openapi/#tag/Languages).
+#
+# Usage: TX_TOKEN=<token> scripts/gen_supported_locales.sh > /tmp/locales.rs
+# then replace the body of SUPPORTED_LOCALES with the output.
+#
+# Requires curl and jq. Codes are sorted bytewise (LC_ALL=C) since the table is binary-searched.
+
+set -eu
+
+: "${TX_TOKEN:?Please set TX_TOKEN to your Transifex API token}"
+
+curl --silent --fail \
+    --header "Authorization: Bearer ${TX_TOKEN}" \
+    --header "Accept: application/vnd.api+json" \
+    "https://rest.api.transifex.com/languages" \
+    | jq --raw-output '.data[].attributes.code' \
+    | LC_ALL=C sort --unique \
+    | sed 's/.*/    "&",/'
@@ -59,6 +59,19 @@ pub enum Commands {
         sort_by: crate::subcmd::statistics::StatsSortBy,
         #[arg(short, long, default_value = "en,en_US", value_delimiter = ',')]
         ignore_languages: Vec<String>,
+        /// Number of threads used to parse translation files. Defaults to the number of CPUs.
+        #[arg(short, long)]
+        jobs: Option<usize>,
+        /// Neither read nor update the stats cache, parse every translation file again.
+        #[clap(long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        no_cache: bool,
+        /// Compare local stats with the stats on Transifex for each resource of .tx/config.
+        /// Shows local stats only if no Transifex API token is available.
+        #[clap(long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        remote: bool,
+        /// Report resources whose local and remote completeness differ by more than this many percentage points.
+        #[arg(long, default_value_t = 5.0)]
+        divergence_threshold: f64,
     },
     #[command(name = "yaml2txconfig")]
     #[command(
@@ -81,6 +94,9 @@ pub enum Commands {
         /// If not provided, it will lookup all projects under the organization slug.
         #[arg(short, long, default_value = None)]
         project_slug: Option<String>,
+        /// Print the .tx/config that would be generated instead of writing it.
+        #[clap(long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        dry_run: bool,
     },
     #[command(name = "txconfig2yaml")]
     #[command(
@@ -104,6 +120,131 @@ pub enum Commands {
         #[arg(short, long, default_value = "linuxdeepin")]
         organization_slug: String,
     },
+    #[command(name = "check")]
+    #[command(
+        about = "Checks translation files of the provided project against the project policies",
+        long_about = "Checks translation files of the provided project according to transifex.yaml or .tx/config file, and the optional .txconfig.toml tool config file.\n\n\
+            Every resource must ship an existing, parseable and non-empty translation file for each of the required languages, \
+            and the content of every file must match the declared file format.",
+    )]
+    Check {
+        project_root: PathBuf,
+        /// Also run thorough checks which need to parse every translation file, e.g. language attribute checks.
+        #[clap(short, long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        thorough: bool,
+        /// Neither read nor update the cache, parse every translation file again.
+        #[clap(long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        no_cache: bool,
+    },
+    #[command(name = "cache")]
+    #[command(
+        about = "Manages the local cache of translation file statistics and lint results",
+    )]
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+    #[command(name = "tm")]
+    #[command(
+        about = "Builds and applies translation memory compendiums",
+    )]
+    Tm {
+        #[command(subcommand)]
+        action: TmAction,
+    },
+    #[command(name = "snapshot")]
+    #[command(
+        about = "Snapshots, restores and compares the translation state of a project",
+    )]
+    Snapshot {
+        #[command(subcommand)]
+        action: SnapshotAction,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum CacheAction {
+    #[command(name = "clear")]
+    #[command(
+        about = "Removes the cached statistics and lint results of translation files",
+    )]
+    Clear,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum TmAction {
+    #[command(name = "build")]
+    #[command(
+        about = "Collects finished translations of the given repositories into a compendium file",
+        long_about = "Collects finished translations of the given language from every Qt Linguist (.ts) and GNU Gettext (.po) file \
+            under the given repositories into a compendium file, with the frequency of each translation.",
+    )]
+    Build {
+        /// Language code of the collected translations.
+        #[arg(short, long)]
+        language: String,
+        /// The compendium file to write.
+        #[arg(short, long, default_value = "compendium.json")]
+        output: PathBuf,
+        repo_roots: Vec<PathBuf>,
+    },
+    #[command(name = "apply")]
+    #[command(
+        about = "Pre-translates the given translation files by exact match against a compendium file",
+        long_about = "Pre-translates untranslated messages of the given translation files by exact match against a compendium file.\n\n\
+            Filled messages are marked unfinished (.ts) or fuzzy (.po) so they still get reviewed, \
+            and less frequent alternatives are recorded in a translator comment.",
+    )]
+    Apply {
+        /// The compendium file to read.
+        #[arg(short, long)]
+        compendium: PathBuf,
+        target_files: Vec<PathBuf>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum SnapshotAction {
+    #[command(name = "create")]
+    #[command(
+        about = "Snapshots the project config files and all translation files into a directory",
+        long_about = "Snapshots transifex.yaml, .tx/config, every source and translation file of the project, their content hashes \
+            and their stats into a new directory, e.g. before mass edits or migrating to new resource slugs.",
+    )]
+    Create {
+        project_root: PathBuf,
+        /// The snapshot directory to create.
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+    #[command(name = "restore")]
+    #[command(
+        about = "Writes the files of a snapshot back into the project",
+        long_about = "Writes the files of a snapshot back into the project, after verifying their hashes.\n\n\
+            Files changed since the snapshot are not overwritten unless --force is given, review them with `snapshot diff` first.",
+    )]
+    Restore {
+        project_root: PathBuf,
+        /// The snapshot directory to read.
+        #[arg(short, long)]
+        snapshot: PathBuf,
+        /// Also overwrite files changed since the snapshot.
+        #[clap(short, long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        force: bool,
+    },
+    #[command(name = "diff")]
+    #[command(
+        about = "Shows files added, removed and modified since a snapshot was taken",
+    )]
+    Diff {
+        project_root: PathBuf,
+        /// The snapshot directory to read.
+        #[arg(short, long)]
+        snapshot: PathBuf,
+        /// Print the report as JSON.
+        #[clap(long, action = clap::ArgAction::SetTrue, default_value_t = false)]
+        json: bool,
+    },
 }
 
 #[derive(TeError, Debug)]
@@ -113,6 +254,10 @@ pub enum CliError {
     Statistics(#[from] crate::subcmd::statistics::CmdError),
     Yaml2TxConfig(#[from] crate::subcmd::yaml2txconfig::CmdY2TCError),
     TxConfig2Yaml(#[from] crate::subcmd::txconfig2yaml::CmdTC2YError),
+    Check(#[from] crate::subcmd::check::CmdError),
+    Cache(#[from] crate::subcmd::cache::CmdError),
+    Tm(#[from] crate::subcmd::tm::CmdError),
+    Snapshot(#[from] crate::subcmd::snapshot::CmdError),
 }
 
 pub fn execute() -> Result<(), CliError> {
@@ -126,11 +271,11 @@ pub fn execute() -> Result<(), CliError> {
         Commands::ZhConvPlain { target_languages, content } => {
             subcmd::subcmd_zhconv_plain(&target_languages, &content)?;
         },
-        Commands::Statistics { project_root, format, sort_by, ignore_languages } => {
-            subcmd::subcmd_statistics(&project_root, format, sort_by, ignore_languages)?;
+        Commands::Statistics { project_root, format, sort_by, ignore_languages, jobs, no_cache, remote, divergence_threshold } => {
+            subcmd::subcmd_statistics(&project_root, format, sort_by, ignore_languages, jobs, no_cache, remote, divergence_threshold)?;
         },
-        Commands::Yaml2TxConfig { project_root, force_online, github_repository, organization_slug, project_slug } => {
-            subcmd::subcmd_yaml2txconfig(&project_root, force_online, github_repository, organization_slug, project_slug)?;
+        Commands::Yaml2TxConfig { project_root, force_online, github_repository, organization_slug, project_slug, dry_run } => {
+            subcmd::subcmd_yaml2txconfig(&project_root, force_online, github_repository, organization_slug, project_slug, dry_run)?;
         },
         Commands::TxConfig2Yaml { project_root } => {
             subcmd::subcmd_txconfig2yaml(&project_root)?;
@@ -138,6 +283,21 @@ pub fn execute() -> Result<(), CliError> {
         Commands::MonoTxConfig { project_root, force_online, organization_slug } => {
             subcmd::subcmd_monotxconfig(&project_root, force_online, organization_slug);
         },
+        Commands::Check { project_root, thorough, no_cache } => {
+            subcmd::subcmd_check(&project_root, thorough, no_cache)?;
+        },
+        Commands::Cache { action } => match action {
+            CacheAction::Clear => subcmd::subcmd_cache_clear()?,
+        },
+        Commands::Tm { action } => match action {
+            TmAction::Build { language, output, repo_roots } => subcmd::subcmd_tm_build(&language, &repo_roots, &output)?,
+            TmAction::Apply { compendium, target_files } => subcmd::subcmd_tm_apply(&compendium, &target_files)?,
+        },
+        Commands::Snapshot { action } => match action {
+            SnapshotAction::Create { project_root, output } => subcmd::subcmd_snapshot_create(&project_root, &output)?,
+            SnapshotAction::Restore { project_root, snapshot, force } => subcmd::subcmd_snapshot_restore(&project_root, &snapshot, force)?,
+            SnapshotAction::Diff { project_root, snapshot, json } => subcmd::subcmd_snapshot_diff(&project_root, &snapshot, json)?,
+        },
     }
 
     Ok(())
@@ -0,0 +1,396 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+//! Translation memory shared across repositories.
+//!
+//! A [`Compendium`] collects the finished translations of one language from many TS and PO files,
+//! and pre-translates other files by exact match of the source text. Pre-translated messages are
+//! left unfinished (TS) or fuzzy (PO), so they still get reviewed.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use polib::message::{MessageMutView, MessageView};
+use serde::{Deserialize, Serialize};
+use thiserror::Error as TeError;
+
+use crate::i18n_file::common::{I18nFileKind, same_language};
+use crate::i18n_file::gettext::{Po, PoLoadError};
+use crate::i18n_file::linguist::{TranslationType, Ts, TsLoadError};
+use crate::walk::{WalkOptions, walk};
+
+/// A translation of a compendium entry, with the number of messages translated this way.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompendiumCandidate {
+    /// Translated text.
+    pub translation: String,
+    /// Number of finished messages with this translation.
+    pub count: usize,
+}
+
+/// A source text and its known translations.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompendiumEntry {
+    /// TS context name or PO `msgctxt`, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+    /// Source text.
+    pub source: String,
+    /// Known translations, the most frequent first.
+    pub candidates: Vec<CompendiumCandidate>,
+}
+
+impl CompendiumEntry {
+    /// The most frequent translation.
+    pub fn best(&self) -> Option<&str> {
+        self.candidates.first().map(|candidate| candidate.translation.as_str())
+    }
+
+    /// Translator comment listing the other candidates, or `None` if there is only one.
+    pub fn alternatives_comment(&self) -> Option<String> {
+        if self.candidates.len() < 2 {
+            return None;
+        }
+        let alternatives = self.candidates[1..].iter()
+            .map(|candidate| format!("{} ({})", candidate.translation, candidate.count))
+            .collect::<Vec<String>>()
+            .join(", ");
+        Some(format!("Translation memory alternatives: {alternatives}"))
+    }
+}
+
+/// Finished translations of one language, keyed by source text and context.
+///
+/// Serialized as compact JSON, see [`Compendium::to_json`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Compendium {
+    /// Language code of the translations.
+    pub language: String,
+    /// Entries sorted by source text and context.
+    pub entries: Vec<CompendiumEntry>,
+}
+
+/// Error of building, loading or applying a compendium.
+#[derive(TeError, Debug)]
+pub enum CompendiumError {
+    /// A file can't be read or written.
+    #[error("Can not access file {0:?} because: {1}")]
+    Io(PathBuf, #[source] std::io::Error),
+    /// A directory can't be scanned.
+    #[error("Can not scan directory because: {0}")]
+    Walk(#[from] walkdir::Error),
+    /// A TS file can't be loaded.
+    #[error("Fail to load TS file {0:?} because: {1}")]
+    LoadTs(PathBuf, #[source] TsLoadError),
+    /// A PO file can't be loaded.
+    #[error("Fail to load PO file {0:?} because: {1}")]
+    LoadPo(PathBuf, #[source] PoLoadError),
+    /// The compendium file is not valid JSON.
+    #[error("Fail to parse compendium because: {0}")]
+    Parse(#[from] serde_json::Error),
+}
+
+/// Collects finished translations into a [`Compendium`].
+pub struct CompendiumBuilder {
+    language: String,
+    counts: BTreeMap<(String, Option<String>), BTreeMap<String, usize>>,
+}
+
+impl CompendiumBuilder {
+    /// Create a builder for translations of the given language.
+    pub fn new(language: &str) -> Self {
+        Self { language: language.to_string(), counts: BTreeMap::new() }
+    }
+
+    fn add(&mut self, context: Option<&str>, source: &str, translation: &str) {
+        if source.is_empty() || translation.is_empty() {
+            return;
+        }
+        let key = (source.to_string(), context.filter(|context| !context.is_empty()).map(str::to_string));
+        *self.counts.entry(key).or_default().entry(translation.to_string()).or_default() += 1;
+    }
+
+    /// Add finished singular messages of the given TS file, regardless of its language.
+    pub fn add_ts(&mut self, ts: &Ts) {
+        for context in &ts.contexts {
+            for message in &context.messages {
+                if message.translation.type_attr.is_some() || message.numerus.as_deref() == Some("yes") {
+                    continue;
+                }
+                if let Some(value) = &message.translation.value {
+                    self.add(Some(&context.name), &message.source, value);
+                }
+            }
+        }
+    }
+
+    /// Add translated, non-fuzzy singular messages of the given PO file, regardless of its language.
+    pub fn add_po(&mut self, po: &Po) {
+        for message in po.inner.messages() {
+            if !message.is_translated() || message.is_plural() || message.flags().is_fuzzy() {
+                continue;
+            }
+            // singular messages always have msgstr
+            self.add(Some(message.msgctxt()), message.msgid(), message.msgstr().unwrap());
+        }
+    }
+
+    /// Add the given TS or PO file if it is of the language of this builder.
+    ///
+    /// Returns whether the file was added. Files of other kinds are ignored.
+    pub fn add_file(&mut self, file_path: &Path) -> Result<bool, CompendiumError> {
+        match I18nFileKind::from_ext_hint(file_path) {
+            Ok(I18nFileKind::Linguist) => {
+                let ts = Ts::load_from_file(file_path).map_err(|e| CompendiumError::LoadTs(file_path.to_path_buf(), e))?;
+                if !ts.language.as_deref().is_some_and(|language| same_language(language, &self.language)) {
+                    return Ok(false);
+                }
+                self.add_ts(&ts);
+            },
+            Ok(I18nFileKind::Gettext) => {
+                let po = Po::load_from_file(file_path).map_err(|e| CompendiumError::LoadPo(file_path.to_path_buf(), e))?;
+                if !same_language(&po.get_language(), &self.language) {
+                    return Ok(false);
+                }
+                self.add_po(&po);
+            },
+            Err(_) => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Add every TS and PO file of the builder's language under `root`, see [`Self::add_file`].
+    ///
+    /// Returns the number of files added, and the errors of files or directories which can't be
+    /// read, which are skipped instead of stopping the scan. [`crate::walk::DEFAULT_PRUNED_DIRS`]
+    /// are not scanned.
+    pub fn add_dir(&mut self, root: &Path) -> (usize, Vec<CompendiumError>) {
+        let mut added = 0;
+        let mut errors = Vec::<CompendiumError>::new();
+        for entry in walk(root, &WalkOptions::default()) {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    errors.push(e.into());
+                    continue;
+                },
+            };
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            match self.add_file(entry.path()) {
+                Ok(true) => added += 1,
+                Ok(false) => {},
+                Err(e) => errors.push(e),
+            }
+        }
+        (added, errors)
+    }
+
+    /// Create the compendium. Candidates with the same count are sorted by translation.
+    pub fn build(self) -> Compendium {
+        let entries = self.counts.into_iter().map(|((source, context), translations)| {
+            let mut candidates = translations.into_iter()
+                .map(|(translation, count)| CompendiumCandidate { translation, count })
+                .collect::<Vec<_>>();
+            // stable sort keeps translations with the same count in alphabetical order
+            candidates.sort_by(|lhs, rhs| rhs.count.cmp(&lhs.count));
+            CompendiumEntry { context, source, candidates }
+        }).collect();
+        Compendium { language: self.language, entries }
+    }
+}
+
+impl Compendium {
+    /// Parse compendium JSON. Entries are sorted again, so edited files still work with [`Self::lookup`].
+    pub fn from_str(content: &str) -> Result<Self, CompendiumError> {
+        let mut compendium: Self = serde_json::from_str(content)?;
+        compendium.entries.sort_by(|lhs, rhs| (&lhs.source, &lhs.context).cmp(&(&rhs.source, &rhs.context)));
+        Ok(compendium)
+    }
+
+    /// Serialize to compact JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("compendium is always serializable")
+    }
+
+    /// Load the given compendium file.
+    pub fn load_from_file(file_path: &Path) -> Result<Self, CompendiumError> {
+        let content = std::fs::read_to_string(file_path).map_err(|e| CompendiumError::Io(file_path.to_path_buf(), e))?;
+        Self::from_str(&content)
+    }
+
+    /// Save into the given compendium file.
+    pub fn save_into_file(&self, file_path: &Path) -> Result<(), CompendiumError> {
+        std::fs::write(file_path, self.to_json()).map_err(|e| CompendiumError::Io(file_path.to_path_buf(), e))
+    }
+
+    /// Find the entry of the given source text.
+    ///
+    /// An entry of the same context is preferred, otherwise the entry with the most frequent
+    /// translation among all contexts is returned.
+    pub fn lookup(&self, context: Option<&str>, source: &str) -> Option<&CompendiumEntry> {
+        let start = self.entries.partition_point(|entry| entry.source.as_str() < source);
+        let same_source = self.entries[start..].iter().take_while(|entry| entry.source == source);
+        let mut best: Option<&CompendiumEntry> = None;
+        for entry in same_source {
+            if context.is_some() && entry.context.as_deref() == context {
+                return Some(entry);
+            }
+            let count = |entry: &CompendiumEntry| entry.candidates.first().map_or(0, |candidate| candidate.count);
+            if best.is_none_or(|best| count(entry) > count(best)) {
+                best = Some(entry);
+            }
+        }
+        best
+    }
+
+    /// Pre-translate unfinished singular messages of the given TS file which have no translation yet.
+    ///
+    /// Filled messages stay unfinished, and other candidates are recorded in their translator comment.
+    /// Returns the number of filled messages.
+    pub fn apply_to_ts(&self, ts: &mut Ts) -> usize {
+        let mut filled = 0;
+        for context in &mut ts.contexts {
+            for message in &mut context.messages {
+                let untranslated = message.translation.value.as_deref().is_none_or(str::is_empty);
+                if !matches!(message.translation.type_attr, Some(TranslationType::Unfinished))
+                    || !untranslated || message.numerus.as_deref() == Some("yes") {
+                    continue;
+                }
+                let Some(entry) = self.lookup(Some(&context.name), &message.source) else {
+                    continue;
+                };
+                message.translation.value = entry.best().map(str::to_string);
+                if let Some(comment) = entry.alternatives_comment() {
+                    message.translator_comment = Some(comment);
+                }
+                filled += 1;
+            }
+        }
+        filled
+    }
+
+    /// Pre-translate untranslated singular messages of the given PO file.
+    ///
+    /// Filled messages are marked fuzzy, and other candidates are recorded in their comments.
+    /// Returns the number of filled messages.
+    pub fn apply_to_po(&self, po: &mut Po) -> usize {
+        let mut filled = 0;
+        for mut message in po.inner.messages_mut() {
+            if message.is_translated() || message.is_plural() {
+                continue;
+            }
+            let context = Some(message.msgctxt()).filter(|context| !context.is_empty());
+            let Some(entry) = self.lookup(context, message.msgid()) else {
+                continue;
+            };
+            let Some(best) = entry.best() else {
+                continue;
+            };
+            // singular messages always accept msgstr
+            message.set_msgstr(best.to_string()).unwrap();
+            message.flags_mut().add_flag("fuzzy");
+            if let Some(comment) = entry.alternatives_comment() {
+                let comments = message.comments_mut();
+                if !comments.is_empty() {
+                    comments.push('\n');
+                }
+                comments.push_str(&comment);
+            }
+            filled += 1;
+        }
+        filled
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::i18n_file::gettext::tests::TEST_ZH_CN_PO_CONTENT;
+    use crate::i18n_file::linguist::tests::TEST_ZH_CN_TS_CONTENT;
+    use crate::transifex::yaml_file::tests::create_test_project_dir;
+
+    #[test]
+    fn tst_build_and_apply_ts() {
+        let ts = Ts::from_str(TEST_ZH_CN_TS_CONTENT).unwrap();
+        let mut builder = CompendiumBuilder::new("zh_CN");
+        builder.add_ts(&ts);
+        let compendium = builder.build();
+        // obsolete, unfinished and plural messages are not collected
+        assert_eq!(compendium.entries.len(), 2);
+        assert_eq!(Compendium::from_str(&compendium.to_json()).unwrap(), compendium);
+
+        let mut target = ts.clone();
+        target.clear_finished_messages();
+        assert_eq!(compendium.apply_to_ts(&mut target), 2);
+        for (filled, original) in target.contexts[0].messages.iter().zip(&ts.contexts[0].messages).take(2) {
+            assert_eq!(filled.translation.value, original.translation.value);
+            assert!(matches!(filled.translation.type_attr, Some(TranslationType::Unfinished)));
+        }
+        assert_eq!(target.contexts[0].messages[3].translation.value, None);
+
+        // filled messages are still unfinished, but not untranslated
+        assert_eq!(compendium.apply_to_ts(&mut target), 0);
+    }
+
+    #[test]
+    fn tst_build_and_apply_po() {
+        let po = Po::from_str(TEST_ZH_CN_PO_CONTENT).unwrap();
+        let mut builder = CompendiumBuilder::new("zh_CN");
+        builder.add_po(&po);
+        let compendium = builder.build();
+        assert_eq!(compendium.entries.len(), 2);
+
+        let mut target = po.clone();
+        target.clear_finished_messages();
+        assert_eq!(compendium.apply_to_po(&mut target), 2);
+        for (filled, original) in target.inner.messages().zip(po.inner.messages()).take(2) {
+            assert_eq!(filled.msgstr().unwrap(), original.msgstr().unwrap());
+            assert!(filled.flags().is_fuzzy());
+        }
+        // fuzzy translations are not collected again
+        let mut builder = CompendiumBuilder::new("zh_CN");
+        builder.add_po(&target);
+        assert!(builder.build().entries.is_empty());
+    }
+
+    #[test]
+    fn tst_conflicting_candidates() {
+        let project_root = create_test_project_dir("tst_compendium_conflicting_candidates");
+        let ts = Ts::from_str(TEST_ZH_CN_TS_CONTENT).unwrap();
+        ts.save_into_file(&project_root.join("a_zh_CN.ts")).unwrap();
+        ts.save_into_file(&project_root.join("b_zh_CN.ts")).unwrap();
+        let mut other = ts.clone();
+        other.contexts[0].messages[0].fill_translation("患难见真情");
+        other.save_into_file(&project_root.join("c_zh_CN.ts")).unwrap();
+        let mut other_language = ts.clone();
+        other_language.set_language("zh_TW");
+        other_language.save_into_file(&project_root.join("c_zh_TW.ts")).unwrap();
+
+        std::fs::write(project_root.join("broken_zh_CN.ts"), "<TS").unwrap();
+
+        let mut builder = CompendiumBuilder::new("zh-CN");
+        let (added, errors) = builder.add_dir(&project_root);
+        assert_eq!(added, 3);
+        assert!(matches!(errors.as_slice(), [CompendiumError::LoadTs(path, _)] if path.ends_with("broken_zh_CN.ts")));
+        let compendium = builder.build();
+        let entry = compendium.lookup(None, "A friend in need is a friend indeed").unwrap();
+        assert_eq!(entry.candidates, vec![
+            CompendiumCandidate { translation: "海内存知己".to_string(), count: 2 },
+            CompendiumCandidate { translation: "患难见真情".to_string(), count: 1 },
+        ]);
+
+        let mut target = ts.clone();
+        target.clear_finished_messages();
+        compendium.apply_to_ts(&mut target);
+        let message = &target.contexts[0].messages[0];
+        assert_eq!(message.translation.value.as_deref(), Some("海内存知己"));
+        assert_eq!(message.translator_comment.as_deref(), Some("Translation memory alternatives: 患难见真情 (1)"));
+
+        // the translator comment survives saving and loading
+        target.save_into_file(&project_root.join("target_zh_CN.ts")).unwrap();
+        let reloaded = Ts::load_from_file(&project_root.join("target_zh_CN.ts")).unwrap();
+        assert_eq!(reloaded.contexts[0].messages[0].translator_comment, message.translator_comment);
+    }
+}
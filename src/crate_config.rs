@@ -0,0 +1,174 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+// Tool-specific project config file, placed at `project_root/.txconfig.toml`.
+
+use std::collections::BTreeMap;
+use std::{fs, path::{Path, PathBuf}};
+use serde::Deserialize;
+use thiserror::Error as TeError;
+
+/// File name of the tool config, relative to the project root.
+pub const CRATE_CONFIG_FILE_NAME: &str = ".txconfig.toml";
+
+/// Content of the tool config file.
+#[derive(Debug, Default, Deserialize)]
+pub struct CrateConfig {
+    /// Languages that every resource must ship a translation file for.
+    #[serde(default)]
+    pub required_languages: Vec<String>,
+    /// Per-filter overrides, keyed by the filter's `source_file`.
+    #[serde(default)]
+    pub filter_overrides: BTreeMap<String, FilterOverride>,
+    /// Language aliases, e.g. `zh_HK = "zh_TW"` means `zh_HK` may fall back to `zh_TW`.
+    #[serde(default)]
+    pub language_fallbacks: BTreeMap<String, String>,
+    /// Whether symlinked or fallback translation files satisfy a required language.
+    #[serde(default)]
+    pub fallback_policy: FallbackPolicy,
+    /// Glossary file checked by the `check` subcommand, relative to the project root.
+    /// See [`crate::glossary`] for the format.
+    pub glossary: Option<PathBuf>,
+    /// Default Transifex host of generated `.tx/config` files.
+    pub host: Option<String>,
+    /// Default template of slugs of resources missing in the lookup table, see
+    /// [`crate::transifex::resource_slug::resource_slug_from_template`].
+    pub fallback_template: Option<String>,
+    /// Default `lang_map` entries of generated `.tx/config` files, mapping Transifex language codes
+    /// to local ones. Entries of the `language_mapping` of `transifex.yaml` take precedence.
+    #[serde(default)]
+    pub lang_aliases: BTreeMap<String, String>,
+}
+
+/// Settings overriding the global ones for a single filter.
+#[derive(Debug, Default, Deserialize)]
+pub struct FilterOverride {
+    /// Replaces the global `required_languages` list for this filter if provided.
+    pub required_languages: Option<Vec<String>>,
+}
+
+/// Which translation files satisfy a required language.
+#[derive(Debug, Default, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum FallbackPolicy {
+    /// Only regular translation files count.
+    #[default]
+    Strict,
+    /// Symlinked translation files count as well.
+    AllowSymlink,
+    /// Symlinked files count, and a missing language is satisfied by its fallback language.
+    AllowFallback,
+}
+
+/// Error of loading the tool config file.
+#[derive(TeError, Debug)]
+pub enum CrateConfigLoadError {
+    /// The file doesn't exist.
+    #[error("File {0:?} not found")]
+    FileNotFound(PathBuf),
+    /// The file exists but can't be read.
+    #[error("Can not read file {0:?}: {1}")]
+    ReadFile(PathBuf, #[source] std::io::Error),
+    /// The file content is malformed.
+    #[error("Fail to deserialize file: {0}")]
+    Serde(#[from] toml::de::Error),
+}
+
+impl CrateConfig {
+    /// Parse tool config content.
+    pub fn from_str(content: &str) -> Result<Self, CrateConfigLoadError> {
+        Ok(toml::from_str::<CrateConfig>(content)?)
+    }
+
+    /// Load `project_root/.txconfig.toml` if it exists.
+    ///
+    /// Returns `None` if the file doesn't exist, since the file is optional.
+    pub fn load(project_root: &Path) -> Result<Option<CrateConfig>, CrateConfigLoadError> {
+        match try_load_crate_config_file(project_root)? {
+            (Some(_), crate_config) => Ok(Some(crate_config)),
+            (None, _) => Ok(None),
+        }
+    }
+
+    /// Get the required languages of the given filter, with per-filter overrides applied.
+    pub fn required_languages_for(&self, source_file: &str) -> &[String] {
+        match self.filter_overrides.get(source_file).and_then(|o| o.required_languages.as_ref()) {
+            Some(languages) => languages,
+            None => &self.required_languages,
+        }
+    }
+}
+
+/// Load the given tool config file.
+pub fn load_crate_config_file(crate_config_file: &Path) -> Result<CrateConfig, CrateConfigLoadError> {
+    if !crate_config_file.is_file() {
+        return Err(CrateConfigLoadError::FileNotFound(crate_config_file.to_path_buf()));
+    }
+    let source_content = fs::read_to_string(crate_config_file)
+        .map_err(|e| CrateConfigLoadError::ReadFile(crate_config_file.to_path_buf(), e))?;
+    CrateConfig::from_str(&source_content)
+}
+
+/// Try loading `project_root/.txconfig.toml`.
+///
+/// Returns default config if the file does not exist, since the file is optional.
+pub fn try_load_crate_config_file(project_root: &Path) -> Result<(Option<PathBuf>, CrateConfig), CrateConfigLoadError> {
+    let crate_config_file = project_root.join(CRATE_CONFIG_FILE_NAME);
+    if crate_config_file.is_file() {
+        let crate_config = load_crate_config_file(&crate_config_file)?;
+        return Ok((Some(crate_config_file), crate_config));
+    }
+    Ok((None, CrateConfig::default()))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    pub const TEST_CRATE_CONFIG_CONTENT: &str = r#"required_languages = ["zh_CN", "zh_TW", "zh_HK"]
+fallback_policy = "allow-fallback"
+
+glossary = "docs/glossary.toml"
+host = "https://app.transifex.com"
+
+[lang_aliases]
+zh_Hans = "zh_CN"
+
+[language_fallbacks]
+zh_HK = "zh_TW"
+
+[filter_overrides."translations/desktop/desktop.ts"]
+required_languages = ["zh_CN"]
+"#;
+
+    #[test]
+    fn tst_parse_crate_config_content() {
+        let crate_config = CrateConfig::from_str(TEST_CRATE_CONFIG_CONTENT).unwrap();
+        assert_eq!(crate_config.required_languages, vec!["zh_CN", "zh_TW", "zh_HK"]);
+        assert_eq!(crate_config.fallback_policy, FallbackPolicy::AllowFallback);
+        assert_eq!(crate_config.glossary, Some(PathBuf::from("docs/glossary.toml")));
+        assert_eq!(crate_config.host.as_deref(), Some("https://app.transifex.com"));
+        assert_eq!(crate_config.fallback_template, None);
+        assert_eq!(crate_config.lang_aliases.get("zh_Hans"), Some(&"zh_CN".to_string()));
+        assert_eq!(crate_config.language_fallbacks.get("zh_HK"), Some(&"zh_TW".to_string()));
+        assert_eq!(crate_config.required_languages_for("translations/desktop/desktop.ts"), ["zh_CN"]);
+        assert_eq!(crate_config.required_languages_for("translations/other.ts").len(), 3);
+    }
+
+    #[test]
+    fn tst_load_crate_config() {
+        let project_root = std::env::temp_dir().join("deepin-translation-utils-tests").join("tst_load_crate_config");
+        if project_root.exists() {
+            fs::remove_dir_all(&project_root).unwrap();
+        }
+        fs::create_dir_all(&project_root).unwrap();
+        assert!(CrateConfig::load(&project_root).unwrap().is_none());
+
+        fs::write(project_root.join(CRATE_CONFIG_FILE_NAME), TEST_CRATE_CONFIG_CONTENT).unwrap();
+        assert!(CrateConfig::load(&project_root).unwrap().is_some());
+
+        fs::write(project_root.join(CRATE_CONFIG_FILE_NAME), "host = [").unwrap();
+        assert!(matches!(CrateConfig::load(&project_root), Err(CrateConfigLoadError::Serde(_))));
+    }
+}
@@ -0,0 +1,194 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+//! Crate-wide error convention.
+//!
+//! Every load or save error is a [`ContextError`], i.e. an error kind describing what went wrong,
+//! plus an [`ErrorLocation`] describing where. Display always leads with `path:line:col` when known,
+//! and the message of the kind already includes its source, so `source()` returns `None` and error
+//! chains don't repeat themselves. Use [`ContextError::kind`] to inspect the underlying error.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use thiserror::Error as TeError;
+
+use crate::crate_config::CrateConfigLoadError;
+use crate::i18n_file::gettext::{PoLoadError, PoSaveError};
+use crate::i18n_file::linguist::{TsLoadError, TsSaveError};
+use crate::transifex::tx_config_file::TxConfigLoadError;
+use crate::transifex::yaml_file::TxYamlLoadError;
+
+/// Where an error happened. Every part is optional.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ErrorLocation {
+    /// The offending file.
+    pub path: Option<PathBuf>,
+    /// 1-based line number.
+    pub line: Option<usize>,
+    /// 1-based column number, only shown together with the line number.
+    pub column: Option<usize>,
+}
+
+impl ErrorLocation {
+    /// Whether nothing about the location is known.
+    pub fn is_empty(&self) -> bool {
+        self.path.is_none() && self.line.is_none()
+    }
+}
+
+impl fmt::Display for ErrorLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::<String>::new();
+        if let Some(path) = &self.path {
+            parts.push(path.display().to_string());
+        }
+        if let Some(line) = self.line {
+            parts.push(line.to_string());
+            if let Some(column) = self.column {
+                parts.push(column.to_string());
+            }
+        }
+        write!(f, "{}", parts.join(":"))
+    }
+}
+
+/// An error kind with its location, see the [module docs](self).
+#[derive(Debug)]
+pub struct ContextError<K> {
+    kind: K,
+    location: ErrorLocation,
+}
+
+impl<K> ContextError<K> {
+    /// Create an error of the given kind without location.
+    pub fn new(kind: K) -> Self {
+        Self { kind, location: ErrorLocation::default() }
+    }
+
+    /// Create a closure wrapping an error kind with the given path, for use with `map_err`.
+    pub fn in_file(path: impl AsRef<Path>) -> impl FnOnce(K) -> Self {
+        move |kind| Self::new(kind).with_path(path)
+    }
+
+    /// What went wrong.
+    pub fn kind(&self) -> &K {
+        &self.kind
+    }
+
+    /// Drop the location and return the kind.
+    pub fn into_kind(self) -> K {
+        self.kind
+    }
+
+    /// Where it went wrong.
+    pub fn location(&self) -> &ErrorLocation {
+        &self.location
+    }
+
+    /// The offending file, if known.
+    pub fn path(&self) -> Option<&Path> {
+        self.location.path.as_deref()
+    }
+
+    /// Attach the given path, unless the error already has one.
+    pub fn with_path(mut self, path: impl AsRef<Path>) -> Self {
+        if self.location.path.is_none() {
+            self.location.path = Some(path.as_ref().to_path_buf());
+        }
+        self
+    }
+
+    /// Attach the given line and column.
+    pub fn at(mut self, line: usize, column: Option<usize>) -> Self {
+        self.location.line = Some(line);
+        self.location.column = column;
+        self
+    }
+}
+
+impl<K> From<K> for ContextError<K> {
+    fn from(kind: K) -> Self {
+        Self::new(kind)
+    }
+}
+
+impl<K: fmt::Display> fmt::Display for ContextError<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.location.is_empty() {
+            write!(f, "{}", self.kind)
+        } else {
+            write!(f, "{}: {}", self.location, self.kind)
+        }
+    }
+}
+
+impl<K: std::error::Error + 'static> std::error::Error for ContextError<K> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        None
+    }
+}
+
+/// Attach location context to the error of a result, so call sites stay terse.
+pub trait ResultExt<T, K> {
+    /// See [`ContextError::with_path`].
+    fn with_path(self, path: impl AsRef<Path>) -> Result<T, ContextError<K>>;
+}
+
+impl<T, K> ResultExt<T, K> for Result<T, ContextError<K>> {
+    fn with_path(self, path: impl AsRef<Path>) -> Result<T, ContextError<K>> {
+        self.map_err(|e| e.with_path(path))
+    }
+}
+
+/// Any error of the loaders and savers of this crate, for applications which don't need to tell them apart.
+#[derive(TeError, Debug)]
+pub enum Error {
+    /// See [`TxYamlLoadError`].
+    #[error(transparent)]
+    TxYaml(#[from] TxYamlLoadError),
+    /// See [`TxConfigLoadError`].
+    #[error(transparent)]
+    TxConfig(#[from] TxConfigLoadError),
+    /// See [`CrateConfigLoadError`].
+    #[error(transparent)]
+    CrateConfig(#[from] CrateConfigLoadError),
+    /// See [`TsLoadError`].
+    #[error(transparent)]
+    TsLoad(#[from] TsLoadError),
+    /// See [`TsSaveError`].
+    #[error(transparent)]
+    TsSave(#[from] TsSaveError),
+    /// See [`PoLoadError`].
+    #[error(transparent)]
+    PoLoad(#[from] PoLoadError),
+    /// See [`PoSaveError`].
+    #[error(transparent)]
+    PoSave(#[from] PoSaveError),
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[derive(TeError, Debug)]
+    enum TestErrorKind {
+        #[error("Can not read file because: {0}")]
+        Read(#[source] std::io::Error),
+    }
+
+    #[test]
+    fn tst_context_error_display() {
+        let io_error = || std::io::Error::other("disk on fire");
+        let err = ContextError::new(TestErrorKind::Read(io_error()));
+        assert_eq!(err.to_string(), "Can not read file because: disk on fire");
+        let err = err.with_path("a/b.ts").with_path("ignored.ts");
+        assert_eq!(err.to_string(), "a/b.ts: Can not read file because: disk on fire");
+        assert_eq!(err.at(3, Some(7)).to_string(), "a/b.ts:3:7: Can not read file because: disk on fire");
+
+        let err = Err::<(), _>(TestErrorKind::Read(io_error())).map_err(ContextError::in_file("c.po")).unwrap_err();
+        assert_eq!(err.path(), Some(Path::new("c.po")));
+        assert!(std::error::Error::source(&err).is_none());
+        assert!(matches!(err.kind(), TestErrorKind::Read(_)));
+    }
+}
@@ -0,0 +1,90 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+// Whole-file read access, which memory-maps large files instead of copying them into memory.
+
+use std::fs::File;
+use std::io::Read;
+use std::ops::Deref;
+use std::path::Path;
+use memmap2::Mmap;
+
+/// Files at least this large are memory-mapped.
+pub(crate) const MMAP_THRESHOLD: u64 = 1024 * 1024;
+
+pub(crate) enum FileContent {
+    Mapped(Mmap),
+    Buffered(Vec<u8>),
+}
+
+impl Deref for FileContent {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Self::Mapped(mmap) => mmap,
+            Self::Buffered(buffer) => buffer,
+        }
+    }
+}
+
+/// Read the whole content of the given file, memory-mapping it if it's larger than [`MMAP_THRESHOLD`].
+///
+/// The file must not be truncated by another process while the content is alive, see the comment
+/// on mapping in [`read_file_with_threshold`].
+pub(crate) fn read_file(file_path: &Path) -> std::io::Result<FileContent> {
+    read_file_with_threshold(file_path, MMAP_THRESHOLD)
+}
+
+pub(crate) fn read_file_with_threshold(file_path: &Path, mmap_threshold: u64) -> std::io::Result<FileContent> {
+    let mut file = File::open(file_path)?;
+    let len = file.metadata()?.len();
+    if len >= mmap_threshold && len > 0 {
+        // SAFETY: the mapping is read-only and never outlives `FileContent`, but it's not protected
+        // against other processes. Content rewritten in place may change under us, and if the file
+        // is truncated while mapped, touching the lost pages raises SIGBUS and kills the process,
+        // where a buffered read would only see short or mixed content. We accept this for large
+        // files only, since translation files are not expected to change while we process them,
+        // e.g. don't run `tx pull` at the same time.
+        // Some filesystems don't support mapping, use buffered read for them instead.
+        if let Ok(mmap) = unsafe { Mmap::map(&file) } {
+            return Ok(FileContent::Mapped(mmap));
+        }
+    }
+    let mut buffer = Vec::with_capacity(len as usize);
+    file.read_to_end(&mut buffer)?;
+    Ok(FileContent::Buffered(buffer))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use std::fs;
+    use crate::i18n_file::gettext::tests::TEST_ZH_CN_PO_CONTENT;
+    use crate::i18n_file::linguist::tests::TEST_ZH_CN_TS_CONTENT;
+    use crate::transifex::yaml_file::tests::create_test_project_dir;
+
+    #[test]
+    fn tst_read_file_mapped_equals_buffered() {
+        let test_dir = create_test_project_dir("tst_read_file_mapped_equals_buffered");
+        let large_content = TEST_ZH_CN_TS_CONTENT.repeat(4096);
+        let fixtures = [
+            ("app_zh_CN.ts", TEST_ZH_CN_TS_CONTENT),
+            ("app_zh_CN.po", TEST_ZH_CN_PO_CONTENT),
+            ("app_zh_CN.json", r#"{"hello": "世界"}"#),
+            ("empty.ts", ""),
+            ("large_zh_CN.ts", large_content.as_str()),
+        ];
+        for (file_name, content) in fixtures {
+            let file_path = test_dir.join(file_name);
+            fs::write(&file_path, content).unwrap();
+            let mapped = read_file_with_threshold(&file_path, 0).unwrap();
+            let buffered = read_file_with_threshold(&file_path, u64::MAX).unwrap();
+            assert!(matches!(buffered, FileContent::Buffered(_)));
+            assert_eq!(&*mapped, &*buffered);
+            assert_eq!(&*mapped, content.as_bytes());
+        }
+        assert!(matches!(read_file(&test_dir.join("large_zh_CN.ts")).unwrap(), FileContent::Mapped(_)));
+    }
+}
@@ -0,0 +1,312 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+//! Terminology consistency checking against a bilingual glossary.
+//!
+//! A glossary is a TOML file listing source terms, and for each language the approved translations
+//! and forbidden variants:
+//!
+//! ```toml
+//! [[terms]]
+//! source = "dock"
+//!
+//! [terms.targets.zh_CN]
+//! approved = ["任务栏"]
+//! forbidden = ["停靠栏"]
+//! ```
+//!
+//! Terms are matched on word boundaries, except terms containing CJK characters, which are
+//! matched as substrings since CJK text doesn't separate words.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use polib::message::MessageView;
+use regex::Regex;
+use serde::Deserialize;
+use thiserror::Error as TeError;
+
+use crate::i18n_file::common::same_language;
+use crate::i18n_file::gettext::Po;
+use crate::i18n_file::linguist::Ts;
+
+/// Content of a glossary file.
+#[derive(Debug, Default, Deserialize)]
+pub struct Glossary {
+    /// Glossary terms.
+    #[serde(default)]
+    pub terms: Vec<GlossaryTerm>,
+    #[serde(skip)]
+    matchers: HashMap<(String, bool), Regex>,
+}
+
+/// A source term and its translations.
+#[derive(Debug, Deserialize)]
+pub struct GlossaryTerm {
+    /// Source term, e.g. `dock`.
+    pub source: String,
+    /// Match the source term and its translations case-sensitively.
+    #[serde(default)]
+    pub case_sensitive: bool,
+    /// Translations, keyed by language code.
+    #[serde(default)]
+    pub targets: BTreeMap<String, TermTargets>,
+}
+
+/// Translations of a term for a single language.
+#[derive(Debug, Default, Deserialize)]
+pub struct TermTargets {
+    /// Translations of which at least one must be used.
+    #[serde(default)]
+    pub approved: Vec<String>,
+    /// Translations which must not be used.
+    #[serde(default)]
+    pub forbidden: Vec<String>,
+}
+
+/// How a translation violates a glossary term.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TermViolationKind {
+    /// The translation uses the given forbidden variant.
+    Forbidden(String),
+    /// The translation uses none of the approved translations.
+    MissingApproved,
+}
+
+/// A finished translation violating a glossary term.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TermViolation {
+    /// TS context name or PO `msgctxt` of the message, if any.
+    pub context: Option<String>,
+    /// Source text of the message.
+    pub source: String,
+    /// Translation of the message.
+    pub translation: String,
+    /// The violated source term.
+    pub term: String,
+    /// How the term is violated.
+    pub kind: TermViolationKind,
+}
+
+/// Error of loading glossary files.
+#[derive(TeError, Debug)]
+pub enum GlossaryLoadError {
+    /// The file can't be read.
+    #[error("Can not read file {0:?}: {1}")]
+    ReadFile(PathBuf, #[source] std::io::Error),
+    /// The file content is malformed.
+    #[error("Fail to deserialize file: {0}")]
+    Serde(#[from] toml::de::Error),
+    /// The matcher of the given term can't be built, e.g. because the term is too long.
+    #[error("Fail to create matcher of term {0:?}: {1}")]
+    Term(String, #[source] regex::Error),
+}
+
+/// Upper bound of the compiled size of a term matcher regex.
+const TERM_PATTERN_SIZE_LIMIT: usize = 1 << 20;
+
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30ff}' // Hiragana and Katakana
+        | '\u{3400}'..='\u{4dbf}' | '\u{4e00}'..='\u{9fff}' | '\u{f900}'..='\u{faff}' // CJK ideographs
+        | '\u{ac00}'..='\u{d7af}' // Hangul syllables
+        | '\u{ff00}'..='\u{ffef}') // full width forms
+}
+
+fn create_term_matcher(term: &str, case_sensitive: bool) -> Result<Regex, GlossaryLoadError> {
+    let flags = if case_sensitive { "" } else { "(?i)" };
+    let pattern = if term.chars().any(is_cjk) {
+        format!("{flags}{}", regex::escape(term))
+    } else {
+        // not `\b`, which never matches next to terms starting or ending with punctuation like `C++`
+        format!(r"{flags}(?:^|\W){}(?:$|\W)", regex::escape(term))
+    };
+    // escaped terms are always valid patterns, but may still be too large
+    regex::RegexBuilder::new(&pattern)
+        .size_limit(TERM_PATTERN_SIZE_LIMIT)
+        .build()
+        .map_err(|e| GlossaryLoadError::Term(term.to_string(), e))
+}
+
+impl Glossary {
+    /// Parse glossary content.
+    pub fn from_str(content: &str) -> Result<Self, GlossaryLoadError> {
+        let mut glossary = toml::from_str::<Glossary>(content)?;
+        let mut matchers = HashMap::new();
+        for term in &glossary.terms {
+            let variants = term.targets.values().flat_map(|targets| targets.approved.iter().chain(&targets.forbidden));
+            for text in std::iter::once(&term.source).chain(variants) {
+                let key = (text.clone(), term.case_sensitive);
+                if !matchers.contains_key(&key) {
+                    matchers.insert(key, create_term_matcher(text, term.case_sensitive)?);
+                }
+            }
+        }
+        glossary.matchers = matchers;
+        Ok(glossary)
+    }
+
+    /// Load the given glossary file.
+    pub fn load_from_file(glossary_file: &Path) -> Result<Self, GlossaryLoadError> {
+        let content = std::fs::read_to_string(glossary_file)
+            .map_err(|e| GlossaryLoadError::ReadFile(glossary_file.to_path_buf(), e))?;
+        Self::from_str(&content)
+    }
+
+    fn contains_term(&self, text: &str, term: &str, case_sensitive: bool) -> bool {
+        if term.is_empty() {
+            return false;
+        }
+        match self.matchers.get(&(term.to_string(), case_sensitive)) {
+            Some(matcher) => matcher.is_match(text),
+            // terms added after parsing, those whose matcher can't be built never match
+            None => create_term_matcher(term, case_sensitive).is_ok_and(|matcher| matcher.is_match(text)),
+        }
+    }
+
+    /// Check a single translation of the given language against all terms found in its source text.
+    ///
+    /// Returns the violated source terms and how they are violated.
+    pub fn check_translation(&self, language: &str, source: &str, translation: &str) -> Vec<(&str, TermViolationKind)> {
+        let mut violations = Vec::new();
+        for term in &self.terms {
+            if !self.contains_term(source, &term.source, term.case_sensitive) {
+                continue;
+            }
+            let Some(targets) = term.targets.iter().find(|(target_language, _)| same_language(target_language, language)).map(|(_, targets)| targets) else {
+                continue;
+            };
+            for forbidden in &targets.forbidden {
+                if self.contains_term(translation, forbidden, term.case_sensitive) {
+                    violations.push((term.source.as_str(), TermViolationKind::Forbidden(forbidden.clone())));
+                }
+            }
+            let has_approved = targets.approved.iter().any(|approved| self.contains_term(translation, approved, term.case_sensitive));
+            if !targets.approved.is_empty() && !has_approved {
+                violations.push((term.source.as_str(), TermViolationKind::MissingApproved));
+            }
+        }
+        violations
+    }
+
+    fn collect_violations(&self, language: &str, context: Option<&str>, source: &str, translation: &str, violations: &mut Vec<TermViolation>) {
+        for (term, kind) in self.check_translation(language, source, translation) {
+            violations.push(TermViolation {
+                context: context.filter(|context| !context.is_empty()).map(str::to_string),
+                source: source.to_string(),
+                translation: translation.to_string(),
+                term: term.to_string(),
+                kind,
+            });
+        }
+    }
+
+    /// Check finished translations of the given TS file, including every plural form.
+    pub fn check_ts(&self, ts: &Ts, language: &str) -> Vec<TermViolation> {
+        let mut violations = Vec::new();
+        for context in &ts.contexts {
+            for message in &context.messages {
+                if message.translation.type_attr.is_some() {
+                    continue;
+                }
+                let translations = message.translation.value.iter().chain(&message.translation.numerus_forms);
+                for translation in translations {
+                    self.collect_violations(language, Some(&context.name), &message.source, translation, &mut violations);
+                }
+            }
+        }
+        violations
+    }
+
+    /// Check translated, non-fuzzy singular messages of the given PO file.
+    pub fn check_po(&self, po: &Po, language: &str) -> Vec<TermViolation> {
+        let mut violations = Vec::new();
+        for message in po.inner.messages() {
+            if !message.is_translated() || message.is_plural() || message.flags().is_fuzzy() {
+                continue;
+            }
+            // singular messages always have msgstr
+            self.collect_violations(language, Some(message.msgctxt()), message.msgid(), message.msgstr().unwrap(), &mut violations);
+        }
+        violations
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    pub const TEST_GLOSSARY_CONTENT: &str = r#"[[terms]]
+source = "dock"
+
+[terms.targets.zh_CN]
+approved = ["任务栏"]
+forbidden = ["停靠栏"]
+
+[[terms]]
+source = "Qt"
+case_sensitive = true
+
+[terms.targets.zh_CN]
+approved = ["Qt"]
+"#;
+
+    #[test]
+    fn tst_term_too_long() {
+        let content = format!("[[terms]]\nsource = \"{}\"\n", "a".repeat(TERM_PATTERN_SIZE_LIMIT));
+        assert!(matches!(Glossary::from_str(&content), Err(GlossaryLoadError::Term(..))));
+    }
+
+    #[test]
+    fn tst_check_translation() {
+        let glossary = Glossary::from_str(TEST_GLOSSARY_CONTENT).unwrap();
+        assert!(glossary.check_translation("zh_CN", "Show the Dock", "显示任务栏").is_empty());
+        assert_eq!(glossary.check_translation("zh-CN", "Show the dock", "显示停靠栏"), vec![
+            ("dock", TermViolationKind::Forbidden("停靠栏".to_string())),
+            ("dock", TermViolationKind::MissingApproved),
+        ]);
+        assert_eq!(glossary.check_translation("zh_CN", "Dock settings", "设置"), vec![("dock", TermViolationKind::MissingApproved)]);
+        // word boundaries for Latin source terms
+        assert!(glossary.check_translation("zh_CN", "Docker images", "镜像").is_empty());
+        // no targets for other languages
+        assert!(glossary.check_translation("ja", "Show the dock", "ドック").is_empty());
+        // case sensitive terms
+        assert!(glossary.check_translation("zh_CN", "qt.conf", "配置").is_empty());
+        assert_eq!(glossary.check_translation("zh_CN", "About Qt", "关于 QT"), vec![("Qt", TermViolationKind::MissingApproved)]);
+    }
+
+    #[test]
+    fn tst_check_ts() {
+        let glossary = Glossary::from_str(TEST_GLOSSARY_CONTENT).unwrap();
+        let ts = Ts::from_str(r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE TS><TS language="zh_CN" version="2.1">
+<context>
+    <name>Dock</name>
+    <message>
+        <source>Dock size</source>
+        <translation>停靠栏大小</translation>
+    </message>
+    <message>
+        <source>Dock position</source>
+        <translation type="unfinished">停靠栏位置</translation>
+    </message>
+</context>
+</TS>"#).unwrap();
+        assert_eq!(glossary.check_ts(&ts, "zh_CN"), vec![
+            TermViolation {
+                context: Some("Dock".to_string()),
+                source: "Dock size".to_string(),
+                translation: "停靠栏大小".to_string(),
+                term: "dock".to_string(),
+                kind: TermViolationKind::Forbidden("停靠栏".to_string()),
+            },
+            TermViolation {
+                context: Some("Dock".to_string()),
+                source: "Dock size".to_string(),
+                translation: "停靠栏大小".to_string(),
+                term: "dock".to_string(),
+                kind: TermViolationKind::MissingApproved,
+            },
+        ]);
+    }
+}
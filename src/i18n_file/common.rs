@@ -2,10 +2,11 @@
 //
 // SPDX-License-Identifier: MIT
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 use thiserror::Error as TeError;
 
+/// Kind of translation file.
 pub enum I18nFileKind {
     /// Qt Linguist translation file format (.ts)
     Linguist,
@@ -13,6 +14,7 @@ pub enum I18nFileKind {
     Gettext,
 }
 
+/// Error of detecting [`I18nFileKind`] from an unknown file extension.
 #[derive(TeError, Debug)]
 #[error("Unknow translation file extension {ext:?}")]
 pub struct UnknownI18nFileExtError {
@@ -44,15 +46,26 @@ impl I18nFileKind {
     }
 }
 
-#[derive(Debug, Default, Serialize, PartialEq)]
+/// Whether the given language codes are the same, ignoring case and `-`/`_` differences, e.g. `zh-CN` and `zh_CN`.
+pub(crate) fn same_language(lhs: &str, rhs: &str) -> bool {
+    lhs.replace('-', "_").eq_ignore_ascii_case(&rhs.replace('-', "_"))
+}
+
+/// Message count of a translation file, by translation state.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
 pub struct MessageStats {
+    /// Translated messages.
     pub finished: u64,
+    /// Untranslated messages.
     pub unfinished: u64,
+    /// Messages removed from the source code.
     pub vanished: u64,
+    /// Messages marked obsolete.
     pub obsolete: u64,
 }
 
 impl MessageStats {
+    /// Percentage of finished messages among finished and unfinished ones.
     pub fn completeness_percentage(&self) -> f64 {
         let total = self.finished + self.unfinished;
         if total == 0 {
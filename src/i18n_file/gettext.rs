@@ -2,6 +2,8 @@
 //
 // SPDX-License-Identifier: MIT
 
+use std::fs::File;
+use std::io::{BufRead, Read};
 use std::path::Path;
 use polib::message::{MessageMutView, MessageView};
 use polib::po_file::{self, POParseError};
@@ -10,12 +12,15 @@ use super::common::MessageStats;
 
 // ===== PO Basic =====
 
+/// A GNU Gettext translation file.
 #[derive(Debug, Clone)]
 pub struct Po {
+    /// The parsed catalog.
     pub inner: polib::catalog::Catalog,
 }
 
 impl Po {
+    /// Drop translations of finished singular messages.
     pub fn clear_finished_messages(&mut self) {
         let catalog = &mut self.inner;
         for mut message in catalog.messages_mut() {
@@ -29,14 +34,17 @@ impl Po {
 }
 
 impl Po {
+    /// Get the language code of the translation.
     pub fn get_language(&self) -> String {
         self.inner.metadata.language.clone()
     }
 
+    /// Set the language code of the translation.
     pub fn set_language(&mut self, language: &str) {
         self.inner.metadata.language = language.to_string();
     }
 
+    /// Count messages by translation state.
     pub fn get_message_stats(&self) -> MessageStats {
         let mut stats = MessageStats::default();
         for message in self.inner.messages() {
@@ -52,33 +60,110 @@ impl Po {
 
 // ===== PO Load & Save =====
 
+/// Error of loading PO files.
 #[derive(TeError, Debug)]
 pub enum PoLoadError {
+    /// The file can't be opened.
+    #[error("Can not open file")]
+    ReadFile(#[from] std::io::Error),
+    /// The content can't be read or parsed.
     #[error("Fail to parse PO file: {0}")]
     ParsePo(#[from] POParseError),
+    /// The content is structurally broken, e.g. a `msgid_plural` without `msgid`, see [`check_po_structure`].
+    #[error("Invalid PO content: {0}")]
+    Malformed(String),
 }
 
+/// Check the structure PO content must have before it is handed to the parser, which doesn't
+/// handle some malformed content gracefully:
+///
+/// - every string is terminated by a `"` on the same line,
+/// - `msgid_plural` directly follows a `msgid`,
+/// - `msgstr[N]` indices of a message count up from 0.
+///
+/// Comments, including obsolete `#~` messages, are not checked.
+pub fn check_po_structure(content: &str) -> Result<(), PoLoadError> {
+    let malformed = |line_idx: usize, reason: &str| PoLoadError::Malformed(format!("line {}: {reason}", line_idx + 1));
+    let mut last_keyword = "";
+    let mut next_plural_index = 0usize;
+    for (line_idx, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (keyword, string) = match line.split_once(char::is_whitespace) {
+            _ if line.starts_with('"') => ("", line),
+            Some((keyword, string)) => (keyword, string.trim_start()),
+            None => (line, ""),
+        };
+        if !is_terminated_po_string(string) {
+            return Err(malformed(line_idx, "unterminated string"));
+        }
+        match keyword {
+            "" => continue,
+            "msgid_plural" if last_keyword != "msgid" => return Err(malformed(line_idx, "msgid_plural without msgid")),
+            "msgid" | "msgid_plural" => next_plural_index = 0,
+            _ => if let Some(index) = keyword.strip_prefix("msgstr[").and_then(|rest| rest.strip_suffix(']')) {
+                if index.parse::<usize>().ok() != Some(next_plural_index) {
+                    return Err(malformed(line_idx, "msgstr index out of order"));
+                }
+                next_plural_index += 1;
+            },
+        }
+        last_keyword = keyword;
+    }
+    Ok(())
+}
+
+/// Whether the given PO string literal, e.g. `"a \"b\""`, has both quotes.
+fn is_terminated_po_string(string: &str) -> bool {
+    let Some(inner) = string.strip_prefix('"') else {
+        return false;
+    };
+    let mut escaped = false;
+    for (idx, c) in inner.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return inner[idx + 1..].trim().is_empty(),
+            _ => {},
+        }
+    }
+    false
+}
+
+/// Error of saving PO files.
 #[derive(TeError, Debug)]
 pub enum PoSaveError {
+    /// The file can't be written.
     #[error("Fail to save PO file: {0}")]
     WritePo(#[from] std::io::Error),
 }
 
 impl Po {
+    /// Load the given PO file.
     pub fn load_from_file(po_file: &Path) -> Result<Po, PoLoadError> {
-        Ok(Po {
-            inner: po_file::parse(po_file)?,
-        })
+        let file = File::open(po_file)?;
+        Self::from_reader(std::io::BufReader::new(file))
     }
 
-    #[cfg(test)]
-    pub fn load_from_str(content: &str) -> Result<Po, PoLoadError> {
-        let reader = std::io::Cursor::new(content.as_bytes());
-        Ok(Po {
-            inner: po_file::parse_from_reader(reader)?
-        })
+    /// Parse PO content.
+    pub fn from_str(content: &str) -> Result<Po, PoLoadError> {
+        Self::from_reader(content.as_bytes())
     }
 
+    /// Parse PO content from the given reader, e.g. a git blob or an HTTP response body.
+    ///
+    /// The content is checked with [`check_po_structure`] first.
+    pub fn from_reader<R: BufRead>(mut reader: R) -> Result<Po, PoLoadError> {
+        let mut content = Vec::<u8>::new();
+        reader.read_to_end(&mut content)?;
+        check_po_structure(&String::from_utf8_lossy(&content))?;
+        let inner = po_file::parse_from_reader(content.as_slice())?;
+        Ok(Po { inner })
+    }
+
+    /// Load the given PO file, or create an untranslated copy of `fallback` for the given language if it doesn't exist.
     pub fn load_from_file_or_default(po_file: &Path, fallback: &Po, fallback_language_code: &str) -> Result<Po, PoLoadError> {
         if !po_file.exists() {
             let mut po = fallback.clone();
@@ -90,6 +175,7 @@ impl Po {
         }
     }
 
+    /// Save into the given PO file.
     pub fn save_into_file(&self, po_file: &Path) -> Result<(), PoSaveError> {
         po_file::write_to_file(&self.inner, po_file)?;
         Ok(())
@@ -133,7 +219,7 @@ msgstr ""
 
     #[test]
     fn tst_parse_po_content() {
-        let po = Po::load_from_str(TEST_ZH_CN_PO_CONTENT).unwrap();
+        let po = Po::from_str(TEST_ZH_CN_PO_CONTENT).unwrap();
         assert_eq!(po.get_language(), "zh_CN");
         assert_eq!(po.get_message_stats(), MessageStats {
             finished: 2,
@@ -143,4 +229,48 @@ msgstr ""
         });
         assert_eq!(po.get_message_stats().completeness_percentage(), 2.0 / 4.0 * 100.0);
     }
+
+    #[test]
+    fn tst_parse_malformed_po_content() {
+        assert!(check_po_structure(TEST_ZH_CN_PO_CONTENT).is_ok());
+        assert!(check_po_structure("msgid \"a \\\"b\\\"\"\nmsgid_plural \"c\"\nmsgstr[0] \"\"\nmsgstr[1] \"\"\n").is_ok());
+        for content in [
+            "msgid \"Hello\nmsgstr \"\"\n",
+            "msgid \"a\\\"\nmsgstr \"\"\n",
+            "msgid_plural \"Files\"\nmsgstr[0] \"\"\n",
+            "msgid \"File\"\nmsgid_plural \"Files\"\nmsgstr[99999999999999999999] \"\"\n",
+            "msgid \"File\"\nmsgid_plural \"Files\"\nmsgstr[1] \"\"\n",
+        ] {
+            assert!(matches!(Po::from_str(content), Err(PoLoadError::Malformed(_))), "{content:?}");
+        }
+    }
+
+    fn po_text() -> impl proptest::strategy::Strategy<Value = String> {
+        "[a-zA-Z0-9\"\\\\]([a-zA-Z0-9\"\\\\ ]{0,16}[a-zA-Z0-9\"\\\\])?"
+    }
+
+    fn escape_po_string(text: &str) -> String {
+        text.replace('\\', "\\\\").replace('"', "\\\"")
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn tst_prop_po_roundtrip(messages in proptest::collection::btree_map(po_text(), proptest::option::of(po_text()), 1..8)) {
+            let mut content = TEST_ZH_CN_PO_CONTENT.split("\n\n").next().unwrap().to_string();
+            for (msgid, msgstr) in &messages {
+                content.push_str(&format!("\n\nmsgid \"{}\"\nmsgstr \"{}\"", escape_po_string(msgid), escape_po_string(msgstr.as_deref().unwrap_or(""))));
+            }
+            content.push('\n');
+            let po = Po::from_str(&content).unwrap();
+
+            let po_file = std::env::temp_dir().join("deepin-translation-utils-tests").join("tst_prop_po_roundtrip.po");
+            std::fs::create_dir_all(po_file.parent().unwrap()).unwrap();
+            po.save_into_file(&po_file).unwrap();
+            let reloaded = Po::load_from_file(&po_file).unwrap();
+            proptest::prop_assert_eq!(reloaded.get_language(), po.get_language());
+            proptest::prop_assert_eq!(reloaded.get_message_stats(), po.get_message_stats());
+            let msgids = |po: &Po| po.inner.messages().map(|message| message.msgid().to_string()).collect::<Vec<String>>();
+            proptest::prop_assert_eq!(msgids(&reloaded), messages.keys().cloned().collect::<Vec<String>>());
+        }
+    }
 }
\ No newline at end of file
@@ -5,24 +5,29 @@
 // Linguist .ts XML file spec: https://doc.qt.io/qt-6/linguist-ts-file-format.html
 
 use std::fs::File;
+use std::io::BufRead;
 use std::path::Path;
 use thiserror::Error as TeError;
 use serde::{Deserialize, Serialize};
 use quick_xml::DeError;
 use quick_xml::se::SeError;
-use quick_xml::Writer;
-use quick_xml::events::{BytesDecl, BytesText, Event};
+use quick_xml::{Reader, Writer};
+use quick_xml::events::{BytesDecl, BytesStart, BytesText, Event};
 use super::common::MessageStats;
 
 // ===== TS Basic =====
 
+/// A Qt Linguist translation file.
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename = "TS")]
 pub struct Ts {
+    /// Language code of the translation, absent in source files.
     #[serde(rename = "@language")]
     pub language: Option<String>,
+    /// TS format version.
     #[serde(rename = "@version")]
     pub version: String,
+    /// Translation contexts, usually one per class.
     #[serde(rename = "context")]
     pub contexts: Vec<Context>,
 }
@@ -30,6 +35,7 @@ pub struct Ts {
 // === TS Unique ===
 
 impl Ts {
+    /// Drop translations of finished messages and mark them unfinished.
     pub fn clear_finished_messages(&mut self) {
         for context in &mut self.contexts {
             for message in &mut context.messages {
@@ -46,14 +52,17 @@ impl Ts {
 // === TS Common ===
 
 impl Ts {
+    /// Get the language code of the translation.
     pub fn get_language(&self) -> Option<String> {
         self.language.clone()
     }
 
+    /// Set the language code of the translation.
     pub fn set_language(&mut self, language: &str) {
         self.language = Some(language.to_string());
     }
 
+    /// Count messages by translation state.
     pub fn get_message_stats(&self) -> MessageStats {
         let mut finished = 0;
         let mut unfinished = 0;
@@ -88,64 +97,90 @@ impl Ts {
 
 // === Sub Structs ===
 
+/// A translation context.
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Context {
+    /// Context name.
     #[serde(rename = "name")]
     pub name: String,
+    /// Messages of this context.
     #[serde(rename = "message")]
     pub messages: Vec<Message>,
 }
 
+/// A translatable message.
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Message {
+    /// Source code locations of the message.
     #[serde(rename = "location", default)]
     pub location: Vec<Location>,
+    /// Source text.
     #[serde(rename = "source")]
     pub source: String,
+    /// Translation of the source text.
     #[serde(rename = "translation")]
     pub translation: Translation,
+    /// Disambiguation comment.
     #[serde(rename = "comment", skip_serializing_if = "Option::is_none", default)]
     pub comment: Option<String>,
+    /// Comment of translators, not shown to developers.
+    #[serde(rename = "translatorcomment", skip_serializing_if = "Option::is_none", default)]
+    pub translator_comment: Option<String>,
+    /// `yes` if the message has plural forms.
     #[serde(rename = "@numerus", skip_serializing_if = "Option::is_none", default)]
     pub numerus: Option<String>,
 }
 
 impl Message {
+    /// Set the translation and mark the message finished.
     pub fn fill_translation(&mut self, translation: &str) {
         self.translation.value = Some(translation.to_string());
         self.translation.type_attr = None;
     }
 }
 
+/// State of a translation which is not finished.
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "lowercase")]
 pub enum TranslationType {
+    /// Not translated yet, or needs review.
     Unfinished,
+    /// The source text no longer exists.
     Vanished,
+    /// The message is obsolete.
     Obsolete,
 }
 
+/// Translation of a message.
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Translation {
+    /// Translation state, `None` means finished.
     #[serde(rename = "@type", skip_serializing_if = "Option::is_none", default)]
     pub type_attr: Option<TranslationType>,
+    /// Translated text of messages without plural forms.
     #[serde(rename = "$value")]
     pub value: Option<String>,
+    /// Translated text of each plural form.
     #[serde(rename = "numerusform", default)]
     pub numerus_forms: Vec<String>,
 }
 
+/// A source code location.
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Location {
+    /// Source file path, relative to the TS file. Absent means the previous file.
     #[serde(rename = "@filename", skip_serializing_if = "Option::is_none")]
     pub filename: Option<String>,
+    /// Line number, may be relative to the previous location.
     #[serde(rename = "@line")]
     pub line: String,
 }
 
 // ===== TS Load & Save =====
 
+/// Extension of [`Writer`] for writing TS files.
 pub trait WriterExt {
+    /// Write the XML declaration, doctype and the given TS content.
     fn write_linguist_ts_file(
         &mut self,
         content: &Ts,
@@ -163,34 +198,230 @@ impl<W: std::io::Write> WriterExt for Writer<W> {
     }
 }
 
+/// Error of loading TS files.
 #[derive(TeError, Debug)]
 pub enum TsLoadError {
+    /// The file can't be read.
     #[error("Can not open file")]
     ReadFile(#[from] std::io::Error),
+    /// Deserializing failed.
     #[error("Fail to deserialize file because: {0}")]
     Serde(#[from] DeError),
+    /// The content is not well-formed XML.
+    #[error("Fail to parse XML because: {0}")]
+    Xml(#[from] quick_xml::Error),
+    /// The XML is not a valid TS document.
+    #[error("Invalid TS content: {0}")]
+    Malformed(String),
 }
 
+// ===== TS Pull Parser =====
+
+/// Builds [`Ts`] from XML events, without going through serde.
+///
+/// Follows what the serde-based deserializer accepted: unknown elements are skipped,
+/// texts are trimmed, and missing required elements or attributes are errors.
+struct TsParser<R: BufRead> {
+    reader: Reader<R>,
+    buf: Vec<u8>,
+}
+
+fn malformed(message: &str) -> TsLoadError {
+    TsLoadError::Malformed(message.to_string())
+}
+
+fn attribute(element: &BytesStart, name: &str) -> Result<Option<String>, TsLoadError> {
+    match element.try_get_attribute(name).map_err(quick_xml::Error::from)? {
+        Some(attr) => Ok(Some(attr.unescape_value()?.into_owned())),
+        None => Ok(None),
+    }
+}
+
+impl<R: BufRead> TsParser<R> {
+    fn new(reader: R) -> Self {
+        let mut reader = Reader::from_reader(reader);
+        reader.config_mut().trim_text(true);
+        Self { reader, buf: Vec::new() }
+    }
+
+    fn next_event(&mut self) -> Result<Event<'static>, TsLoadError> {
+        self.buf.clear();
+        Ok(self.reader.read_event_into(&mut self.buf)?.into_owned())
+    }
+
+    /// Visit child elements until the end of current element, and return the text directly inside it.
+    ///
+    /// `on_child` is called with each child element and whether it is an empty element, and must consume
+    /// the whole child element.
+    fn read_children(&mut self, mut on_child: impl FnMut(&mut Self, &BytesStart, bool) -> Result<(), TsLoadError>) -> Result<String, TsLoadError> {
+        let mut text = String::new();
+        loop {
+            match self.next_event()? {
+                Event::Start(element) => on_child(self, &element, false)?,
+                Event::Empty(element) => on_child(self, &element, true)?,
+                Event::Text(content) => text.push_str(&content.unescape()?),
+                Event::CData(content) => text.push_str(&String::from_utf8_lossy(&content)),
+                Event::End(_) => return Ok(text),
+                Event::Eof => return Err(malformed("unexpected end of file")),
+                _ => {},
+            }
+        }
+    }
+
+    fn skip_element(&mut self, element: &BytesStart, empty: bool) -> Result<(), TsLoadError> {
+        if !empty {
+            self.reader.read_to_end_into(element.name(), &mut self.buf)?;
+        }
+        Ok(())
+    }
+
+    fn read_text(&mut self, empty: bool) -> Result<String, TsLoadError> {
+        if empty {
+            return Ok(String::new());
+        }
+        self.read_children(|parser, element, empty| parser.skip_element(element, empty))
+    }
+
+    fn parse_ts(&mut self) -> Result<Ts, TsLoadError> {
+        loop {
+            let (element, empty) = match self.next_event()? {
+                Event::Start(element) => (element, false),
+                Event::Empty(element) => (element, true),
+                Event::Eof => return Err(malformed("missing TS root element")),
+                _ => continue,
+            };
+            if element.name().as_ref() != b"TS" {
+                return Err(malformed("root element is not TS"));
+            }
+            let language = attribute(&element, "language")?;
+            let version = attribute(&element, "version")?.ok_or_else(|| malformed("missing version attribute of TS"))?;
+            let mut contexts = Vec::<Context>::new();
+            if !empty {
+                self.read_children(|parser, element, empty| match element.name().as_ref() {
+                    b"context" => {
+                        contexts.push(parser.parse_context(empty)?);
+                        Ok(())
+                    },
+                    _ => parser.skip_element(element, empty),
+                })?;
+            }
+            return Ok(Ts { language, version, contexts });
+        }
+    }
+
+    fn parse_context(&mut self, empty: bool) -> Result<Context, TsLoadError> {
+        let mut name = None;
+        let mut messages = Vec::<Message>::new();
+        if !empty {
+            self.read_children(|parser, element, empty| match element.name().as_ref() {
+                b"name" => {
+                    name = Some(parser.read_text(empty)?);
+                    Ok(())
+                },
+                b"message" => {
+                    messages.push(parser.parse_message(element, empty)?);
+                    Ok(())
+                },
+                _ => parser.skip_element(element, empty),
+            })?;
+        }
+        let name = name.ok_or_else(|| malformed("missing name of context"))?;
+        Ok(Context { name, messages })
+    }
+
+    fn parse_message(&mut self, element: &BytesStart, empty: bool) -> Result<Message, TsLoadError> {
+        let numerus = attribute(element, "numerus")?;
+        let mut location = Vec::<Location>::new();
+        let mut source = None;
+        let mut translation = None;
+        let mut comment = None;
+        let mut translator_comment = None;
+        if !empty {
+            self.read_children(|parser, element, empty| match element.name().as_ref() {
+                b"location" => {
+                    let filename = attribute(element, "filename")?;
+                    let line = attribute(element, "line")?.ok_or_else(|| malformed("missing line attribute of location"))?;
+                    location.push(Location { filename, line });
+                    parser.skip_element(element, empty)
+                },
+                b"source" => {
+                    source = Some(parser.read_text(empty)?);
+                    Ok(())
+                },
+                b"translation" => {
+                    translation = Some(parser.parse_translation(element, empty)?);
+                    Ok(())
+                },
+                b"comment" => {
+                    comment = Some(parser.read_text(empty)?);
+                    Ok(())
+                },
+                b"translatorcomment" => {
+                    translator_comment = Some(parser.read_text(empty)?);
+                    Ok(())
+                },
+                _ => parser.skip_element(element, empty),
+            })?;
+        }
+        let source = source.ok_or_else(|| malformed("missing source of message"))?;
+        let translation = translation.ok_or_else(|| malformed("missing translation of message"))?;
+        Ok(Message { location, source, translation, comment, translator_comment, numerus })
+    }
+
+    fn parse_translation(&mut self, element: &BytesStart, empty: bool) -> Result<Translation, TsLoadError> {
+        let type_attr = match attribute(element, "type")?.as_deref() {
+            None => None,
+            Some("unfinished") => Some(TranslationType::Unfinished),
+            Some("vanished") => Some(TranslationType::Vanished),
+            Some("obsolete") => Some(TranslationType::Obsolete),
+            Some(other) => return Err(TsLoadError::Malformed(format!("unknown translation type {other:?}"))),
+        };
+        let mut numerus_forms = Vec::<String>::new();
+        let value = if empty {
+            String::new()
+        } else {
+            self.read_children(|parser, element, empty| match element.name().as_ref() {
+                b"numerusform" => {
+                    numerus_forms.push(parser.read_text(empty)?);
+                    Ok(())
+                },
+                _ => parser.skip_element(element, empty),
+            })?
+        };
+        let value = if value.is_empty() { None } else { Some(value) };
+        Ok(Translation { type_attr, value, numerus_forms })
+    }
+}
+
+/// Error of saving TS files.
 #[derive(TeError, Debug)]
 pub enum TsSaveError {
+    /// The file can't be created or written.
     #[error("Can not create file")]
     CreateFile(#[from] std::io::Error),
+    /// Serializing failed.
     #[error("Fail to serialize file because: {0}")]
     Serde(#[from] SeError),
 }
 
 impl Ts {
+    /// Load the given TS file.
     pub fn load_from_file(linguist_ts_file: &Path) -> Result<Ts, TsLoadError> {
         let file = File::open(linguist_ts_file)?;
-        let file_reader = std::io::BufReader::new(file);
-        Ok(quick_xml::de::from_reader::<_, Ts>(file_reader)?)
+        Self::from_reader(std::io::BufReader::new(file))
+    }
+
+    /// Parse TS content.
+    pub fn from_str(content: &str) -> Result<Ts, TsLoadError> {
+        Self::from_reader(content.as_bytes())
     }
 
-    #[cfg(test)]
-    pub fn load_from_from_str(content: &str) -> Result<Ts, TsLoadError> {
-        Ok(quick_xml::de::from_str(content)?)
+    /// Parse TS content from the given reader, e.g. a git blob or an HTTP response body.
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Ts, TsLoadError> {
+        TsParser::new(reader).parse_ts()
     }
 
+    /// Load the given TS file, or create an untranslated copy of `fallback` for the given language if it doesn't exist.
     pub fn load_from_file_or_default(linguist_ts_file: &Path, fallback: &Ts, fallback_language_code: &str) -> Result<Ts, TsLoadError> {
         if !linguist_ts_file.exists() {
             let mut clone = fallback.clone();
@@ -202,6 +433,7 @@ impl Ts {
         }
     }
 
+    /// Save into the given TS file.
     pub fn save_into_file(&self, linguist_ts_file: &Path) -> Result<(), TsSaveError> {
         let target_file = File::create(linguist_ts_file)?;
         let mut writer = Writer::new_with_indent(&target_file, b' ', 4);
@@ -247,7 +479,7 @@ pub mod tests {
 
     #[test]
     fn tst_parse_ts_content() {
-        let ts = Ts::load_from_from_str(TEST_ZH_CN_TS_CONTENT).unwrap();
+        let ts = Ts::from_str(TEST_ZH_CN_TS_CONTENT).unwrap();
         assert_eq!(ts.language, Some("zh_CN".to_string()));
         assert_eq!(ts.version, "2.1");
         assert_eq!(ts.contexts.len(), 1);
@@ -263,5 +495,73 @@ pub mod tests {
             obsolete: 1,
         });
         assert_eq!(ts.get_message_stats().completeness_percentage(), 3.0 / 4.0 * 100.0);
+        assert_eq!(ts.contexts[0].messages[1].location.len(), 2);
+        assert_eq!(ts.contexts[0].messages[1].location[0].filename, None);
+        assert_eq!(ts.contexts[0].messages[4].numerus, Some("yes".to_string()));
+        assert_eq!(ts.contexts[0].messages[4].translation.numerus_forms, vec!["共%n张照片".to_string()]);
+    }
+
+    #[test]
+    fn tst_roundtrip_ts_content() {
+        let ts = Ts::from_str(TEST_ZH_CN_TS_CONTENT).unwrap();
+        let ts_file = std::env::temp_dir().join("deepin-translation-utils-tst_roundtrip_ts_content.ts");
+        ts.save_into_file(&ts_file).unwrap();
+        let reloaded = Ts::load_from_file(&ts_file).unwrap();
+        assert_eq!(reloaded.language, ts.language);
+        assert_eq!(reloaded.get_message_stats(), ts.get_message_stats());
+        for (reloaded_message, message) in reloaded.contexts[0].messages.iter().zip(&ts.contexts[0].messages) {
+            assert_eq!(reloaded_message.source, message.source);
+            assert_eq!(reloaded_message.translation.value, message.translation.value);
+            assert_eq!(reloaded_message.translation.numerus_forms, message.translation.numerus_forms);
+        }
+    }
+
+    #[test]
+    fn tst_parse_malformed_ts_content() {
+        assert!(matches!(Ts::from_str("<TS version=\"2.1\"><context>"), Err(TsLoadError::Malformed(_) | TsLoadError::Xml(_))));
+        assert!(matches!(Ts::from_str("<TS/>"), Err(TsLoadError::Malformed(_))));
+        assert!(matches!(Ts::from_str("<svg/>"), Err(TsLoadError::Malformed(_))));
+    }
+
+    fn ts_text() -> impl proptest::strategy::Strategy<Value = String> {
+        "[a-zA-Z0-9&<>\"']([a-zA-Z0-9&<>\"' ]{0,16}[a-zA-Z0-9&<>\"'])?"
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn tst_prop_ts_roundtrip(messages in proptest::collection::vec((
+            ts_text(),
+            proptest::option::of(ts_text()),
+            proptest::option::of(proptest::prop_oneof![
+                proptest::strategy::Just(TranslationType::Unfinished),
+                proptest::strategy::Just(TranslationType::Vanished),
+                proptest::strategy::Just(TranslationType::Obsolete),
+            ]),
+        ), 1..8)) {
+            let ts = Ts {
+                language: Some("zh_CN".to_string()),
+                version: "2.1".to_string(),
+                contexts: vec![Context {
+                    name: "Context".to_string(),
+                    messages: messages.into_iter().map(|(source, value, type_attr)| Message {
+                        location: Vec::new(),
+                        source,
+                        translation: Translation { type_attr, value, numerus_forms: Vec::new() },
+                        comment: None,
+                        translator_comment: None,
+                        numerus: None,
+                    }).collect(),
+                }],
+            };
+            let mut writer = Writer::new_with_indent(Vec::new(), b' ', 4);
+            writer.write_linguist_ts_file(&ts).unwrap();
+            let reloaded = Ts::from_reader(writer.into_inner().as_slice()).unwrap();
+            proptest::prop_assert_eq!(&reloaded.language, &ts.language);
+            proptest::prop_assert_eq!(reloaded.get_message_stats(), ts.get_message_stats());
+            for (reloaded_message, message) in reloaded.contexts[0].messages.iter().zip(&ts.contexts[0].messages) {
+                proptest::prop_assert_eq!(&reloaded_message.source, &message.source);
+                proptest::prop_assert_eq!(&reloaded_message.translation.value, &message.translation.value);
+            }
+        }
     }
 }
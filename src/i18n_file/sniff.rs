@@ -0,0 +1,153 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+// Content sniffing, used to confirm a file really is what its declared format or extension says.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use super::common::I18nFileKind;
+use crate::file_access::read_file;
+
+/// Number of leading bytes inspected when sniffing file content.
+pub const SNIFF_PREFIX_LEN: usize = 512;
+
+/// Format guessed from file content.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SniffedFormat {
+    /// XML with a `TS` doctype or root element.
+    Linguist,
+    /// Any other XML document.
+    Xml,
+    /// Starts with comments, or contains `msgctxt`/`msgid`/`msgstr` entries or a gettext charset header.
+    Gettext,
+    /// A JSON document.
+    Json,
+    /// Contains a `[Desktop Entry]` group.
+    Desktop,
+    /// File has no content other than whitespace.
+    Empty,
+    /// None of the above.
+    Unknown,
+}
+
+impl std::fmt::Display for SniffedFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let description = match self {
+            Self::Linguist => "Qt Linguist TS",
+            Self::Xml => "non-TS XML",
+            Self::Gettext => "GNU Gettext PO",
+            Self::Json => "JSON",
+            Self::Desktop => "desktop entry",
+            Self::Empty => "empty file",
+            Self::Unknown => "unknown content",
+        };
+        write!(f, "{description}")
+    }
+}
+
+impl SniffedFormat {
+    /// Guess the format from the leading bytes of a file.
+    ///
+    /// JSON is only guessed by its leading bracket here, use [`sniff_file`] to fully verify it.
+    pub fn from_prefix(prefix: &[u8]) -> Self {
+        let content = String::from_utf8_lossy(prefix);
+        let content = content.trim_start_matches('\u{feff}').trim_start();
+        if content.is_empty() {
+            return Self::Empty;
+        }
+        if content.starts_with('<') {
+            if content.contains("<!DOCTYPE TS") || content.contains("<TS") {
+                return Self::Linguist;
+            }
+            return Self::Xml;
+        }
+        if content.starts_with('{') || content.starts_with('[') {
+            if content.contains("[Desktop Entry]") {
+                return Self::Desktop;
+            }
+            return Self::Json;
+        }
+        if content.contains("[Desktop Entry]") {
+            return Self::Desktop;
+        }
+        // PO files usually start with translator comments, which may be longer than the sniffed prefix.
+        if content.starts_with('#') || ["msgctxt ", "msgid ", "msgstr ", "charset="].iter().any(|marker| content.contains(marker)) {
+            return Self::Gettext;
+        }
+        Self::Unknown
+    }
+
+    /// Whether the sniffed format matches the given Transifex `file_format`.
+    ///
+    /// Returns `None` if we don't know how to verify the declared format.
+    pub fn matches_declared_format(&self, format: &str) -> Option<bool> {
+        let expected = match format {
+            "QT" => Self::Linguist,
+            "PO" => Self::Gettext,
+            "KEYVALUEJSON" | "STRUCTURED_JSON" | "CHROME" => Self::Json,
+            "DESKTOP" => Self::Desktop,
+            _ => return None,
+        };
+        Some(*self == expected)
+    }
+
+    /// Whether the sniffed format is parseable as the given translation file kind.
+    ///
+    /// PO files have no signature, e.g. an entry without comments may start with a context longer
+    /// than the sniffed prefix, and an empty PO file is valid. So for Gettext, only content of other
+    /// known formats is rejected, and the rest is left to the parser.
+    pub fn matches_kind(&self, kind: &I18nFileKind) -> bool {
+        match kind {
+            I18nFileKind::Linguist => *self == Self::Linguist,
+            I18nFileKind::Gettext => matches!(self, Self::Gettext | Self::Empty | Self::Unknown),
+        }
+    }
+}
+
+/// Read at most `len` leading bytes of the given file.
+pub fn read_prefix(file_path: &Path, len: usize) -> std::io::Result<Vec<u8>> {
+    let mut prefix = Vec::with_capacity(len);
+    File::open(file_path)?.take(len as u64).read_to_end(&mut prefix)?;
+    Ok(prefix)
+}
+
+/// Sniff the format of the given file.
+///
+/// Files looking like JSON are fully parsed, and reported as [`SniffedFormat::Unknown`] if they are not valid JSON.
+pub fn sniff_file(file_path: &Path) -> std::io::Result<SniffedFormat> {
+    let sniffed = SniffedFormat::from_prefix(&read_prefix(file_path, SNIFF_PREFIX_LEN)?);
+    if sniffed == SniffedFormat::Json {
+        let content = read_file(file_path)?;
+        if serde_json::from_slice::<serde_json::Value>(&content).is_err() {
+            return Ok(SniffedFormat::Unknown);
+        }
+    }
+    Ok(sniffed)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::i18n_file::gettext::tests::TEST_ZH_CN_PO_CONTENT;
+    use crate::i18n_file::linguist::tests::TEST_ZH_CN_TS_CONTENT;
+
+    #[test]
+    fn tst_sniff_prefix() {
+        assert_eq!(SniffedFormat::from_prefix(TEST_ZH_CN_TS_CONTENT.as_bytes()), SniffedFormat::Linguist);
+        assert_eq!(SniffedFormat::from_prefix(TEST_ZH_CN_PO_CONTENT.as_bytes()), SniffedFormat::Gettext);
+        assert_eq!(SniffedFormat::from_prefix(br#"{"hello": "world"}"#), SniffedFormat::Json);
+        assert_eq!(SniffedFormat::from_prefix(b"[Desktop Entry]\nName=Dock\n"), SniffedFormat::Desktop);
+        assert_eq!(SniffedFormat::from_prefix(b"<?xml version=\"1.0\"?><svg/>"), SniffedFormat::Xml);
+        assert_eq!(SniffedFormat::from_prefix(b" \n"), SniffedFormat::Empty);
+        assert_eq!(SniffedFormat::from_prefix(b"msgctxt \"Dock\"\n"), SniffedFormat::Gettext);
+        assert!(SniffedFormat::Empty.matches_kind(&I18nFileKind::Gettext));
+        assert!(SniffedFormat::Unknown.matches_kind(&I18nFileKind::Gettext));
+        assert!(!SniffedFormat::Linguist.matches_kind(&I18nFileKind::Gettext));
+        assert!(!SniffedFormat::Empty.matches_kind(&I18nFileKind::Linguist));
+        assert_eq!(SniffedFormat::Gettext.matches_declared_format("QT"), Some(false));
+        assert_eq!(SniffedFormat::Linguist.matches_declared_format("QT"), Some(true));
+        assert_eq!(SniffedFormat::Linguist.matches_declared_format("YAML_GENERIC"), None);
+    }
+}
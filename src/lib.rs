@@ -2,7 +2,118 @@
 //
 // SPDX-License-Identifier: MIT
 
+//! Utilities for deepin translation resources.
+//!
+//! - [`transifex`]: `transifex.yaml` and `.tx/config` files, and matching translation files of their resources.
+//! - [`linguist`] and [`gettext`]: Qt Linguist TS and GNU Gettext PO files.
+//! - [`locale`]: language codes supported by Transifex.
+//! - [`report`]: findings and stats shared by all analysis features.
+//! - [`compendium`]: translation memory built from and applied to TS and PO files.
+//! - [`glossary`]: terminology consistency checking against a bilingual glossary.
+//! - [`weblate`]: export of filters as Weblate components.
+//!
+//! The command line tool is built with the `cli` feature, and the Transifex REST API client with
+//! the `rest-api` feature. Both are enabled by default.
+//! The `async` feature adds loaders built on tokio, e.g. `load_tx_yaml_file_async`, and the `http`
+//! feature adds `load_tx_yaml_from_url`. The `miette` feature implements `miette::Diagnostic` for
+//! [`transifex::yaml_file::TxYamlLoadError`] and [`transifex::yaml_file::ResourceSlugError`].
+
+#![cfg_attr(not(test), deny(missing_docs))]
+
+#[cfg(feature = "cli")]
+#[doc(hidden)]
 pub mod cli;
-pub mod i18n_file;
+#[cfg(feature = "cli")]
+#[doc(hidden)]
+pub mod subcmd;
+mod i18n_file;
 pub mod transifex;
-pub mod subcmd;
\ No newline at end of file
+pub mod error;
+pub mod report;
+pub mod compendium;
+pub mod glossary;
+pub mod weblate;
+/// Tool config file, see [`crate_config::CRATE_CONFIG_FILE_NAME`].
+pub mod crate_config;
+/// Filesystem walking with directory pruning.
+pub mod walk;
+pub(crate) mod file_access;
+
+pub mod linguist {
+    //! Qt Linguist translation files (`.ts`).
+    pub use crate::i18n_file::linguist::*;
+}
+
+pub mod gettext {
+    //! GNU Gettext translation files (`.po`).
+    pub use crate::i18n_file::gettext::*;
+}
+
+pub mod locale {
+    //! Language codes supported by Transifex.
+    pub use crate::transifex::supported_locales::*;
+    pub use crate::transifex::locale_names::*;
+}
+
+pub use error::{ContextError, Error, ErrorLocation};
+pub use i18n_file::common::{I18nFileKind, MessageStats, UnknownI18nFileExtError};
+pub use i18n_file::sniff::{SniffedFormat, sniff_file};
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use crate::gettext::Po;
+    use crate::linguist::Ts;
+    use crate::transifex::tx_config_file::TxConfig;
+    use crate::transifex::target_match::match_lang_code;
+    use crate::transifex::yaml_file::{TransifexYaml, TxResourceLookupEntry};
+
+    /// Read `(file name, content)` of every input in the corpus of the given fuzz target.
+    fn fuzz_corpus(target: &str) -> Vec<(String, Vec<u8>)> {
+        let corpus_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("fuzz").join("corpus").join(target);
+        let mut inputs = std::fs::read_dir(corpus_dir).unwrap()
+            .map(|entry| entry.unwrap().path())
+            .map(|path| (path.file_name().unwrap().to_string_lossy().to_string(), std::fs::read(&path).unwrap()))
+            .collect::<Vec<(String, Vec<u8>)>>();
+        inputs.sort();
+        inputs
+    }
+
+    /// Runs the interesting inputs of each fuzz target, so regressions show up without cargo-fuzz.
+    /// Inputs named `valid` must parse, every other input only must not panic.
+    #[test]
+    fn tst_fuzz_corpus_smoke() {
+        for (name, data) in fuzz_corpus("tx_yaml") {
+            let result = TransifexYaml::from_str(&String::from_utf8_lossy(&data));
+            assert!(name != "valid" || result.is_ok(), "{name}");
+            if let Ok(tx_yaml) = result {
+                let _ = tx_yaml.to_tx_config(String::new(), vec![]);
+            }
+        }
+        for (name, data) in fuzz_corpus("tx_config") {
+            let result = TxConfig::from_str(&String::from_utf8_lossy(&data));
+            assert!(name != "valid" || result.is_ok(), "{name}");
+            if let Ok(tx_config) = result {
+                let _ = TxConfig::from_str(&tx_config.to_str());
+            }
+        }
+        for (name, data) in fuzz_corpus("lookup_table") {
+            let result = TxResourceLookupEntry::table_from_reader(data.as_slice());
+            assert!(name != "valid" || result.is_ok(), "{name}");
+        }
+        for (name, data) in fuzz_corpus("ts") {
+            let result = Ts::from_reader(data.as_slice());
+            assert!(name != "valid" || result.is_ok(), "{name}");
+        }
+        for (name, data) in fuzz_corpus("po") {
+            let result = Po::from_reader(data.as_slice());
+            assert!(name != "valid" || result.is_ok(), "{name}");
+        }
+        for (name, data) in fuzz_corpus("filter_pattern") {
+            let content = String::from_utf8_lossy(&data);
+            let (pattern, file_name) = content.split_once('\n').unwrap_or((&*content, ""));
+            let result = match_lang_code(pattern, file_name, false);
+            assert!(name != "valid" || result.is_some(), "{name}");
+        }
+    }
+}
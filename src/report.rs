@@ -0,0 +1,301 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+//! Report types shared by all analysis features.
+//!
+//! Checks produce [`Finding`]s and statistics produce [`StatsEntry`]s, both aggregated by a
+//! [`Report`]. The JSON form of these types is a stable interface for dashboards, so field names
+//! only change together with [`REPORT_SCHEMA_VERSION`].
+
+use std::fmt;
+use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+
+use crate::i18n_file::common::MessageStats;
+
+/// Version of the JSON schema of [`Report`], bumped on incompatible changes.
+pub const REPORT_SCHEMA_VERSION: u32 = 1;
+
+/// How bad a finding is. Ordered from the least to the most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Worth knowing, but nothing to fix.
+    Info,
+    /// Probably a mistake, but doesn't fail checks.
+    Warning,
+    /// Fails checks.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Stable identifier of a kind of finding in kebab-case, e.g. `missing-translation`.
+///
+/// Suppressions and dashboards match findings by this id, so it never changes once released.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct IssueId(pub String);
+
+impl From<&str> for IssueId {
+    fn from(id: &str) -> Self {
+        Self(id.to_string())
+    }
+}
+
+impl fmt::Display for IssueId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Position inside a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FindingLocation {
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column number.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+}
+
+/// A single issue found by a check.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Finding {
+    /// How bad it is.
+    pub severity: Severity,
+    /// What kind of issue it is.
+    pub id: IssueId,
+    /// The offending file, if the issue is about a file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    /// Position inside `path`, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<FindingLocation>,
+    /// Human readable description.
+    pub message: String,
+    /// Machine readable details, specific to `id`.
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub data: serde_json::Value,
+}
+
+impl Finding {
+    /// Create a finding without path, location and data.
+    pub fn new(severity: Severity, id: impl Into<IssueId>, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            id: id.into(),
+            path: None,
+            location: None,
+            message: message.into(),
+            data: serde_json::Value::Null,
+        }
+    }
+
+    /// Attach the offending file.
+    pub fn with_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Attach machine readable details.
+    pub fn with_data(mut self, data: serde_json::Value) -> Self {
+        self.data = data;
+        self
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} [{}]: ", self.severity, self.id)?;
+        if let Some(path) = &self.path {
+            write!(f, "{}", path.display())?;
+            if let Some(location) = &self.location {
+                write!(f, ":{}", location.line)?;
+                if let Some(column) = location.column {
+                    write!(f, ":{column}")?;
+                }
+            }
+            write!(f, ": ")?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Message stats of a single translation file, or of a whole resource.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatsEntry {
+    /// Source file of the resource, or the project path for project totals.
+    pub resource: String,
+    /// Language code, or `None` for the source language.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// The translation file, if the entry is about a single file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    /// The stats.
+    pub stats: MessageStats,
+    /// Transifex resource slug `o:org:p:proj:r:res`, if the entry is joined with remote stats.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+    /// Stats reported by Transifex for the same resource and language, if fetched.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote: Option<RemoteStats>,
+}
+
+/// Stats of a resource language as reported by Transifex.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct RemoteStats {
+    /// Translated strings.
+    pub translated: u64,
+    /// All strings of the resource.
+    pub total: u64,
+}
+
+impl RemoteStats {
+    /// Percentage of translated strings, see [`MessageStats::completeness_percentage`].
+    pub fn completeness_percentage(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        (self.translated as f64 / self.total as f64) * 100.0
+    }
+}
+
+/// Findings and stats of an analysis run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Report {
+    /// See [`REPORT_SCHEMA_VERSION`].
+    pub schema_version: u32,
+    /// Findings, in the order they were found.
+    #[serde(default)]
+    pub findings: Vec<Finding>,
+    /// Stats, in the order they were computed.
+    #[serde(default)]
+    pub stats: Vec<StatsEntry>,
+}
+
+impl Default for Report {
+    fn default() -> Self {
+        Self {
+            schema_version: REPORT_SCHEMA_VERSION,
+            findings: Vec::new(),
+            stats: Vec::new(),
+        }
+    }
+}
+
+impl Report {
+    /// Add a finding.
+    pub fn push(&mut self, finding: Finding) {
+        self.findings.push(finding);
+    }
+
+    /// Add findings.
+    pub fn extend(&mut self, findings: impl IntoIterator<Item = Finding>) {
+        self.findings.extend(findings);
+    }
+
+    /// The severity of the most severe finding, or `None` if there are no findings.
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.findings.iter().map(|finding| finding.severity).max()
+    }
+
+    /// Number of findings with at least the given severity.
+    pub fn count_at_least(&self, severity: Severity) -> usize {
+        self.findings.iter().filter(|finding| finding.severity >= severity).count()
+    }
+
+    /// Iterate over findings with the given id.
+    pub fn filter_by_id<'a>(&'a self, id: &'a str) -> impl Iterator<Item = &'a Finding> + 'a {
+        self.findings.iter().filter(move |finding| finding.id.0 == id)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    /// Pins the JSON schema, update [`REPORT_SCHEMA_VERSION`] together with it.
+    pub const TEST_REPORT_JSON: &str = r#"{
+  "schema_version": 1,
+  "findings": [
+    {
+      "severity": "error",
+      "id": "missing-translation",
+      "path": "translations/app_zh_CN.ts",
+      "location": {
+        "line": 3,
+        "column": 7
+      },
+      "message": "no translation file found",
+      "data": {
+        "language": "zh_CN"
+      }
+    },
+    {
+      "severity": "warning",
+      "id": "empty-file",
+      "message": "file is empty"
+    }
+  ],
+  "stats": [
+    {
+      "resource": "translations/app.ts",
+      "language": "zh_CN",
+      "stats": {
+        "finished": 3,
+        "unfinished": 1,
+        "vanished": 0,
+        "obsolete": 0
+      }
+    }
+  ]
+}"#;
+
+    fn test_report() -> Report {
+        let mut report = Report::default();
+        let mut finding = Finding::new(Severity::Error, "missing-translation", "no translation file found")
+            .with_path("translations/app_zh_CN.ts")
+            .with_data(serde_json::json!({ "language": "zh_CN" }));
+        finding.location = Some(FindingLocation { line: 3, column: Some(7) });
+        report.push(finding);
+        report.push(Finding::new(Severity::Warning, "empty-file", "file is empty"));
+        report.stats.push(StatsEntry {
+            resource: "translations/app.ts".to_string(),
+            language: Some("zh_CN".to_string()),
+            path: None,
+            stats: MessageStats { finished: 3, unfinished: 1, vanished: 0, obsolete: 0 },
+            slug: None,
+            remote: None,
+        });
+        report
+    }
+
+    #[test]
+    fn tst_report_json_schema() {
+        let report = test_report();
+        assert_eq!(serde_json::to_string_pretty(&report).unwrap(), TEST_REPORT_JSON);
+        assert_eq!(serde_json::from_str::<Report>(TEST_REPORT_JSON).unwrap(), report);
+    }
+
+    #[test]
+    fn tst_report_queries() {
+        let report = test_report();
+        assert_eq!(report.max_severity(), Some(Severity::Error));
+        assert_eq!(report.count_at_least(Severity::Warning), 2);
+        assert_eq!(report.filter_by_id("empty-file").count(), 1);
+        assert_eq!(Report::default().max_severity(), None);
+        assert_eq!(report.findings[0].to_string(), "error [missing-translation]: translations/app_zh_CN.ts:3:7: no translation file found");
+    }
+}
@@ -7,9 +7,17 @@ pub mod statistics;
 pub mod yaml2txconfig;
 pub mod txconfig2yaml;
 pub mod monotxconfig;
+pub mod check;
+pub mod cache;
+pub mod tm;
+pub mod snapshot;
 
 pub use self::zhconv::{subcmd_zhconv, subcmd_zhconv_plain};
 pub use statistics::subcmd_statistics;
 pub use yaml2txconfig::{subcmd_yaml2txconfig, create_linked_resources_table};
 pub use txconfig2yaml::subcmd_txconfig2yaml;
 pub use monotxconfig::subcmd_monotxconfig;
+pub use check::subcmd_check;
+pub use cache::subcmd_cache_clear;
+pub use tm::{subcmd_tm_apply, subcmd_tm_build};
+pub use snapshot::{subcmd_snapshot_create, subcmd_snapshot_diff, subcmd_snapshot_restore};
@@ -0,0 +1,233 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+// On-disk cache of per-file message stats and lint results, so unchanged files are not parsed again between runs.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use thiserror::Error as TeError;
+use crate::i18n_file::common::MessageStats;
+use crate::file_access::read_file;
+
+#[derive(TeError, Debug)]
+pub enum CmdError {
+    #[error("Fail to remove cache file {0:?} because: {1}")]
+    ClearCache(PathBuf, #[source] std::io::Error),
+    #[error("Not able to get the cache directory, e.g. because HOME is not set")]
+    NoCacheDir,
+}
+
+/// Bump this whenever the cache format, the stats computation or the lint rules change, so old caches are discarded.
+pub const STATS_CACHE_VERSION: u32 = 2;
+
+pub const STATS_CACHE_FILE_NAME: &str = "file-stats.json";
+
+/// Identifies a specific content of a file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileFingerprint {
+    pub size: u64,
+    pub modified_secs: u64,
+    pub modified_nanos: u32,
+    /// FNV-1a hash of the file content.
+    pub hash: u64,
+}
+
+impl FileFingerprint {
+    pub fn of(file_path: &Path) -> std::io::Result<Self> {
+        let metadata = fs::metadata(file_path)?;
+        let modified = metadata.modified()?.duration_since(UNIX_EPOCH).unwrap_or_default();
+        Ok(Self {
+            size: metadata.len(),
+            modified_secs: modified.as_secs(),
+            modified_nanos: modified.subsec_nanos(),
+            hash: hash_file(file_path)?,
+        })
+    }
+}
+
+/// 64-bit FNV-1a hash of the file content. It's fast, and unlike `DefaultHasher` it's stable across Rust versions.
+fn hash_file(file_path: &Path) -> std::io::Result<u64> {
+    Ok(hash_bytes(&read_file(file_path)?))
+}
+
+pub(crate) fn hash_bytes(content: &[u8]) -> u64 {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
+
+    let mut hash = FNV_OFFSET_BASIS;
+    for byte in content {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+/// Results computed from one file content. Each result is only filled by the subcommand which
+/// needs it, so an entry may lack some of them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatsCacheEntry {
+    pub fingerprint: FileFingerprint,
+    #[serde(default)]
+    pub stats: Option<MessageStats>,
+    /// Language declared inside the file, or why it can't be loaded, as linted by `check --thorough`.
+    #[serde(default)]
+    pub language: Option<Result<Option<String>, String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StatsCache {
+    pub version: u32,
+    /// Keyed by absolute file path.
+    pub entries: BTreeMap<PathBuf, StatsCacheEntry>,
+}
+
+impl Default for StatsCache {
+    fn default() -> Self {
+        Self {
+            version: STATS_CACHE_VERSION,
+            entries: BTreeMap::new(),
+        }
+    }
+}
+
+fn absolute_path(file_path: &Path) -> PathBuf {
+    fs::canonicalize(file_path).unwrap_or(file_path.to_path_buf())
+}
+
+impl StatsCache {
+    /// Default cache file location, under the XDG cache directory.
+    pub fn default_cache_file() -> Result<PathBuf, CmdError> {
+        let xdg_proj_dirs = ProjectDirs::from("", "deepin", "deepin-translation-utils").ok_or(CmdError::NoCacheDir)?;
+        Ok(xdg_proj_dirs.cache_dir().join(STATS_CACHE_FILE_NAME))
+    }
+
+    /// Load the cache from the default cache file, see [`Self::default_cache_file`] and [`Self::load`].
+    ///
+    /// Returns `None` if there is no cache directory, a cache only makes runs faster.
+    pub fn load_default() -> Option<(PathBuf, Self)> {
+        let cache_file = Self::default_cache_file().ok()?;
+        let cache = Self::load(&cache_file);
+        Some((cache_file, cache))
+    }
+
+    /// Load the cache from the given file.
+    ///
+    /// Missing, corrupted or outdated cache files are silently discarded, and an empty cache is returned instead.
+    pub fn load(cache_file: &Path) -> Self {
+        fs::read(cache_file).ok()
+            .and_then(|content| serde_json::from_slice::<StatsCache>(&content).ok())
+            .filter(|cache| cache.version == STATS_CACHE_VERSION)
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, cache_file: &Path) -> std::io::Result<()> {
+        if let Some(parent_dir) = cache_file.parent() {
+            fs::create_dir_all(parent_dir)?;
+        }
+        fs::write(cache_file, serde_json::to_vec(self)?)
+    }
+
+    /// Get the cached entry of the given file, if it was made for the same file content.
+    fn entry(&self, file_path: &Path, fingerprint: &FileFingerprint) -> Option<&StatsCacheEntry> {
+        self.entries.get(&absolute_path(file_path))
+            .filter(|entry| entry.fingerprint == *fingerprint)
+    }
+
+    /// Get the entry of the given file to update. Results of a different file content are dropped.
+    fn entry_mut(&mut self, file_path: &Path, fingerprint: FileFingerprint) -> &mut StatsCacheEntry {
+        let entry = self.entries.entry(absolute_path(file_path))
+            .or_insert_with(|| StatsCacheEntry { fingerprint: fingerprint.clone(), stats: None, language: None });
+        if entry.fingerprint != fingerprint {
+            *entry = StatsCacheEntry { fingerprint, stats: None, language: None };
+        }
+        entry
+    }
+
+    /// Get cached stats of the given file, if the cached entry was made for the same file content.
+    pub fn lookup(&self, file_path: &Path, fingerprint: &FileFingerprint) -> Option<&MessageStats> {
+        self.entry(file_path, fingerprint).and_then(|entry| entry.stats.as_ref())
+    }
+
+    pub fn insert(&mut self, file_path: &Path, fingerprint: FileFingerprint, stats: MessageStats) {
+        self.entry_mut(file_path, fingerprint).stats = Some(stats);
+    }
+
+    /// Get the cached language lint result of the given file, see [`StatsCacheEntry::language`].
+    pub fn lookup_language(&self, file_path: &Path, fingerprint: &FileFingerprint) -> Option<&Result<Option<String>, String>> {
+        self.entry(file_path, fingerprint).and_then(|entry| entry.language.as_ref())
+    }
+
+    pub fn insert_language(&mut self, file_path: &Path, fingerprint: FileFingerprint, language: Result<Option<String>, String>) {
+        self.entry_mut(file_path, fingerprint).language = Some(language);
+    }
+}
+
+/// Remove the cache file. Removing a non-existing cache file is not an error.
+pub fn clear_stats_cache(cache_file: &Path) -> std::io::Result<()> {
+    match fs::remove_file(cache_file) {
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
+
+pub fn subcmd_cache_clear() -> Result<(), CmdError> {
+    let cache_file = StatsCache::default_cache_file()?;
+    clear_stats_cache(&cache_file).map_err(|e| CmdError::ClearCache(cache_file.clone(), e))?;
+    println!("Removed stats cache at: {cache_file:?}");
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::transifex::yaml_file::tests::create_test_project_dir;
+
+    #[test]
+    fn tst_stats_cache_roundtrip() {
+        let project_root = create_test_project_dir("tst_stats_cache_roundtrip");
+        let file_path = project_root.join("app_zh_CN.ts");
+        fs::write(&file_path, "<TS/>").unwrap();
+        let cache_file = project_root.join("cache").join(STATS_CACHE_FILE_NAME);
+
+        let stats = MessageStats { finished: 1, unfinished: 2, vanished: 3, obsolete: 4 };
+        let fingerprint = FileFingerprint::of(&file_path).unwrap();
+        let mut cache = StatsCache::load(&cache_file);
+        assert!(cache.lookup(&file_path, &fingerprint).is_none());
+        cache.insert(&file_path, fingerprint.clone(), stats.clone());
+        cache.save(&cache_file).unwrap();
+
+        let cache = StatsCache::load(&cache_file);
+        assert_eq!(cache.lookup(&file_path, &fingerprint), Some(&MessageStats { finished: 1, unfinished: 2, vanished: 3, obsolete: 4 }));
+        // changed content misses the cache
+        fs::write(&file_path, "<TS></TS>").unwrap();
+        assert!(cache.lookup(&file_path, &FileFingerprint::of(&file_path).unwrap()).is_none());
+
+        // lint results are kept along with the stats of the same content, and dropped with them
+        let fingerprint = FileFingerprint::of(&file_path).unwrap();
+        let mut cache = StatsCache::default();
+        cache.insert_language(&file_path, fingerprint.clone(), Ok(Some("zh_CN".to_string())));
+        assert!(cache.lookup(&file_path, &fingerprint).is_none());
+        cache.insert(&file_path, fingerprint.clone(), stats.clone());
+        assert_eq!(cache.lookup_language(&file_path, &fingerprint), Some(&Ok(Some("zh_CN".to_string()))));
+        cache.save(&cache_file).unwrap();
+        let mut cache = StatsCache::load(&cache_file);
+        assert_eq!(cache.lookup(&file_path, &fingerprint), Some(&stats));
+        assert_eq!(cache.lookup_language(&file_path, &fingerprint), Some(&Ok(Some("zh_CN".to_string()))));
+        fs::write(&file_path, "<TS/>").unwrap();
+        let fingerprint = FileFingerprint::of(&file_path).unwrap();
+        cache.insert(&file_path, fingerprint.clone(), stats);
+        assert!(cache.lookup_language(&file_path, &fingerprint).is_none());
+
+        // corrupted cache file is discarded
+        fs::write(&cache_file, "{ not json").unwrap();
+        assert!(StatsCache::load(&cache_file).entries.is_empty());
+        clear_stats_cache(&cache_file).unwrap();
+        assert!(!cache_file.exists());
+        clear_stats_cache(&cache_file).unwrap();
+    }
+}
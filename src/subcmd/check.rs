@@ -0,0 +1,742 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error as TeError;
+use crate::crate_config::{CrateConfig, CrateConfigLoadError, FallbackPolicy, try_load_crate_config_file};
+use crate::glossary::{Glossary, GlossaryLoadError, TermViolation, TermViolationKind};
+use crate::i18n_file::{common::{I18nFileKind, MessageStats}, gettext::Po, linguist::Ts};
+use crate::i18n_file::sniff::{SniffedFormat, sniff_file};
+use crate::report::{Finding, Report, Severity};
+use crate::transifex::supported_locales::{closest_supported_locale, is_supported_locale};
+use crate::transifex::tx_config_file::{TxConfig, TxConfigLoadError, TxConfigLoadErrorKind, main_host_has_trailing_slash, try_laod_tx_config_file};
+use crate::transifex::yaml_file::{BRANCH_UNIQUE_ID_PLACEHOLDER, Filter, ResourceSlugError, TransifexYaml};
+use super::cache::{FileFingerprint, StatsCache};
+use super::statistics::{self, TxProjectFileLoadError, load_file_stats, try_laod_transifex_project_file};
+
+#[derive(TeError, Debug)]
+pub enum CmdError {
+    #[error("Fail to load Transifex project file because: {0}")]
+    LoadTxProjectFile(#[from] TxProjectFileLoadError),
+    #[error("Fail to load .tx/config file because: {0}")]
+    LoadTxConfigFile(#[from] TxConfigLoadError),
+    #[error("Fail to load tool config file because: {0}")]
+    LoadCrateConfigFile(#[from] CrateConfigLoadError),
+    #[error("Fail to match resources because: {0}")]
+    MatchResources(#[source] std::io::Error),
+    #[error("Can not read file {0:?} because: {1}")]
+    SniffFile(PathBuf, #[source] std::io::Error),
+    #[error("Fail to load glossary file because: {0}")]
+    LoadGlossaryFile(#[from] GlossaryLoadError),
+    #[error("{0} check(s) failed")]
+    ChecksFailed(usize),
+}
+
+/// Ids of findings produced by checks, see [`crate::report::IssueId`].
+pub mod issue_ids {
+    pub const SATISFIED_BY_SYMLINK: &str = "satisfied-by-symlink";
+    pub const SATISFIED_BY_FALLBACK: &str = "satisfied-by-fallback";
+    pub const MISSING_TRANSLATION: &str = "missing-translation";
+    pub const REJECTED_SYMLINK: &str = "rejected-symlink";
+    pub const EMPTY_TRANSLATION: &str = "empty-translation";
+    pub const UNPARSEABLE_FILE: &str = "unparseable-file";
+    pub const FORMAT_MISMATCH: &str = "format-mismatch";
+    pub const EMPTY_FILE: &str = "empty-file";
+    pub const LANGUAGE_MISMATCH: &str = "language-mismatch";
+    pub const UNSUPPORTED_LOCALE: &str = "unsupported-locale";
+    pub const BRANCH_WITHOUT_UNIQUE_ID: &str = "branch-without-unique-id";
+    pub const INVALID_RESOURCE_SLUG: &str = "invalid-resource-slug";
+    pub const DUPLICATE_RESOURCE_SLUG: &str = "duplicate-resource-slug";
+    pub const FORBIDDEN_TERM: &str = "forbidden-term";
+    pub const MISSING_APPROVED_TERM: &str = "missing-approved-term";
+    pub const UNMATCHABLE_TARGET_PATTERN: &str = "unmatchable-target-pattern";
+    pub const SUSPICIOUS_MATCH: &str = "suspicious-match";
+    pub const HOST_TRAILING_SLASH: &str = "host-trailing-slash";
+    pub const SNAPSHOT_FILE_ADDED: &str = "snapshot-file-added";
+    pub const SNAPSHOT_FILE_REMOVED: &str = "snapshot-file-removed";
+    pub const SNAPSHOT_FILE_MODIFIED: &str = "snapshot-file-modified";
+}
+
+// ===== Required Languages =====
+
+#[derive(Debug, PartialEq)]
+pub enum RequirementOutcome {
+    Satisfied(PathBuf),
+    SatisfiedBySymlink(PathBuf),
+    /// Satisfied by the translation file of the given fallback language.
+    SatisfiedByFallback(String, PathBuf),
+    Missing,
+    /// The translation file is a symlink, but the policy doesn't accept symlinks.
+    RejectedSymlink(PathBuf),
+    Empty(PathBuf),
+    Unparseable(PathBuf, String),
+}
+
+impl RequirementOutcome {
+    pub fn is_satisfied(&self) -> bool {
+        matches!(self, Self::Satisfied(_) | Self::SatisfiedBySymlink(_) | Self::SatisfiedByFallback(_, _))
+    }
+}
+
+#[derive(Debug)]
+pub struct RequirementResult {
+    pub source: String,
+    pub language: String,
+    pub outcome: RequirementOutcome,
+}
+
+impl RequirementResult {
+    /// Convert to a report finding, or `None` if the requirement is plainly satisfied.
+    pub fn to_finding(&self) -> Option<Finding> {
+        let RequirementResult { source, language, outcome } = self;
+        let data = serde_json::json!({ "source": source, "language": language });
+        let (severity, id, path, message) = match outcome {
+            RequirementOutcome::Satisfied(_) => return None,
+            RequirementOutcome::SatisfiedBySymlink(file_path) => (Severity::Info, issue_ids::SATISFIED_BY_SYMLINK, Some(file_path), "satisfied by symlink".to_string()),
+            RequirementOutcome::SatisfiedByFallback(fallback_language, file_path) => (Severity::Info, issue_ids::SATISFIED_BY_FALLBACK, Some(file_path), format!("satisfied by fallback language {fallback_language}")),
+            RequirementOutcome::Missing => (Severity::Error, issue_ids::MISSING_TRANSLATION, None, "no translation file found".to_string()),
+            RequirementOutcome::RejectedSymlink(file_path) => (Severity::Error, issue_ids::REJECTED_SYMLINK, Some(file_path), "symlink is not allowed by the strict policy".to_string()),
+            RequirementOutcome::Empty(file_path) => (Severity::Error, issue_ids::EMPTY_TRANSLATION, Some(file_path), "no messages".to_string()),
+            RequirementOutcome::Unparseable(file_path, reason) => (Severity::Error, issue_ids::UNPARSEABLE_FILE, Some(file_path), reason.clone()),
+        };
+        let finding = Finding::new(severity, id, format!("{source} × {language}: {message}")).with_data(data);
+        Some(match path {
+            Some(path) => finding.with_path(path),
+            None => finding,
+        })
+    }
+}
+
+/// Same as [`load_file_stats`], but reuses the cached stats of an unchanged file, and caches new ones.
+fn load_file_stats_cached(file_path: &Path, cache: Option<&mut StatsCache>) -> Result<MessageStats, statistics::CmdError> {
+    let Some((cache, fingerprint)) = cache.and_then(|cache| Some((cache, FileFingerprint::of(file_path).ok()?))) else {
+        return load_file_stats(file_path);
+    };
+    if let Some(stats) = cache.lookup(file_path, &fingerprint) {
+        return Ok(stats.clone());
+    }
+    let stats = load_file_stats(file_path)?;
+    cache.insert(file_path, fingerprint, stats.clone());
+    Ok(stats)
+}
+
+fn inspect_translation_file(file_path: &Path, policy: FallbackPolicy, cache: Option<&mut StatsCache>) -> RequirementOutcome {
+    let is_symlink = fs::symlink_metadata(file_path)
+        .map(|metadata| metadata.file_type().is_symlink())
+        .unwrap_or(false);
+    if is_symlink && policy == FallbackPolicy::Strict {
+        return RequirementOutcome::RejectedSymlink(file_path.to_path_buf());
+    }
+    match fs::metadata(file_path) {
+        Ok(metadata) if metadata.len() == 0 => return RequirementOutcome::Empty(file_path.to_path_buf()),
+        Ok(_) => {},
+        Err(e) => return RequirementOutcome::Unparseable(file_path.to_path_buf(), e.to_string()),
+    }
+    // Only translation file kinds we know how to parse are checked for parseability.
+    if I18nFileKind::from_ext_hint(file_path).is_ok() {
+        match load_file_stats_cached(file_path, cache) {
+            Ok(stats) if stats.finished + stats.unfinished == 0 => return RequirementOutcome::Empty(file_path.to_path_buf()),
+            Ok(_) => {},
+            Err(e) => return RequirementOutcome::Unparseable(file_path.to_path_buf(), e.to_string()),
+        }
+    }
+    if is_symlink {
+        RequirementOutcome::SatisfiedBySymlink(file_path.to_path_buf())
+    } else {
+        RequirementOutcome::Satisfied(file_path.to_path_buf())
+    }
+}
+
+/// Verify every filter has an existing, parseable and non-empty translation file for each required language.
+///
+/// One result is returned for each filter × required language combination. Stats of parsed files
+/// are looked up in and added to `cache` if given.
+pub fn check_required_languages(project_root: &PathBuf, tx_yaml: &TransifexYaml, crate_config: &CrateConfig, mut cache: Option<&mut StatsCache>) -> Result<Vec<RequirementResult>, CmdError> {
+    let policy = crate_config.fallback_policy;
+    let mut results = Vec::<RequirementResult>::new();
+    for filter in &tx_yaml.filters {
+        let required_languages = crate_config.required_languages_for(&filter.source);
+        if required_languages.is_empty() {
+            continue;
+        }
+        let matched_files: HashMap<String, PathBuf> = filter.match_target_files(project_root)
+            .map_err(CmdError::MatchResources)?
+            .into_iter()
+            .collect();
+        for language in required_languages {
+            let outcome = match matched_files.get(language) {
+                Some(file_path) => inspect_translation_file(file_path, policy, cache.as_deref_mut()),
+                None => {
+                    let fallback = crate_config.language_fallbacks.get(language)
+                        .and_then(|fallback_language| matched_files.get(fallback_language).map(|path| (fallback_language, path)));
+                    match fallback {
+                        Some((fallback_language, file_path)) if policy == FallbackPolicy::AllowFallback => {
+                            match inspect_translation_file(file_path, policy, cache.as_deref_mut()) {
+                                outcome if outcome.is_satisfied() => RequirementOutcome::SatisfiedByFallback(fallback_language.clone(), file_path.clone()),
+                                outcome => outcome,
+                            }
+                        },
+                        _ => RequirementOutcome::Missing,
+                    }
+                },
+            };
+            results.push(RequirementResult {
+                source: filter.source.clone(),
+                language: language.clone(),
+                outcome,
+            });
+        }
+    }
+    Ok(results)
+}
+
+fn print_requirement_result(result: &RequirementResult) {
+    let RequirementResult { source, language, outcome } = result;
+    match outcome {
+        RequirementOutcome::Satisfied(_) => {},
+        RequirementOutcome::SatisfiedBySymlink(file_path) => println!("Note: {source:?} × {language}: satisfied by symlink {file_path:?}"),
+        RequirementOutcome::SatisfiedByFallback(fallback_language, file_path) => println!("Note: {source:?} × {language}: satisfied by fallback language {fallback_language} at {file_path:?}"),
+        RequirementOutcome::Missing => println!("Missing: {source:?} × {language}: no translation file found"),
+        RequirementOutcome::RejectedSymlink(file_path) => println!("Missing: {source:?} × {language}: {file_path:?} is a symlink, which is not allowed by the strict policy"),
+        RequirementOutcome::Empty(file_path) => println!("Empty: {source:?} × {language}: {file_path:?} has no messages"),
+        RequirementOutcome::Unparseable(file_path, reason) => println!("Unparseable: {source:?} × {language}: {file_path:?}: {reason}"),
+    }
+}
+
+// ===== File Formats =====
+
+#[derive(Debug, PartialEq)]
+pub enum FormatFinding {
+    /// File content does not match the declared `file_format`.
+    Mismatch { path: PathBuf, declared: String, actual: SniffedFormat },
+    /// File is empty. This is less severe than a mismatch.
+    Empty(PathBuf),
+}
+
+impl FormatFinding {
+    pub fn is_error(&self) -> bool {
+        matches!(self, Self::Mismatch { .. })
+    }
+}
+
+impl FormatFinding {
+    /// Convert to a report finding.
+    pub fn to_finding(&self) -> Finding {
+        match self {
+            Self::Mismatch { path, declared, actual } => Finding::new(Severity::Error, issue_ids::FORMAT_MISMATCH, format!("declared as {declared} but looks like {actual}"))
+                .with_path(path)
+                .with_data(serde_json::json!({ "declared": declared, "actual": actual.to_string() })),
+            Self::Empty(path) => Finding::new(Severity::Warning, issue_ids::EMPTY_FILE, "file is empty").with_path(path),
+        }
+    }
+}
+
+/// Sniff the source file and all matched translation files of each filter, and verify their
+/// content matches the declared `file_format`.
+pub fn check_file_formats(project_root: &PathBuf, tx_yaml: &TransifexYaml) -> Result<Vec<FormatFinding>, CmdError> {
+    let mut findings = Vec::<FormatFinding>::new();
+    for filter in &tx_yaml.filters {
+        let mut files = Vec::<PathBuf>::new();
+        let source_file = project_root.join(&filter.source);
+        if source_file.is_file() {
+            files.push(source_file);
+        }
+        let matched_files = filter.match_target_files(project_root).map_err(CmdError::MatchResources)?;
+        files.extend(matched_files.into_iter().map(|(_, file_path)| file_path));
+
+        for file_path in files {
+            let sniffed = sniff_file(&file_path).map_err(|e| CmdError::SniffFile(file_path.clone(), e))?;
+            if sniffed == SniffedFormat::Empty {
+                findings.push(FormatFinding::Empty(file_path));
+            } else if sniffed.matches_declared_format(&filter.format) == Some(false) {
+                findings.push(FormatFinding::Mismatch {
+                    path: file_path,
+                    declared: filter.format.clone(),
+                    actual: sniffed,
+                });
+            }
+        }
+    }
+    Ok(findings)
+}
+
+fn print_format_finding(finding: &FormatFinding) {
+    match finding {
+        FormatFinding::Mismatch { path, declared, actual } => println!("Mismatched format: {path:?} is declared as {declared} but looks like {actual}"),
+        FormatFinding::Empty(path) => println!("Warning: {path:?} is empty"),
+    }
+}
+
+// ===== Language Attributes (Thorough) =====
+
+#[derive(Debug, PartialEq)]
+pub enum LanguageAttributeFinding {
+    /// Language declared inside the file doesn't match the language Transifex expects.
+    Mismatch { path: PathBuf, expected: String, claimed: Option<String> },
+    Unparseable(PathBuf, String),
+}
+
+impl LanguageAttributeFinding {
+    /// Convert to a report finding.
+    pub fn to_finding(&self) -> Finding {
+        match self {
+            Self::Mismatch { path, expected, claimed } => {
+                let claimed_text = claimed.as_deref().unwrap_or("no language");
+                Finding::new(Severity::Error, issue_ids::LANGUAGE_MISMATCH, format!("will be pushed as {expected} but the file claims {claimed_text}"))
+                    .with_path(path)
+                    .with_data(serde_json::json!({ "expected": expected, "claimed": claimed }))
+            },
+            Self::Unparseable(path, reason) => Finding::new(Severity::Error, issue_ids::UNPARSEABLE_FILE, reason.clone()).with_path(path),
+        }
+    }
+}
+
+fn normalize_language_code(language_code: &str) -> String {
+    language_code.replace('-', "_")
+}
+
+fn load_file_language(file_path: &Path) -> Result<Option<String>, String> {
+    let kind = I18nFileKind::from_ext_hint(file_path).map_err(|e| e.to_string())?;
+    let language = match kind {
+        I18nFileKind::Linguist => Ts::load_from_file(file_path).map_err(|e| e.to_string())?.get_language(),
+        I18nFileKind::Gettext => Some(Po::load_from_file(file_path).map_err(|e| e.to_string())?.get_language()),
+    };
+    Ok(language.filter(|language| !language.is_empty()))
+}
+
+/// Same as [`load_file_language`], but reuses the cached result of an unchanged file, and caches new ones.
+fn load_file_language_cached(file_path: &Path, cache: Option<&mut StatsCache>) -> Result<Option<String>, String> {
+    let Some((cache, fingerprint)) = cache.and_then(|cache| Some((cache, FileFingerprint::of(file_path).ok()?))) else {
+        return load_file_language(file_path);
+    };
+    if let Some(language) = cache.lookup_language(file_path, &fingerprint) {
+        return language.clone();
+    }
+    let language = load_file_language(file_path);
+    cache.insert_language(file_path, fingerprint, language.clone());
+    language
+}
+
+/// Get the effective lang map of the given filter, mapping Transifex language codes to local ones.
+fn lang_map_for(filter: &Filter, tx_yaml: &TransifexYaml, tx_config: Option<&TxConfig>) -> BTreeMap<String, String> {
+    let mut lang_map = tx_yaml.settings.lang_map.clone().unwrap_or_default();
+    if let Some(tx_config) = tx_config {
+        lang_map.extend(tx_config.lang_map_for(&filter.source));
+    }
+    lang_map
+}
+
+/// Same as [`lang_map_for`], but in the reverse direction, which maps local language codes to Transifex ones.
+fn reverse_lang_map_for(filter: &Filter, tx_yaml: &TransifexYaml, tx_config: Option<&TxConfig>) -> HashMap<String, String> {
+    lang_map_for(filter, tx_yaml, tx_config)
+        .into_iter()
+        .map(|(remote, local)| (local, remote))
+        .collect()
+}
+
+/// For each matched translation file, compute the language code Transifex will use (the language
+/// code in the file name, mapped by `lang_map`), and verify the language declared inside the file
+/// matches it.
+///
+/// This needs to parse every translation file, so it belongs to the thorough checks. Results of
+/// parsed files are looked up in and added to `cache` if given.
+pub fn check_language_attributes(project_root: &PathBuf, tx_yaml: &TransifexYaml, tx_config: Option<&TxConfig>, mut cache: Option<&mut StatsCache>) -> Result<Vec<LanguageAttributeFinding>, CmdError> {
+    let mut findings = Vec::<LanguageAttributeFinding>::new();
+    for filter in &tx_yaml.filters {
+        if I18nFileKind::from_ext_hint(std::path::Path::new(&filter.target_pattern)).is_err() {
+            continue;
+        }
+        let local_to_remote = reverse_lang_map_for(filter, tx_yaml, tx_config);
+        let matched_files = filter.match_target_files(project_root).map_err(CmdError::MatchResources)?;
+        for (lang, file_path) in matched_files {
+            let expected = local_to_remote.get(&lang).cloned().unwrap_or(lang);
+            match load_file_language_cached(&file_path, cache.as_deref_mut()) {
+                Ok(claimed) => {
+                    let is_matched = claimed.as_deref()
+                        .is_some_and(|claimed| normalize_language_code(claimed) == normalize_language_code(&expected));
+                    if !is_matched {
+                        findings.push(LanguageAttributeFinding::Mismatch { path: file_path, expected, claimed });
+                    }
+                },
+                Err(reason) => findings.push(LanguageAttributeFinding::Unparseable(file_path, reason)),
+            }
+        }
+    }
+    Ok(findings)
+}
+
+fn print_language_attribute_finding(finding: &LanguageAttributeFinding) {
+    match finding {
+        LanguageAttributeFinding::Mismatch { path, expected, claimed: Some(claimed) } => println!("Mismatched language: {path:?} will be pushed as {expected} but the file claims {claimed}"),
+        LanguageAttributeFinding::Mismatch { path, expected, claimed: None } => println!("Mismatched language: {path:?} will be pushed as {expected} but the file claims no language"),
+        LanguageAttributeFinding::Unparseable(path, reason) => println!("Unparseable: {path:?}: {reason}"),
+    }
+}
+
+// ===== Supported Locales =====
+
+#[derive(Debug, PartialEq)]
+pub struct UnsupportedLocaleFinding {
+    /// Transifex-side language code, i.e. with `lang_map` applied.
+    pub locale: String,
+    pub suggestion: Option<&'static str>,
+}
+
+impl UnsupportedLocaleFinding {
+    /// Convert to a report finding. Only a warning, since the table of supported locales may lag
+    /// behind the languages Transifex actually accepts.
+    pub fn to_finding(&self) -> Finding {
+        let message = match self.suggestion {
+            Some(suggestion) => format!("{} is not accepted by Transifex, did you mean {suggestion}?", self.locale),
+            None => format!("{} is not accepted by Transifex", self.locale),
+        };
+        Finding::new(Severity::Warning, issue_ids::UNSUPPORTED_LOCALE, message)
+            .with_data(serde_json::json!({ "locale": self.locale, "suggestion": self.suggestion }))
+    }
+}
+
+/// Verify every locale we intend to sync, i.e. source languages, required languages and languages
+/// of matched translation files, is accepted by Transifex.
+pub fn check_supported_locales(project_root: &PathBuf, tx_yaml: &TransifexYaml, tx_config: Option<&TxConfig>, crate_config: &CrateConfig) -> Result<Vec<UnsupportedLocaleFinding>, CmdError> {
+    let mut locales = BTreeSet::<String>::new();
+    for filter in &tx_yaml.filters {
+        let local_to_remote = reverse_lang_map_for(filter, tx_yaml, tx_config);
+        let matched_files = filter.match_target_files(project_root).map_err(CmdError::MatchResources)?;
+        let local_locales = matched_files.into_iter()
+            .map(|(lang, _)| lang)
+            .chain(crate_config.required_languages_for(&filter.source).iter().cloned());
+        for local_locale in local_locales {
+            locales.insert(local_to_remote.get(&local_locale).cloned().unwrap_or(local_locale));
+        }
+        locales.insert(filter.source_lang.clone());
+    }
+    Ok(locales.into_iter()
+        .filter(|locale| !is_supported_locale(locale))
+        .map(|locale| UnsupportedLocaleFinding {
+            suggestion: closest_supported_locale(&locale),
+            locale,
+        })
+        .collect())
+}
+
+fn print_unsupported_locale_finding(finding: &UnsupportedLocaleFinding) {
+    match finding.suggestion {
+        Some(suggestion) => println!("Warning: unsupported locale: {} is not accepted by Transifex, did you mean {suggestion}?", finding.locale),
+        None => println!("Warning: unsupported locale: {} is not accepted by Transifex", finding.locale),
+    }
+}
+
+// ===== Terminology =====
+
+#[derive(Debug, PartialEq)]
+pub struct TerminologyFinding {
+    pub path: PathBuf,
+    pub violation: TermViolation,
+}
+
+impl TerminologyFinding {
+    /// Convert to a report finding. Forbidden variants are errors, missing approved terms are
+    /// only warnings since rephrasing may be legit.
+    pub fn to_finding(&self) -> Finding {
+        let violation = &self.violation;
+        let (severity, id, message) = match &violation.kind {
+            TermViolationKind::Forbidden(variant) => (Severity::Error, issue_ids::FORBIDDEN_TERM,
+                format!("{:?} translates {:?} as forbidden {variant:?}", violation.source, violation.term)),
+            TermViolationKind::MissingApproved => (Severity::Warning, issue_ids::MISSING_APPROVED_TERM,
+                format!("{:?} doesn't use an approved translation of {:?}", violation.source, violation.term)),
+        };
+        Finding::new(severity, id, message)
+            .with_path(&self.path)
+            .with_data(serde_json::json!({
+                "context": violation.context,
+                "source": violation.source,
+                "translation": violation.translation,
+                "term": violation.term,
+            }))
+    }
+}
+
+/// Check finished translations of every matched translation file against the glossary.
+///
+/// Files which can't be parsed are skipped, they are already reported by other checks.
+pub fn check_terminology(project_root: &PathBuf, tx_yaml: &TransifexYaml, glossary: &Glossary) -> Result<Vec<TerminologyFinding>, CmdError> {
+    let mut findings = Vec::<TerminologyFinding>::new();
+    for filter in &tx_yaml.filters {
+        let Ok(kind) = I18nFileKind::from_ext_hint(Path::new(&filter.target_pattern)) else {
+            continue;
+        };
+        let matched_files = filter.match_target_files(project_root).map_err(CmdError::MatchResources)?;
+        for (lang, file_path) in matched_files {
+            let violations = match kind {
+                I18nFileKind::Linguist => Ts::load_from_file(&file_path).map(|ts| glossary.check_ts(&ts, &lang)).ok(),
+                I18nFileKind::Gettext => Po::load_from_file(&file_path).map(|po| glossary.check_po(&po, &lang)).ok(),
+            };
+            findings.extend(violations.unwrap_or_default().into_iter()
+                .map(|violation| TerminologyFinding { path: file_path.clone(), violation }));
+        }
+    }
+    Ok(findings)
+}
+
+fn print_terminology_finding(finding: &TerminologyFinding) {
+    let violation = &finding.violation;
+    match &violation.kind {
+        TermViolationKind::Forbidden(variant) => println!("Forbidden term: {:?}: {:?} is translated as {variant:?} in {:?}", finding.path, violation.term, violation.translation),
+        TermViolationKind::MissingApproved => println!("Missing approved term: {:?}: {:?} is not translated as approved in {:?}", finding.path, violation.term, violation.translation),
+    }
+}
+
+// ===== Sub Command =====
+
+pub fn subcmd_check(project_root: &PathBuf, thorough: bool, no_cache: bool) -> Result<(), CmdError> {
+    let (transifex_yaml_file, tx_yaml) = try_laod_transifex_project_file(project_root)?;
+    println!("Found Transifex project config file at: {transifex_yaml_file:?}");
+    let (crate_config_file, crate_config) = try_load_crate_config_file(project_root)?;
+    match crate_config_file {
+        Some(crate_config_file) => println!("Found tool config file at: {crate_config_file:?}"),
+        None => println!("No tool config file found, using default config..."),
+    }
+    // .tx/config is optional, but lang_map inside it is taken into account if it exists.
+    let mut report = Report::default();
+    let tx_config = match try_laod_tx_config_file(project_root) {
+        Ok((tx_config_file, tx_config)) => {
+            println!("Found .tx/config file at: {tx_config_file:?}");
+            if fs::read_to_string(&tx_config_file).is_ok_and(|content| main_host_has_trailing_slash(&content)) {
+                println!("Warning: host of .tx/config has a trailing slash, which Transifex may reject, use {:?} instead.", tx_config.main_section.host);
+                report.push(Finding::new(Severity::Warning, issue_ids::HOST_TRAILING_SLASH, "host of the [main] section has a trailing slash")
+                    .with_path(&tx_config_file));
+            }
+            Some(tx_config)
+        },
+        Err(e) if matches!(e.kind(), TxConfigLoadErrorKind::FileNotFound) => None,
+        Err(e) => return Err(e.into()),
+    };
+
+    if !tx_yaml.settings.has_unique_branch_token() {
+        println!("Warning: pr_branch_name {:?} contains no {BRANCH_UNIQUE_ID_PLACEHOLDER}, every pull request would use the same branch.",
+            tx_yaml.settings.branch_template);
+        report.push(Finding::new(Severity::Warning, issue_ids::BRANCH_WITHOUT_UNIQUE_ID,
+            format!("pr_branch_name {:?} contains no {BRANCH_UNIQUE_ID_PLACEHOLDER}", tx_yaml.settings.branch_template)));
+    }
+    if let Err(errors) = tx_yaml.validate_resource_slugs() {
+        for error in &errors {
+            println!("Error: {error}");
+            let id = match error {
+                ResourceSlugError::Invalid { .. } => issue_ids::INVALID_RESOURCE_SLUG,
+                ResourceSlugError::Duplicate { .. } => issue_ids::DUPLICATE_RESOURCE_SLUG,
+            };
+            report.push(Finding::new(Severity::Error, id, error.to_string()).with_path(&transifex_yaml_file));
+        }
+    }
+    for filter in &tx_yaml.filters {
+        if let Err(reason) = filter.self_check() {
+            println!("Warning: {reason}, no translation file of {:?} will ever be matched.", filter.source);
+            report.push(Finding::new(Severity::Warning, issue_ids::UNMATCHABLE_TARGET_PATTERN, reason).with_path(&transifex_yaml_file));
+        }
+        for (lang_code, file_path) in filter.suspicious_matches(project_root).map_err(CmdError::MatchResources)? {
+            println!("Warning: {file_path:?} is matched as language {lang_code:?}, which is not a known locale.");
+            report.push(Finding::new(Severity::Warning, issue_ids::SUSPICIOUS_MATCH, format!("matched as unknown locale {lang_code}"))
+                .with_path(&file_path));
+        }
+    }
+
+    // reuse results of files unchanged since the last run, shared with the statistics subcommand.
+    let mut cache = if no_cache { None } else { StatsCache::load_default() };
+    let results = check_required_languages(project_root, &tx_yaml, &crate_config, cache.as_mut().map(|(_, cache)| cache))?;
+    results.iter().for_each(print_requirement_result);
+    let failed_requirements = results.iter().filter(|result| !result.outcome.is_satisfied()).count();
+    println!("Checked {} resource × language combination(s), {failed_requirements} failed.", results.len());
+    report.extend(results.iter().filter_map(RequirementResult::to_finding));
+
+    let findings = check_file_formats(project_root, &tx_yaml)?;
+    findings.iter().for_each(print_format_finding);
+    let failed_formats = findings.iter().filter(|finding| finding.is_error()).count();
+    println!("Checked file formats, {failed_formats} mismatch(es) found.");
+    report.extend(findings.iter().map(FormatFinding::to_finding));
+
+    let findings = check_supported_locales(project_root, &tx_yaml, tx_config.as_ref(), &crate_config)?;
+    findings.iter().for_each(print_unsupported_locale_finding);
+    println!("Checked locales, {} unsupported locale(s) found.", findings.len());
+    report.extend(findings.iter().map(UnsupportedLocaleFinding::to_finding));
+
+    if let Some(glossary_file) = &crate_config.glossary {
+        let glossary = Glossary::load_from_file(&project_root.join(glossary_file))?;
+        let findings = check_terminology(project_root, &tx_yaml, &glossary)?;
+        findings.iter().for_each(print_terminology_finding);
+        println!("Checked terminology, {} issue(s) found.", findings.len());
+        report.extend(findings.iter().map(TerminologyFinding::to_finding));
+    }
+
+    if thorough {
+        let findings = check_language_attributes(project_root, &tx_yaml, tx_config.as_ref(), cache.as_mut().map(|(_, cache)| cache))?;
+        findings.iter().for_each(print_language_attribute_finding);
+        println!("Checked language attributes, {} issue(s) found.", findings.len());
+        report.extend(findings.iter().map(LanguageAttributeFinding::to_finding));
+    }
+
+    // a failure to update the cache only makes the next run slower.
+    if let Some((cache_file, cache)) = &cache {
+        if let Err(e) = cache.save(cache_file) {
+            println!("Warning: fail to save stats cache to {cache_file:?} because: {e}");
+        }
+    }
+
+    let failed = report.count_at_least(Severity::Error);
+    if failed > 0 {
+        return Err(CmdError::ChecksFailed(failed));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n_file::linguist::tests::TEST_ZH_CN_TS_CONTENT;
+    use crate::transifex::yaml_file::tests::create_test_project_dir;
+
+    const TEST_TX_YAML_CONTENT: &str = r#"filters:
+  - filter_type: file
+    source_file: translations/app.ts
+    file_format: QT
+    source_language: en_US
+    translation_files_expression: translations/app_<lang>.ts
+settings:
+  pr_branch_name: transifex_update_<br_unique_id>
+"#;
+
+    #[test]
+    fn tst_check_required_languages() {
+        let project_root = create_test_project_dir("tst_check_required_languages");
+        let translations_dir = project_root.join("translations");
+        fs::create_dir_all(&translations_dir).unwrap();
+        fs::write(translations_dir.join("app.ts"), TEST_ZH_CN_TS_CONTENT).unwrap();
+        fs::write(translations_dir.join("app_zh_CN.ts"), TEST_ZH_CN_TS_CONTENT).unwrap();
+        fs::write(translations_dir.join("app_zh_TW.ts"), TEST_ZH_CN_TS_CONTENT).unwrap();
+        fs::write(translations_dir.join("app_ja.ts"), "").unwrap();
+
+        let tx_yaml = serde_yml::from_str::<TransifexYaml>(TEST_TX_YAML_CONTENT).unwrap();
+        let mut crate_config = CrateConfig::from_str(r#"required_languages = ["zh_CN", "zh_HK", "ja", "de"]
+
+[language_fallbacks]
+zh_HK = "zh_TW"
+"#).unwrap();
+
+        let results = check_required_languages(&project_root, &tx_yaml, &crate_config, None).unwrap();
+        assert_eq!(results.len(), 4);
+        assert!(matches!(results[0].outcome, RequirementOutcome::Satisfied(_)));
+        assert_eq!(results[1].outcome, RequirementOutcome::Missing); // strict policy ignores fallbacks
+        assert!(matches!(results[2].outcome, RequirementOutcome::Empty(_)));
+        assert_eq!(results[3].outcome, RequirementOutcome::Missing);
+        let mut report = Report::default();
+        report.extend(results.iter().filter_map(RequirementResult::to_finding));
+        assert_eq!(report.findings.len(), 3);
+        assert_eq!(report.max_severity(), Some(Severity::Error));
+        assert_eq!(report.filter_by_id(issue_ids::MISSING_TRANSLATION).count(), 2);
+        assert_eq!(report.filter_by_id(issue_ids::EMPTY_TRANSLATION).next().unwrap().path, Some(translations_dir.join("app_ja.ts")));
+
+        crate_config.fallback_policy = FallbackPolicy::AllowFallback;
+        let results = check_required_languages(&project_root, &tx_yaml, &crate_config, None).unwrap();
+        assert_eq!(results[1].outcome, RequirementOutcome::SatisfiedByFallback("zh_TW".to_string(), translations_dir.join("app_zh_TW.ts")));
+    }
+
+    #[test]
+    fn tst_check_file_formats() {
+        use crate::i18n_file::gettext::tests::TEST_ZH_CN_PO_CONTENT;
+
+        let project_root = create_test_project_dir("tst_check_file_formats");
+        let translations_dir = project_root.join("translations");
+        fs::create_dir_all(&translations_dir).unwrap();
+        fs::write(translations_dir.join("app.ts"), TEST_ZH_CN_TS_CONTENT).unwrap();
+        fs::write(translations_dir.join("app_zh_CN.ts"), TEST_ZH_CN_PO_CONTENT).unwrap();
+        fs::write(translations_dir.join("app_zh_TW.ts"), "").unwrap();
+
+        let tx_yaml = serde_yml::from_str::<TransifexYaml>(TEST_TX_YAML_CONTENT).unwrap();
+        let mut findings = check_file_formats(&project_root, &tx_yaml).unwrap();
+        findings.sort_by_key(|finding| finding.is_error());
+        assert_eq!(findings, vec![
+            FormatFinding::Empty(translations_dir.join("app_zh_TW.ts")),
+            FormatFinding::Mismatch {
+                path: translations_dir.join("app_zh_CN.ts"),
+                declared: "QT".to_string(),
+                actual: SniffedFormat::Gettext,
+            },
+        ]);
+    }
+
+    #[test]
+    fn tst_check_supported_locales() {
+        let project_root = create_test_project_dir("tst_check_supported_locales");
+        let translations_dir = project_root.join("translations");
+        fs::create_dir_all(&translations_dir).unwrap();
+        fs::write(translations_dir.join("app_zh_CN.ts"), TEST_ZH_CN_TS_CONTENT).unwrap();
+        fs::write(translations_dir.join("app_de_XX.ts"), TEST_ZH_CN_TS_CONTENT).unwrap();
+
+        let tx_yaml = serde_yml::from_str::<TransifexYaml>(TEST_TX_YAML_CONTENT).unwrap();
+        let crate_config = CrateConfig::from_str(r#"required_languages = ["zz"]"#).unwrap();
+        let findings = check_supported_locales(&project_root, &tx_yaml, None, &crate_config).unwrap();
+        assert_eq!(findings, vec![
+            UnsupportedLocaleFinding { locale: "de_XX".to_string(), suggestion: Some("de") },
+            UnsupportedLocaleFinding { locale: "zz".to_string(), suggestion: None },
+        ]);
+        assert_eq!(findings[1].to_finding().severity, Severity::Warning);
+    }
+
+    #[test]
+    fn tst_check_language_attributes() {
+        let project_root = create_test_project_dir("tst_check_language_attributes");
+        let translations_dir = project_root.join("translations");
+        fs::create_dir_all(&translations_dir).unwrap();
+        // Language attribute of the fixture is zh_CN
+        fs::write(translations_dir.join("app_zh.ts"), TEST_ZH_CN_TS_CONTENT).unwrap();
+        fs::write(translations_dir.join("app_zh_TW.ts"), TEST_ZH_CN_TS_CONTENT).unwrap();
+
+        let tx_yaml = serde_yml::from_str::<TransifexYaml>(&TEST_TX_YAML_CONTENT.replace(
+            "settings:\n", "settings:\n  language_mapping:\n    zh_CN: zh\n"
+        )).unwrap();
+        let findings = check_language_attributes(&project_root, &tx_yaml, None, None).unwrap();
+        let expected = vec![
+            LanguageAttributeFinding::Mismatch {
+                path: translations_dir.join("app_zh_TW.ts"),
+                expected: "zh_TW".to_string(),
+                claimed: Some("zh_CN".to_string()),
+            },
+        ];
+        assert_eq!(findings, expected);
+
+        // the second run is served from the cache
+        let mut cache = StatsCache::default();
+        assert_eq!(check_language_attributes(&project_root, &tx_yaml, None, Some(&mut cache)).unwrap(), expected);
+        let file_path = translations_dir.join("app_zh_TW.ts");
+        let fingerprint = FileFingerprint::of(&file_path).unwrap();
+        assert_eq!(cache.lookup_language(&file_path, &fingerprint), Some(&Ok(Some("zh_CN".to_string()))));
+        cache.insert_language(&file_path, fingerprint, Ok(Some("zh_TW".to_string())));
+        assert!(check_language_attributes(&project_root, &tx_yaml, None, Some(&mut cache)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn tst_check_terminology() {
+        use crate::glossary::tests::TEST_GLOSSARY_CONTENT;
+        let project_root = create_test_project_dir("tst_check_terminology");
+        let translations_dir = project_root.join("translations");
+        fs::create_dir_all(&translations_dir).unwrap();
+        let mut ts = Ts::from_str(TEST_ZH_CN_TS_CONTENT).unwrap();
+        ts.contexts[0].messages[0].source = "Dock size".to_string();
+        ts.contexts[0].messages[0].fill_translation("停靠栏大小");
+        ts.save_into_file(&translations_dir.join("app_zh_CN.ts")).unwrap();
+        ts.save_into_file(&translations_dir.join("app_ja.ts")).unwrap();
+
+        let tx_yaml = serde_yml::from_str::<TransifexYaml>(TEST_TX_YAML_CONTENT).unwrap();
+        let glossary = Glossary::from_str(TEST_GLOSSARY_CONTENT).unwrap();
+        let findings = check_terminology(&project_root, &tx_yaml, &glossary).unwrap();
+        assert_eq!(findings.len(), 2);
+        assert!(findings.iter().all(|finding| finding.path == translations_dir.join("app_zh_CN.ts")));
+        let findings = findings.iter().map(TerminologyFinding::to_finding).collect::<Vec<_>>();
+        assert_eq!(findings[0].id.0, issue_ids::FORBIDDEN_TERM);
+        assert_eq!(findings[0].severity, Severity::Error);
+        assert_eq!(findings[1].id.0, issue_ids::MISSING_APPROVED_TERM);
+        assert_eq!(findings[1].severity, Severity::Warning);
+    }
+}
@@ -0,0 +1,361 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+// Restorable snapshots of the translation state of a project, taken before risky operations.
+//
+// A snapshot is a directory holding `manifest.json` and a copy of every snapshotted file under `files/`,
+// at the same path relative to the project root.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use serde::{Deserialize, Serialize};
+use thiserror::Error as TeError;
+use crate::report::{Finding, Report, Severity, StatsEntry};
+use crate::transifex::yaml_file::TransifexYaml;
+use super::cache::hash_bytes;
+use super::check::issue_ids;
+use super::statistics::{TxProjectFileLoadError, load_file_stats, try_laod_transifex_project_file};
+
+#[derive(TeError, Debug)]
+pub enum CmdError {
+    #[error("Fail to load Transifex project file because: {0}")]
+    LoadTxProjectFile(#[from] TxProjectFileLoadError),
+    #[error("Fail to match resources because: {0}")]
+    MatchResources(#[source] std::io::Error),
+    #[error("Can not read file {0:?} because: {1}")]
+    ReadFile(PathBuf, #[source] std::io::Error),
+    #[error("Can not write file {0:?} because: {1}")]
+    WriteFile(PathBuf, #[source] std::io::Error),
+    #[error("Snapshot directory {0:?} already exists and is not empty")]
+    SnapshotExists(PathBuf),
+    #[error("Fail to parse snapshot manifest {0:?} because: {1}")]
+    ParseManifest(PathBuf, #[source] serde_json::Error),
+    #[error("Snapshot version {0} is not supported, expected {SNAPSHOT_VERSION}")]
+    UnsupportedVersion(u32),
+    #[error("Snapshot contains invalid path {0:?}")]
+    InvalidPath(PathBuf),
+    #[error("Snapshot copy of file {0:?} doesn't match its recorded hash")]
+    CorruptedFile(PathBuf),
+    #[error("Fail to serialize report: {0}")]
+    Serde(#[from] serde_json::Error),
+}
+
+/// Bump this whenever the snapshot layout or manifest format changes.
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+pub const SNAPSHOT_MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// Sub-directory of the snapshot holding the copies of the snapshotted files.
+pub const SNAPSHOT_FILES_DIR_NAME: &str = "files";
+
+/// Project files which are snapshotted if they exist, besides the files of the resources.
+const PROJECT_CONFIG_FILES: &[&str] = &["transifex.yaml", ".tx/transifex.yaml", ".tx/config"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SnapshotFileKind {
+    ProjectConfig,
+    Source,
+    Translation,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotFile {
+    /// Path relative to the project root, with `/` separators.
+    pub path: PathBuf,
+    /// FNV-1a hash of the file content, in hex.
+    pub hash: String,
+    pub kind: SnapshotFileKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    pub version: u32,
+    pub project_path: PathBuf,
+    /// Creation time, in seconds since the Unix epoch.
+    pub created_secs: u64,
+    pub files: Vec<SnapshotFile>,
+    /// Stats of every snapshotted file which can be parsed, paths are relative to the project root.
+    pub stats: Vec<StatsEntry>,
+}
+
+/// Result of [`restore_snapshot`].
+#[derive(Debug, Default, PartialEq)]
+pub struct RestoreSummary {
+    /// Files written back.
+    pub restored: Vec<PathBuf>,
+    /// Files already identical to the snapshot.
+    pub unchanged: Vec<PathBuf>,
+    /// Files changed since the snapshot, which are only overwritten if forced.
+    pub refused: Vec<PathBuf>,
+}
+
+fn content_hash(content: &[u8]) -> String {
+    format!("{:016x}", hash_bytes(content))
+}
+
+fn read_file(file_path: &Path) -> Result<Vec<u8>, CmdError> {
+    fs::read(file_path).map_err(|e| CmdError::ReadFile(file_path.to_path_buf(), e))
+}
+
+fn write_file(file_path: &Path, content: &[u8]) -> Result<(), CmdError> {
+    if let Some(parent_dir) = file_path.parent() {
+        fs::create_dir_all(parent_dir).map_err(|e| CmdError::WriteFile(file_path.to_path_buf(), e))?;
+    }
+    fs::write(file_path, content).map_err(|e| CmdError::WriteFile(file_path.to_path_buf(), e))
+}
+
+/// Manifests come from disk, don't let them write outside of the project root.
+fn is_safe_relative_path(path: &Path) -> bool {
+    path.components().all(|component| matches!(component, Component::Normal(_)))
+}
+
+/// A file of the project which belongs into a snapshot.
+struct CollectedFile {
+    /// Relative to the project root.
+    path: PathBuf,
+    kind: SnapshotFileKind,
+    /// Source file of the resource, and language code of translation files.
+    resource: Option<(String, Option<String>)>,
+}
+
+fn collect_snapshot_files(project_root: &PathBuf, tx_yaml: &TransifexYaml) -> Result<Vec<CollectedFile>, CmdError> {
+    let mut files = BTreeMap::<PathBuf, CollectedFile>::new();
+    for config_file in PROJECT_CONFIG_FILES {
+        if project_root.join(config_file).is_file() {
+            let path = PathBuf::from(config_file);
+            files.insert(path.clone(), CollectedFile { path, kind: SnapshotFileKind::ProjectConfig, resource: None });
+        }
+    }
+    for filter in &tx_yaml.filters {
+        if project_root.join(&filter.source).is_file() {
+            let path = PathBuf::from(&filter.source);
+            files.entry(path.clone()).or_insert(CollectedFile { path, kind: SnapshotFileKind::Source, resource: Some((filter.source.clone(), None)) });
+        }
+        let matched = match filter.match_target_files(project_root) {
+            Ok(matched) => matched,
+            // no translations yet
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(CmdError::MatchResources(e)),
+        };
+        for (lang, target_file) in matched {
+            if let Ok(path) = target_file.strip_prefix(project_root) {
+                let path = path.to_path_buf();
+                files.entry(path.clone()).or_insert(CollectedFile { path, kind: SnapshotFileKind::Translation, resource: Some((filter.source.clone(), Some(lang))) });
+            }
+        }
+    }
+    Ok(files.into_values().collect())
+}
+
+/// Capture the current state of the project, and return the captured files with their content.
+fn capture_project_state(project_root: &PathBuf) -> Result<(SnapshotManifest, Vec<Vec<u8>>), CmdError> {
+    let (_, tx_yaml) = try_laod_transifex_project_file(project_root)?;
+    let mut manifest = SnapshotManifest {
+        version: SNAPSHOT_VERSION,
+        project_path: project_root.clone(),
+        created_secs: SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or_default(),
+        files: Vec::new(),
+        stats: Vec::new(),
+    };
+    let mut contents = Vec::new();
+    for file in collect_snapshot_files(project_root, &tx_yaml)? {
+        let file_path = project_root.join(&file.path);
+        let content = read_file(&file_path)?;
+        // stats are informational, files which can't be parsed are still snapshotted
+        if let (Some((resource, language)), Ok(stats)) = (file.resource, load_file_stats(&file_path)) {
+            manifest.stats.push(StatsEntry { resource, language, path: Some(file.path.clone()), stats, slug: None, remote: None });
+        }
+        manifest.files.push(SnapshotFile { path: file.path, hash: content_hash(&content), kind: file.kind });
+        contents.push(content);
+    }
+    Ok((manifest, contents))
+}
+
+/// Snapshot the project files, their content hashes and their stats into the given directory.
+///
+/// Refuses to write into an existing non-empty directory, so older snapshots are never overwritten.
+pub fn create_snapshot(project_root: &PathBuf, snapshot_dir: &Path) -> Result<SnapshotManifest, CmdError> {
+    if fs::read_dir(snapshot_dir).is_ok_and(|mut entries| entries.next().is_some()) {
+        return Err(CmdError::SnapshotExists(snapshot_dir.to_path_buf()));
+    }
+    let (manifest, contents) = capture_project_state(project_root)?;
+    let files_dir = snapshot_dir.join(SNAPSHOT_FILES_DIR_NAME);
+    for (file, content) in manifest.files.iter().zip(&contents) {
+        write_file(&files_dir.join(&file.path), content)?;
+    }
+    // the manifest is written last, so an interrupted snapshot can't be loaded
+    write_file(&snapshot_dir.join(SNAPSHOT_MANIFEST_FILE_NAME), &serde_json::to_vec_pretty(&manifest)?)?;
+    Ok(manifest)
+}
+
+/// Load the manifest of the given snapshot directory.
+pub fn load_snapshot_manifest(snapshot_dir: &Path) -> Result<SnapshotManifest, CmdError> {
+    let manifest_file = snapshot_dir.join(SNAPSHOT_MANIFEST_FILE_NAME);
+    let content = read_file(&manifest_file)?;
+    let manifest = serde_json::from_slice::<SnapshotManifest>(&content)
+        .map_err(|e| CmdError::ParseManifest(manifest_file.clone(), e))?;
+    if manifest.version != SNAPSHOT_VERSION {
+        return Err(CmdError::UnsupportedVersion(manifest.version));
+    }
+    if let Some(file) = manifest.files.iter().find(|file| !is_safe_relative_path(&file.path)) {
+        return Err(CmdError::InvalidPath(file.path.clone()));
+    }
+    Ok(manifest)
+}
+
+/// Write the snapshotted files back into the project.
+///
+/// Every snapshot copy is verified against its recorded hash before anything is written. Missing files
+/// are restored, but files changed since the snapshot are only overwritten if `force` is set, since
+/// they may hold work done after the snapshot, review them with [`diff_snapshot`] first.
+pub fn restore_snapshot(project_root: &Path, snapshot_dir: &Path, force: bool) -> Result<RestoreSummary, CmdError> {
+    let manifest = load_snapshot_manifest(snapshot_dir)?;
+    let files_dir = snapshot_dir.join(SNAPSHOT_FILES_DIR_NAME);
+    let mut contents = Vec::new();
+    for file in &manifest.files {
+        let content = read_file(&files_dir.join(&file.path))?;
+        if content_hash(&content) != file.hash {
+            return Err(CmdError::CorruptedFile(file.path.clone()));
+        }
+        contents.push(content);
+    }
+
+    let mut summary = RestoreSummary::default();
+    for (file, content) in manifest.files.iter().zip(contents) {
+        let file_path = project_root.join(&file.path);
+        match fs::read(&file_path) {
+            Ok(current) if content_hash(&current) == file.hash => {
+                summary.unchanged.push(file.path.clone());
+                continue;
+            },
+            Ok(_) if !force => {
+                summary.refused.push(file.path.clone());
+                continue;
+            },
+            Ok(_) => {},
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {},
+            Err(e) => return Err(CmdError::ReadFile(file_path, e)),
+        }
+        write_file(&file_path, &content)?;
+        summary.restored.push(file.path.clone());
+    }
+    Ok(summary)
+}
+
+/// Compare the current state of the project with the given snapshot.
+///
+/// Every added, removed and modified file is reported as a finding, with the stats before and after
+/// the change as data if known. Stats of the report are the current stats.
+pub fn diff_snapshot(project_root: &PathBuf, snapshot_dir: &Path) -> Result<Report, CmdError> {
+    let snapshot = load_snapshot_manifest(snapshot_dir)?;
+    let (current, _) = capture_project_state(project_root)?;
+    let stats_of = |manifest: &SnapshotManifest, path: &Path| {
+        manifest.stats.iter().find(|entry| entry.path.as_deref() == Some(path)).map(|entry| entry.stats.clone())
+    };
+
+    let mut report = Report::default();
+    for file in &snapshot.files {
+        match current.files.iter().find(|current_file| current_file.path == file.path) {
+            None => report.push(Finding::new(Severity::Warning, issue_ids::SNAPSHOT_FILE_REMOVED, "File removed since the snapshot")
+                .with_path(&file.path)),
+            Some(current_file) if current_file.hash != file.hash => {
+                report.push(Finding::new(Severity::Info, issue_ids::SNAPSHOT_FILE_MODIFIED, "File modified since the snapshot")
+                    .with_path(&file.path)
+                    .with_data(serde_json::json!({
+                        "before": stats_of(&snapshot, &file.path),
+                        "after": stats_of(&current, &file.path),
+                    })));
+            },
+            Some(_) => {},
+        }
+    }
+    for file in &current.files {
+        if !snapshot.files.iter().any(|snapshot_file| snapshot_file.path == file.path) {
+            report.push(Finding::new(Severity::Info, issue_ids::SNAPSHOT_FILE_ADDED, "File added since the snapshot")
+                .with_path(&file.path));
+        }
+    }
+    report.stats = current.stats;
+    Ok(report)
+}
+
+pub fn subcmd_snapshot_create(project_root: &PathBuf, snapshot_dir: &Path) -> Result<(), CmdError> {
+    let manifest = create_snapshot(project_root, snapshot_dir)?;
+    println!("Snapshotted {} file(s) of {project_root:?} into {snapshot_dir:?}", manifest.files.len());
+    Ok(())
+}
+
+pub fn subcmd_snapshot_restore(project_root: &PathBuf, snapshot_dir: &Path, force: bool) -> Result<(), CmdError> {
+    let summary = restore_snapshot(project_root, snapshot_dir, force)?;
+    for path in &summary.restored {
+        println!("Restored: {path:?}");
+    }
+    for path in &summary.refused {
+        println!("Warning: {path:?} changed since the snapshot, use --force to overwrite it.");
+    }
+    println!("Restored {} file(s), {} unchanged, {} refused.", summary.restored.len(), summary.unchanged.len(), summary.refused.len());
+    Ok(())
+}
+
+pub fn subcmd_snapshot_diff(project_root: &PathBuf, snapshot_dir: &Path, json: bool) -> Result<(), CmdError> {
+    let report = diff_snapshot(project_root, snapshot_dir)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        for finding in &report.findings {
+            println!("{finding}");
+        }
+        println!("{} file(s) changed since the snapshot.", report.findings.len());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::i18n_file::linguist::tests::TEST_ZH_CN_TS_CONTENT;
+    use crate::transifex::yaml_file::tests::{TEST_TX_YAML_CONTENT, create_test_project_dir};
+
+    #[test]
+    fn tst_snapshot_restore_and_diff() {
+        let project_root = create_test_project_dir("tst_snapshot_restore_and_diff");
+        let snapshot_dir = project_root.join("snapshot");
+        let project_root = project_root.join("project");
+        fs::create_dir_all(project_root.join("translations")).unwrap();
+        fs::write(project_root.join("transifex.yaml"), TEST_TX_YAML_CONTENT.replace(
+            "shell-launcher-applet/translations/org.deepin.ds.dock.launcherapplet", "translations/app")).unwrap();
+        fs::write(project_root.join("translations/app.ts"), TEST_ZH_CN_TS_CONTENT).unwrap();
+        fs::write(project_root.join("translations/app_zh_CN.ts"), TEST_ZH_CN_TS_CONTENT).unwrap();
+        fs::write(project_root.join("translations/app_ja.ts"), TEST_ZH_CN_TS_CONTENT).unwrap();
+
+        let manifest = create_snapshot(&project_root, &snapshot_dir).unwrap();
+        assert_eq!(manifest.files.len(), 4);
+        assert_eq!(manifest.stats.iter().filter(|entry| entry.language.is_some()).count(), 2);
+        assert!(matches!(create_snapshot(&project_root, &snapshot_dir), Err(CmdError::SnapshotExists(_))));
+        assert!(diff_snapshot(&project_root, &snapshot_dir).unwrap().findings.is_empty());
+
+        fs::write(project_root.join("translations/app_zh_CN.ts"), "<TS/>").unwrap();
+        fs::remove_file(project_root.join("translations/app_ja.ts")).unwrap();
+        fs::write(project_root.join("translations/app_ko.ts"), TEST_ZH_CN_TS_CONTENT).unwrap();
+        let report = diff_snapshot(&project_root, &snapshot_dir).unwrap();
+        assert_eq!(report.filter_by_id(issue_ids::SNAPSHOT_FILE_MODIFIED).count(), 1);
+        assert_eq!(report.filter_by_id(issue_ids::SNAPSHOT_FILE_REMOVED).count(), 1);
+        assert_eq!(report.filter_by_id(issue_ids::SNAPSHOT_FILE_ADDED).count(), 1);
+
+        // changed files are kept unless forced
+        let summary = restore_snapshot(&project_root, &snapshot_dir, false).unwrap();
+        assert_eq!(summary.restored, vec![PathBuf::from("translations/app_ja.ts")]);
+        assert_eq!(summary.refused, vec![PathBuf::from("translations/app_zh_CN.ts")]);
+        let summary = restore_snapshot(&project_root, &snapshot_dir, true).unwrap();
+        assert_eq!(summary.restored, vec![PathBuf::from("translations/app_zh_CN.ts")]);
+        assert_eq!(fs::read_to_string(project_root.join("translations/app_zh_CN.ts")).unwrap(), TEST_ZH_CN_TS_CONTENT);
+
+        // corrupted snapshot copies are detected before anything is written
+        fs::write(snapshot_dir.join(SNAPSHOT_FILES_DIR_NAME).join("translations/app_ja.ts"), "<TS/>").unwrap();
+        assert!(matches!(restore_snapshot(&project_root, &snapshot_dir, true), Err(CmdError::CorruptedFile(_))));
+    }
+}
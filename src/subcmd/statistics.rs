@@ -4,9 +4,16 @@
 
 use serde::Serialize;
 use thiserror::Error as TeError;
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use rayon::prelude::*;
 use crate::transifex::{yaml_file::*, tx_config_file::*};
-use crate::i18n_file::{self, common::{MessageStats, I18nFileKind}};
+use crate::report::{Report, StatsEntry};
+use crate::transifex::rest_api::TransifexRestApi;
+use crate::transifex::resource_stats::{ResourceStats, RemoteResourceStats, join_resource_stats, resource_stats_report};
+use crate::i18n_file::{self, common::{MessageStats, I18nFileKind}, sniff::{SniffedFormat, sniff_file}};
+use super::cache::{FileFingerprint, StatsCache};
 
 #[derive(TeError, Debug)]
 pub enum CmdError {
@@ -16,12 +23,18 @@ pub enum CmdError {
     LoadTsFile(PathBuf, #[source] i18n_file::linguist::TsLoadError),
     #[error("Fail to load Gettext PO/POT file {0:?} because: {1}")]
     LoadPoError(PathBuf, #[source] i18n_file::gettext::PoLoadError),
+    #[error("Can not read file {0:?} because: {1}")]
+    SniffFile(PathBuf, #[source] std::io::Error),
+    #[error("Content of file {0:?} looks like {1} which does not match its file extension")]
+    MismatchedContent(PathBuf, SniffedFormat),
     #[error("Fail to load Transifex project file because: {0}")]
     LoadTxProjectFile(#[from] TxProjectFileLoadError),
     #[error("Fail to match resources because: {0}")]
     MatchResources(#[source] std::io::Error),
     #[error("Fail to serialize stats: {0}")]
     Serde(#[from] serde_yml::Error),
+    #[error("Fail to create thread pool because: {0}")]
+    ThreadPool(#[from] rayon::ThreadPoolBuildError),
 }
 
 #[derive(clap::ValueEnum, Clone, Default, Copy, Debug)]
@@ -45,9 +58,19 @@ struct ProjectResourceStats {
     resource_groups: Vec<TsResourceGroupStats>,
 }
 
-fn load_file_stats(file_path: &Path) -> Result<MessageStats, CmdError> {
+pub(crate) fn load_file_stats(file_path: &Path) -> Result<MessageStats, CmdError> {
     let kind = i18n_file::common::I18nFileKind::from_ext_hint(&file_path)
         .map_err(|e| CmdError::GuessI18nFileType(file_path.to_path_buf(), e))?;
+    // Avoid feeding the file to the wrong parser.
+    let sniffed = sniff_file(file_path)
+        .map_err(|e| CmdError::SniffFile(file_path.to_path_buf(), e))?;
+    if !sniffed.matches_kind(&kind) {
+        return Err(CmdError::MismatchedContent(file_path.to_path_buf(), sniffed));
+    }
+    // an empty PO file is valid, it just has no entries.
+    if sniffed == SniffedFormat::Empty {
+        return Ok(MessageStats::default());
+    }
 
     Ok(match kind {
         I18nFileKind::Linguist => i18n_file::linguist::Ts::load_from_file(&file_path)
@@ -151,7 +174,7 @@ pub enum TxProjectFileLoadError {
 /// Try find transifex.yaml in `project_root/transifex.yaml`.
 /// And if not found, try `project_root/.tx/transifex.yaml`.
 /// If still not found, return error.
-fn try_laod_transifex_project_file(project_root: &PathBuf) -> Result<(PathBuf, TransifexYaml), TxProjectFileLoadError> {
+pub(crate) fn try_laod_transifex_project_file(project_root: &PathBuf) -> Result<(PathBuf, TransifexYaml), TxProjectFileLoadError> {
     try_laod_transifex_yaml_file(project_root).or_else(|e| {
         try_laod_tx_config_file(project_root).map(|(tx_config_file, tx_config)| {
             let tx_yaml = tx_config.to_transifex_yaml();
@@ -160,48 +183,240 @@ fn try_laod_transifex_project_file(project_root: &PathBuf) -> Result<(PathBuf, T
     })
 }
 
-pub fn subcmd_statistics(project_root: &PathBuf, format: StatsFormat, sort_by: StatsSortBy, ignore_languages: Vec<String>) -> Result<(), CmdError> {
-    let (transifex_yaml_file, tx_yaml) = try_laod_transifex_project_file(project_root)?;
-    if matches!(format, StatsFormat::PlainTable) {
-        println!("Found Transifex project config file at: {transifex_yaml_file:?}");
+/// Parse the given files and compute their message stats in parallel.
+///
+/// Results are returned in the same order as the given files, regardless of scheduling.
+/// `jobs` caps the number of worker threads, and defaults to the number of CPUs if not provided.
+/// `on_progress` is called with the number of processed files and the total number of files
+/// after each file is processed, possibly from multiple threads.
+pub fn compute_files_stats(files: &[PathBuf], jobs: Option<usize>, on_progress: impl Fn(usize, usize) + Sync) -> Result<Vec<Result<MessageStats, CmdError>>, CmdError> {
+    compute_files_stats_with(files, jobs, on_progress, load_file_stats)
+}
+
+fn compute_files_stats_with(
+    files: &[PathBuf],
+    jobs: Option<usize>,
+    on_progress: impl Fn(usize, usize) + Sync,
+    load: impl Fn(&Path) -> Result<MessageStats, CmdError> + Sync,
+) -> Result<Vec<Result<MessageStats, CmdError>>, CmdError> {
+    let mut pool_builder = rayon::ThreadPoolBuilder::new();
+    if let Some(jobs) = jobs {
+        pool_builder = pool_builder.num_threads(jobs);
     }
-    let mut project_stats = ProjectResourceStats::default();
-    project_stats.project_path = project_root.clone();
+    let pool = pool_builder.build()?;
+
+    let total = files.len();
+    let processed = AtomicUsize::new(0);
+    Ok(pool.install(|| {
+        files.par_iter().map(|file_path| {
+            // Errors are isolated per file, one broken file doesn't stop others from being parsed.
+            let result = load(file_path);
+            let processed = processed.fetch_add(1, Ordering::SeqCst) + 1;
+            on_progress(processed, total);
+            result
+        }).collect()
+    }))
+}
+
+/// A filter, its source file, and its matched language codes and target files.
+type ResourceGroup<'a> = (&'a Filter, PathBuf, Vec<(String, PathBuf)>);
 
+/// Collect resources we can compute stats for, and their files, so they can be parsed in parallel later.
+fn collect_resource_groups<'a>(project_root: &PathBuf, tx_yaml: &'a TransifexYaml, ignore_languages: &[String], verbose: bool) -> Result<Vec<ResourceGroup<'a>>, CmdError> {
+    let mut resource_groups = Vec::<ResourceGroup>::new();
     for filter in &tx_yaml.filters {
         if (filter.format != "QT" && filter.format != "PO") || filter.type_attr != "file" {
-            if matches!(format, StatsFormat::PlainTable) {
+            if verbose {
                 println!("Skipping resource {:?} with format {:?}...", filter.source, filter.format);
             }
             continue;
         }
-        let mut source_group_stats = TsResourceGroupStats::default();
         let source_file = project_root.join(&filter.source);
         // check if project_root/filter.source_file exists, and print stats of the source file if exists.
         if source_file.is_file() {
-            if matches!(format, StatsFormat::PlainTable) {
+            if verbose {
                 println!("Hit source file at: {source_file:?}");
             }
-            let content_stats = load_file_stats(&source_file)?;
-            source_group_stats.source_path = source_file.clone();
-            source_group_stats.source_lang_code = filter.source_lang.clone();
-            source_group_stats.source_stats = content_stats;
         } else {
-            if matches!(format, StatsFormat::PlainTable) {
+            if verbose {
                 println!("Missing source resource: {source_file:?}");
             }
             continue;
         }
 
-        let matched_resources = filter.match_target_files(project_root).or_else(|e| { Err(CmdError::MatchResources(e)) })?;
-        for (lang, target_file) in matched_resources {
-            if ignore_languages.contains(&lang) {
+        let matches = filter.match_target_files_with_skipped(project_root).or_else(|e| { Err(CmdError::MatchResources(e)) })?;
+        if verbose {
+            for skipped_file in &matches.skipped_non_utf8 {
+                println!("Warning: skipping file with non UTF-8 file name: {skipped_file:?}");
+            }
+            for (skipped_file, e) in &matches.skipped_unreadable {
+                println!("Warning: skipping unreadable file {skipped_file:?}: {e}");
+            }
+        }
+        let mut matched_resources = matches.matched;
+        matched_resources.retain(|(lang, _)| !ignore_languages.contains(lang));
+        matched_resources.sort();
+        resource_groups.push((filter, source_file, matched_resources));
+    }
+    Ok(resource_groups)
+}
+
+/// All files of the given resource groups, sorted and deduplicated.
+///
+/// Sorted before merge, so the first reported error doesn't depend on scheduling.
+fn resource_group_files(resource_groups: &[ResourceGroup]) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = resource_groups.iter()
+        .flat_map(|(_, source_file, matched_resources)| {
+            std::iter::once(source_file.clone()).chain(matched_resources.iter().map(|(_, target_file)| target_file.clone()))
+        })
+        .collect();
+    files.sort();
+    files.dedup();
+    files
+}
+
+/// Print local stats and the stats reported by Transifex side by side, keyed by resource slug.
+///
+/// Shows local stats only if there is no Transifex API token or the stats can't be fetched.
+/// Returns `false` without printing anything if the project has no `.tx/config` mapping files to resource slugs.
+fn print_resource_stats(project_root: &PathBuf, stats_by_file: &BTreeMap<PathBuf, MessageStats>, format: StatsFormat, divergence_threshold: f64) -> Result<bool, CmdError> {
+    let verbose = matches!(format, StatsFormat::PlainTable);
+    let Ok((_, tx_config)) = try_laod_tx_config_file(project_root) else {
+        if verbose {
+            println!("Warning: no .tx/config file found to look up resource slugs, showing local stats only");
+        }
+        return Ok(false);
+    };
+    let mut remote = RemoteResourceStats::new();
+    match TransifexRestApi::new_from_transifexrc() {
+        Ok(client) => {
+            for section in &tx_config.resource_sections {
+                match client.get_resource_language_stats(&section.resource_full_slug) {
+                    Ok(stats) => {
+                        remote.insert(section.resource_full_slug.clone(), stats);
+                    },
+                    Err(e) => if verbose {
+                        println!("Warning: fail to fetch stats of resource {} because: {e}", section.resource_full_slug);
+                    },
+                }
+            }
+        },
+        Err(e) => if verbose {
+            println!("Warning: no Transifex API token available, showing local stats only because: {e}");
+        },
+    }
+
+    let local = stats_by_file.iter()
+        .filter_map(|(file_path, stats)| Some((file_path.strip_prefix(project_root).ok()?.to_path_buf(), stats.clone())))
+        .collect();
+    let resources = join_resource_stats(&tx_config, &local, &remote);
+    let report = resource_stats_report(&resources, divergence_threshold);
+    match format {
+        StatsFormat::PlainTable => print_resource_stats_plain_table(&resources, &report, divergence_threshold),
+        StatsFormat::Yaml => println!("{}", serde_yml::to_string::<Report>(&report)?),
+    }
+    Ok(true)
+}
+
+fn print_resource_stats_plain_table(resources: &[ResourceStats], report: &Report, divergence_threshold: f64) {
+    for resource in resources {
+        println!();
+        println!("Resource {} ({})", resource.slug, resource.source_file);
+        println!("| Lang   | Local        | Remote       | Divergence |");
+        println!("| ------ | ------------ | ------------ | ---------- |");
+        for language_stats in &resource.languages {
+            let local = language_stats.local.as_ref().map_or("-".to_string(), |stats| format!("{:.2}%", stats.completeness_percentage()));
+            let remote = language_stats.remote.as_ref().map_or("-".to_string(), |stats| format!("{:.2}%", stats.completeness_percentage()));
+            let mark = if language_stats.is_divergent(divergence_threshold) { " !" } else { "" };
+            let divergence = language_stats.divergence().map_or("-".to_string(), |divergence| format!("{divergence:.2}{mark}"));
+            println!("| {0:>6} | {1:>12} | {2:>12} | {3:>10} |", language_stats.language, local, remote, divergence);
+        }
+    }
+    for finding in &report.findings {
+        println!("{finding}");
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn subcmd_statistics(project_root: &PathBuf, format: StatsFormat, sort_by: StatsSortBy, ignore_languages: Vec<String>, jobs: Option<usize>, no_cache: bool, remote: bool, divergence_threshold: f64) -> Result<(), CmdError> {
+    let (transifex_yaml_file, tx_yaml) = try_laod_transifex_project_file(project_root)?;
+    if matches!(format, StatsFormat::PlainTable) {
+        println!("Found Transifex project config file at: {transifex_yaml_file:?}");
+    }
+    let mut project_stats = ProjectResourceStats::default();
+    project_stats.project_path = project_root.clone();
+
+    let resource_groups = collect_resource_groups(project_root, &tx_yaml, &ignore_languages, matches!(format, StatsFormat::PlainTable))?;
+    let files = resource_group_files(&resource_groups);
+
+    // reuse stats of files unchanged since the last run, and only parse the others.
+    let mut cache = if no_cache { None } else { StatsCache::load_default() };
+    let mut stats_by_file = BTreeMap::<PathBuf, MessageStats>::new();
+    let mut files_to_parse = Vec::<PathBuf>::new();
+    let mut fingerprints = Vec::<Option<FileFingerprint>>::new();
+    for file_path in files {
+        let fingerprint = cache.as_ref().and_then(|_| FileFingerprint::of(&file_path).ok());
+        if let (Some((_, cache)), Some(fingerprint)) = (&cache, &fingerprint) {
+            if let Some(stats) = cache.lookup(&file_path, fingerprint) {
+                stats_by_file.insert(file_path, stats.clone());
                 continue;
             }
-            let content_stats = load_file_stats(&target_file)?;
+        }
+        files_to_parse.push(file_path);
+        fingerprints.push(fingerprint);
+    }
+
+    let show_progress = matches!(format, StatsFormat::PlainTable);
+    if show_progress && cache.is_some() {
+        eprintln!("Reused cached stats of {} of {} translation file(s).", stats_by_file.len(), stats_by_file.len() + files_to_parse.len());
+    }
+    let files_stats = compute_files_stats(&files_to_parse, jobs, |processed, total| {
+        if show_progress {
+            eprint!("\rParsing translation files... {processed}/{total}");
+        }
+    })?;
+    if show_progress && !files_to_parse.is_empty() {
+        eprintln!();
+    }
+    let mut first_error = None;
+    for ((file_path, fingerprint), stats) in files_to_parse.into_iter().zip(fingerprints).zip(files_stats) {
+        match stats {
+            Ok(stats) => {
+                if let (Some((_, cache)), Some(fingerprint)) = (&mut cache, fingerprint) {
+                    cache.insert(&file_path, fingerprint, stats.clone());
+                }
+                stats_by_file.insert(file_path, stats);
+            },
+            Err(e) => {
+                first_error.get_or_insert(e);
+            },
+        }
+    }
+    // a failure to update the cache only makes the next run slower.
+    if let Some((cache_file, cache)) = &cache {
+        if let Err(e) = cache.save(cache_file) {
+            if show_progress {
+                println!("Warning: fail to save stats cache to {cache_file:?} because: {e}");
+            }
+        }
+    }
+    if let Some(e) = first_error {
+        return Err(e);
+    }
+    if remote && print_resource_stats(project_root, &stats_by_file, format, divergence_threshold)? {
+        return Ok(());
+    }
+
+    for (filter, source_file, matched_resources) in resource_groups {
+        let mut source_group_stats = TsResourceGroupStats::default();
+        source_group_stats.source_stats = stats_by_file.get(&source_file).cloned().unwrap_or_default();
+        source_group_stats.source_path = source_file;
+        source_group_stats.source_lang_code = filter.source_lang.clone();
+
+        for (lang, target_file) in matched_resources {
             let target_resource_stats = TsResourceStats {
-                resource_path: target_file.clone(),
-                stats: content_stats,
+                stats: stats_by_file.get(&target_file).cloned().unwrap_or_default(),
+                resource_path: target_file,
             };
             source_group_stats.target_lang_codes.push(lang.clone());
             if !project_stats.target_lang_codes.contains(&lang) {
@@ -221,4 +436,199 @@ pub fn subcmd_statistics(project_root: &PathBuf, format: StatsFormat, sort_by: S
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Options of [`compute_workspace_stats`].
+#[derive(Debug, Clone)]
+pub struct WorkspaceStatsOptions {
+    /// Maximum number of repositories processed at the same time.
+    pub max_concurrent_repos: usize,
+    /// Maximum number of files parsed at the same time within one repository.
+    pub jobs_per_repo: usize,
+    pub ignore_languages: Vec<String>,
+}
+
+/// Stats of a repository, reduced from its per-file stats as soon as the repository is processed.
+#[derive(Debug, Default, Serialize)]
+pub struct RepoStatsSummary {
+    pub project_path: PathBuf,
+    pub resources: usize,
+    pub source_stats: MessageStats,
+    /// Total stats of all target files, by language code.
+    pub target_stats: BTreeMap<String, MessageStats>,
+}
+
+impl RepoStatsSummary {
+    /// Convert to report stats entries, one for the source language and one for each target language.
+    pub fn to_stats_entries(&self) -> Vec<StatsEntry> {
+        let resource = self.project_path.to_string_lossy().to_string();
+        std::iter::once(StatsEntry { resource: resource.clone(), language: None, path: None, stats: self.source_stats.clone(), slug: None, remote: None })
+            .chain(self.target_stats.iter().map(|(language, stats)| StatsEntry {
+                resource: resource.clone(),
+                language: Some(language.clone()),
+                path: None,
+                stats: stats.clone(),
+                slug: None,
+                remote: None,
+            }))
+            .collect()
+    }
+}
+
+#[derive(Default)]
+struct WorkspaceHooks {
+    before_repo: Option<Box<dyn Fn(&Path) + Sync>>,
+    live_documents: AtomicUsize,
+    peak_live_documents: AtomicUsize,
+}
+
+/// Compute stats of every repository in a workspace.
+///
+/// At most `max_concurrent_repos × jobs_per_repo` files are parsed at the same time, and parsed
+/// documents are dropped as soon as their stats are known, so memory usage doesn't grow with the
+/// workspace size. Results are sorted by repository path, regardless of completion order.
+pub fn compute_workspace_stats(repos: &[PathBuf], opts: &WorkspaceStatsOptions) -> Result<Vec<(PathBuf, Result<RepoStatsSummary, CmdError>)>, CmdError> {
+    compute_workspace_stats_impl(repos, opts, &WorkspaceHooks::default())
+}
+
+fn compute_workspace_stats_impl(repos: &[PathBuf], opts: &WorkspaceStatsOptions, hooks: &WorkspaceHooks) -> Result<Vec<(PathBuf, Result<RepoStatsSummary, CmdError>)>, CmdError> {
+    let mut repos = repos.to_vec();
+    repos.sort();
+    repos.dedup();
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(opts.max_concurrent_repos.max(1)).build()?;
+    Ok(pool.install(|| {
+        // collecting an indexed parallel iterator keeps the input order.
+        repos.par_iter().map(|repo| {
+            if let Some(before_repo) = &hooks.before_repo {
+                before_repo(repo);
+            }
+            (repo.clone(), compute_repo_stats_summary(repo, opts, hooks))
+        }).collect()
+    }))
+}
+
+fn compute_repo_stats_summary(project_root: &PathBuf, opts: &WorkspaceStatsOptions, hooks: &WorkspaceHooks) -> Result<RepoStatsSummary, CmdError> {
+    let (_, tx_yaml) = try_laod_transifex_project_file(project_root)?;
+    let resource_groups = collect_resource_groups(project_root, &tx_yaml, &opts.ignore_languages, false)?;
+    let files = resource_group_files(&resource_groups);
+    let files_stats = compute_files_stats_with(&files, Some(opts.jobs_per_repo.max(1)), |_, _| {}, |file_path| {
+        let live = hooks.live_documents.fetch_add(1, Ordering::SeqCst) + 1;
+        hooks.peak_live_documents.fetch_max(live, Ordering::SeqCst);
+        let result = load_file_stats(file_path);
+        hooks.live_documents.fetch_sub(1, Ordering::SeqCst);
+        result
+    })?;
+    let mut stats_by_file = BTreeMap::<PathBuf, MessageStats>::new();
+    for (file_path, stats) in files.into_iter().zip(files_stats) {
+        stats_by_file.insert(file_path, stats?);
+    }
+
+    let mut summary = RepoStatsSummary {
+        project_path: project_root.clone(),
+        resources: resource_groups.len(),
+        ..RepoStatsSummary::default()
+    };
+    for (_, source_file, matched_resources) in &resource_groups {
+        if let Some(stats) = stats_by_file.get(source_file) {
+            summary.source_stats += stats;
+        }
+        for (lang, target_file) in matched_resources {
+            if let Some(stats) = stats_by_file.get(target_file) {
+                *summary.target_stats.entry(lang.clone()).or_default() += stats;
+            }
+        }
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use super::*;
+    use crate::i18n_file::linguist::tests::TEST_ZH_CN_TS_CONTENT;
+    use crate::transifex::yaml_file::tests::create_test_project_dir;
+
+    #[test]
+    fn tst_compute_workspace_stats() {
+        let workspace = create_test_project_dir("tst_compute_workspace_stats");
+        let mut repos = Vec::<PathBuf>::new();
+        // reversed, so later repos finish first
+        for idx in (0..4).rev() {
+            let repo = workspace.join(format!("repo_{idx}"));
+            fs::create_dir_all(repo.join("translations")).unwrap();
+            fs::write(repo.join("transifex.yaml"), r#"filters:
+  - filter_type: file
+    source_file: translations/app.ts
+    file_format: QT
+    source_language: en_US
+    translation_files_expression: translations/app_<lang>.ts
+settings:
+  pr_branch_name: transifex_update_<br_unique_id>
+"#).unwrap();
+            fs::write(repo.join("translations/app.ts"), TEST_ZH_CN_TS_CONTENT).unwrap();
+            for lang in ["zh_CN", "zh_TW", "zh_HK", "ja", "ko"] {
+                fs::write(repo.join(format!("translations/app_{lang}.ts")), TEST_ZH_CN_TS_CONTENT).unwrap();
+            }
+            repos.push(repo);
+        }
+
+        let opts = WorkspaceStatsOptions { max_concurrent_repos: 2, jobs_per_repo: 2, ignore_languages: Vec::new() };
+        let hooks = WorkspaceHooks {
+            before_repo: Some(Box::new(|repo: &Path| {
+                let idx: u64 = repo.to_string_lossy().rsplit('_').next().unwrap().parse().unwrap();
+                std::thread::sleep(std::time::Duration::from_millis(40 * (4 - idx)));
+            })),
+            ..WorkspaceHooks::default()
+        };
+        let results = compute_workspace_stats_impl(&repos, &opts, &hooks).unwrap();
+        let result_repos: Vec<PathBuf> = results.iter().map(|(repo, _)| repo.clone()).collect();
+        repos.sort();
+        assert_eq!(result_repos, repos);
+        for (_, summary) in &results {
+            let summary = summary.as_ref().unwrap();
+            assert_eq!(summary.resources, 1);
+            assert_eq!(summary.target_stats.len(), 5);
+        }
+        let peak = hooks.peak_live_documents.load(Ordering::SeqCst);
+        assert!((1..=4).contains(&peak), "peak live documents: {peak}");
+        assert_eq!(hooks.live_documents.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn tst_load_po_file_stats() {
+        let project_root = create_test_project_dir("tst_load_po_file_stats");
+        let file_path = project_root.join("app_zh_CN.po");
+        fs::write(&file_path, "").unwrap();
+        assert_eq!(load_file_stats(&file_path).unwrap(), MessageStats::default());
+        // a PO file without comments
+        fs::write(&file_path, format!("msgid \"\"\nmsgstr \"\"\n\"Language: zh_CN\\n\"\n\nmsgctxt \"{}\"\nmsgid \"Dock\"\nmsgstr \"任务栏\"\n", "x".repeat(1024))).unwrap();
+        assert_eq!(load_file_stats(&file_path).unwrap().finished, 1);
+        fs::write(&file_path, TEST_ZH_CN_TS_CONTENT).unwrap();
+        assert!(matches!(load_file_stats(&file_path), Err(CmdError::MismatchedContent(_, SniffedFormat::Linguist))));
+    }
+
+    #[test]
+    fn tst_compute_files_stats() {
+        let project_root = create_test_project_dir("tst_compute_files_stats");
+        let mut files = Vec::<PathBuf>::new();
+        for idx in 0..16 {
+            let file_path = project_root.join(format!("app_{idx}.ts"));
+            // every fourth file is broken
+            let content = if idx % 4 == 0 { "<TS" } else { TEST_ZH_CN_TS_CONTENT };
+            fs::write(&file_path, content).unwrap();
+            files.push(file_path);
+        }
+
+        let max_processed = AtomicUsize::new(0);
+        let results = compute_files_stats(&files, Some(4), |processed, total| {
+            assert_eq!(total, 16);
+            max_processed.fetch_max(processed, Ordering::SeqCst);
+        }).unwrap();
+        assert_eq!(max_processed.load(Ordering::SeqCst), 16);
+        assert_eq!(results.len(), 16);
+        for (idx, result) in results.iter().enumerate() {
+            // broken files don't affect others, and results keep the input order
+            assert_eq!(result.is_err(), idx % 4 == 0);
+        }
+    }
+}
@@ -0,0 +1,61 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+use std::path::{Path, PathBuf};
+use thiserror::Error as TeError;
+
+use crate::compendium::{Compendium, CompendiumBuilder, CompendiumError};
+use crate::i18n_file::common::{I18nFileKind, UnknownI18nFileExtError};
+use crate::i18n_file::{gettext::{Po, PoSaveError}, linguist::{Ts, TsSaveError}};
+
+#[derive(TeError, Debug)]
+pub enum CmdError {
+    #[error("{0}")]
+    Compendium(#[from] CompendiumError),
+    #[error("Can not guess translation file kind from path {0:?} because: {1}")]
+    GuessI18nFileType(PathBuf, #[source] UnknownI18nFileExtError),
+    #[error("Fail to save file {0:?} because: {1}")]
+    SaveTsFile(PathBuf, #[source] TsSaveError),
+    #[error("Fail to save file {0:?} because: {1}")]
+    SavePoFile(PathBuf, #[source] PoSaveError),
+}
+
+pub fn subcmd_tm_build(language: &str, repo_roots: &[PathBuf], output: &Path) -> Result<(), CmdError> {
+    let mut builder = CompendiumBuilder::new(language);
+    for repo_root in repo_roots {
+        let (added, errors) = builder.add_dir(repo_root);
+        for error in &errors {
+            eprintln!("Warning: skipped because: {error}");
+        }
+        println!("Collected {added} {language} translation file(s) from {repo_root:?}, {} skipped", errors.len());
+    }
+    let compendium = builder.build();
+    compendium.save_into_file(output)?;
+    println!("Wrote {} entries to {}", compendium.entries.len(), output.display());
+    Ok(())
+}
+
+pub fn subcmd_tm_apply(compendium_file: &Path, target_files: &[PathBuf]) -> Result<(), CmdError> {
+    let compendium = Compendium::load_from_file(compendium_file)?;
+    for target_file in target_files {
+        let kind = I18nFileKind::from_ext_hint(target_file)
+            .map_err(|e| CmdError::GuessI18nFileType(target_file.clone(), e))?;
+        let filled = match kind {
+            I18nFileKind::Linguist => {
+                let mut ts = Ts::load_from_file(target_file).map_err(|e| CompendiumError::LoadTs(target_file.clone(), e))?;
+                let filled = compendium.apply_to_ts(&mut ts);
+                ts.save_into_file(target_file).map_err(|e| CmdError::SaveTsFile(target_file.clone(), e))?;
+                filled
+            },
+            I18nFileKind::Gettext => {
+                let mut po = Po::load_from_file(target_file).map_err(|e| CompendiumError::LoadPo(target_file.clone(), e))?;
+                let filled = compendium.apply_to_po(&mut po);
+                po.save_into_file(target_file).map_err(|e| CmdError::SavePoFile(target_file.clone(), e))?;
+                filled
+            },
+        };
+        println!("Pre-translated {filled} message(s) of {target_file:?}, please review them.");
+    }
+    Ok(())
+}
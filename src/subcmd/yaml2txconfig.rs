@@ -5,7 +5,7 @@
 use core::panic;
 use std::fs;
 use std::path::PathBuf;
-use std::io::stdin;
+use std::io::{Write, stdin};
 use directories::ProjectDirs;
 use thiserror::Error as TeError;
 
@@ -18,6 +18,8 @@ use crate::transifex::{
 pub enum CmdY2TCError {
     #[error("Fail to load transifex.yaml file because: {0}")]
     TxYamlLoadError(#[from] TxYamlLoadError),
+    #[error("Fail to convert transifex.yaml file because: {0}")]
+    Format(#[from] FormatError),
 }
 
 fn get_github_repository_from_user_input(project_root: &PathBuf, github_repository_hint: Option<String>) -> String {
@@ -33,7 +35,7 @@ fn get_github_repository_from_user_input(project_root: &PathBuf, github_reposito
         }
 
         let github_repository = format!("{}/{}", "linuxdeepin", repo_name);
-        println!("Is {github_repository:?} your GitHub repo name?\n- If yes, simply press Enter.\n- If not, please enter the repo name in owner/repo format: ");
+        eprintln!("Is {github_repository:?} your GitHub repo name?\n- If yes, simply press Enter.\n- If not, please enter the repo name in owner/repo format: ");
         let mut user_input = String::new();
         repo_name = match stdin().read_line(&mut user_input) {
             Ok(_) => {
@@ -45,7 +47,7 @@ fn get_github_repository_from_user_input(project_root: &PathBuf, github_reposito
                 }
             },
             Err(_) => {
-                println!("Failed to read user input.");
+                eprintln!("Failed to read user input.");
                 panic!();
             }
         }
@@ -63,7 +65,7 @@ fn fetch_project_list(organization_slug: &str, force_online: bool) -> Vec<String
     } else {
         let client = TransifexRestApi::new_from_transifexrc().expect("Failed to create Transifex REST client");
 
-        println!("Fetching o:{organization_slug} project list from Transifex...");
+        eprintln!("Fetching o:{organization_slug} project list from Transifex...");
         let entries = client.get_all_projects(organization_slug).expect("Failed to fetch project resource list");
         let entries = entries.into_iter().map(|entry| entry.id.to_string());
         let entries: Vec<String> = entries.collect();
@@ -80,14 +82,13 @@ fn fetch_linked_resource_list(organization_slug: &str, project_slug: &str, force
     let cache_file = xdg_proj_dirs.cache_dir().join(format!("{organization_slug}/{project_slug}.yaml"));
     
     if cache_file.exists() && !force_online {
-        println!("Reusing o:{organization_slug}:p:{project_slug} project resource list from local cache...");
-        let source_content = fs::read_to_string(&cache_file).expect("Failed to read cached project resource list");
-        let list = serde_yml::from_str::<Vec<TxResourceLookupEntry>>(source_content.as_str()).expect("Failed to parse cached project resource list");
+        eprintln!("Reusing o:{organization_slug}:p:{project_slug} project resource list from local cache...");
+        let list = load_lookup_table_file(&cache_file).expect("Failed to load cached project resource list");
         return list;
     } else {
         let client = TransifexRestApi::new_from_transifexrc().expect("Failed to create Transifex REST client");
 
-        println!("Fetching o:{organization_slug}:p:{project_slug} project resource list from Transifex...");
+        eprintln!("Fetching o:{organization_slug}:p:{project_slug} project resource list from Transifex...");
         let entries = client.get_all_linked_resources(organization_slug, project_slug).expect("Failed to fetch project resource list");
         let entries = entries.into_iter().filter_map(|entry| entry.parse_linked_resource_category()).collect();
         let cache_content = serde_yml::to_string::<Vec<TxResourceLookupEntry>>(&entries).expect("Failed to serialize project resource list as cache");
@@ -120,27 +121,61 @@ pub fn create_linked_resources_table(organization_slug: &str, project_slug: Opti
     lookup_table
 }
 
-pub fn subcmd_yaml2txconfig(project_root: &PathBuf, force_online: bool, github_repository: Option<String>, organization_slug: String, project_slug: Option<String>) -> Result<(), CmdY2TCError> {
+pub fn subcmd_yaml2txconfig(project_root: &PathBuf, force_online: bool, github_repository: Option<String>, organization_slug: String, project_slug: Option<String>, dry_run: bool) -> Result<(), CmdY2TCError> {
+    // stdout of dry runs is the config alone, so it can be redirected to .tx/config
+    let status = |message: String| if dry_run { eprintln!("{message}") } else { println!("{message}") };
     let (transifex_yaml_file, tx_yaml) = try_laod_transifex_yaml_file(project_root)?;
-    println!("Found Transifex project config file at: {transifex_yaml_file:?}");
+    status(format!("Found Transifex project config file at: {transifex_yaml_file:?}"));
 
     let github_repository = get_github_repository_from_user_input(project_root, github_repository);
-    println!("GitHub repository name: {github_repository}");
+    status(format!("GitHub repository name: {github_repository}"));
     
     let lookup_table = create_linked_resources_table(&organization_slug, project_slug, force_online);
-    let tx_config = tx_yaml.to_tx_config(github_repository, lookup_table);
+    write_tx_config(&mut std::io::stdout(), project_root, &tx_yaml, &github_repository, &lookup_table, dry_run)
+}
+
+/// Convert to .tx/config and write it unless it exists already. Dry runs write the config to `out`
+/// and nothing else.
+fn write_tx_config(out: &mut impl Write, project_root: &PathBuf, tx_yaml: &TransifexYaml, github_repository: &str, lookup_table: &[TxResourceLookupEntry], dry_run: bool) -> Result<(), CmdY2TCError> {
+    for duplicate in tx_yaml.duplicate_resolved_slugs(github_repository, lookup_table, None) {
+        eprintln!("Warning: {duplicate}, these resources would overwrite each other");
+    }
+    let tx_config = tx_yaml.try_to_tx_config(github_repository, lookup_table)?;
+    if dry_run {
+        write!(out, "{}", tx_config.to_str()).expect("Failed to print .tx/config");
+        return Ok(());
+    }
 
     let tx_config_file = project_root.join(".tx/config");
     if tx_config_file.exists() {
-        println!("Note: {tx_config_file:?} file already exists, not overwriting it.");
-        println!("You can use the following context to update the file manually:\n");
-        println!("{}", tx_config.to_str());
+        writeln!(out, "Note: {tx_config_file:?} file already exists, not overwriting it.").expect("Failed to print note");
+        writeln!(out, "You can use the following context to update the file manually:\n").expect("Failed to print note");
+        writeln!(out, "{}", tx_config.to_str()).expect("Failed to print .tx/config");
     } else {
         let parent_dir = tx_config_file.parent().unwrap();
         fs::create_dir_all(&parent_dir).expect("Failed to create .tx directory");
         fs::write(&tx_config_file, tx_config.to_str()).expect("Failed to write .tx/config file");
-        println!("Generated .tx/config file at: {tx_config_file:?}");
+        writeln!(out, "Generated .tx/config file at: {tx_config_file:?}").expect("Failed to print note");
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transifex::tx_config_file::TxConfig;
+    use crate::transifex::yaml_file::tests::{TEST_TX_YAML_CONTENT, create_test_project_dir};
+
+    #[test]
+    fn tst_dry_run_prints_config_only() {
+        let project_root = create_test_project_dir("tst_yaml2txconfig_dry_run");
+        let tx_yaml = TransifexYaml::from_str(TEST_TX_YAML_CONTENT).unwrap();
+        let mut out = Vec::<u8>::new();
+        write_tx_config(&mut out, &project_root, &tx_yaml, "linuxdeepin/dde-shell", &[], true).unwrap();
+        let tx_config = TxConfig::from_str(&String::from_utf8(out).unwrap()).unwrap();
+        assert_eq!(tx_config.resource_sections.len(), 1);
+        assert_eq!(tx_config.resource_sections[0].source_file, "shell-launcher-applet/translations/org.deepin.ds.dock.launcherapplet.ts");
+        assert!(!project_root.join(".tx/config").exists());
+    }
+}
@@ -2,6 +2,43 @@
 //
 // SPDX-License-Identifier: MIT
 
+//! Transifex project files and resources.
+
+/// `transifex.yaml` files of the Transifex GitHub integration.
 pub mod yaml_file;
+/// Matching translation files against the target patterns of `transifex.yaml` filters.
+pub mod target_match;
+/// Project-wide reports over the translation files matched by `transifex.yaml` filters.
+pub mod project_files;
+/// Checks of `transifex.yaml` files and their project files.
+pub mod validation;
+/// Generation of `.tx/config` files from `transifex.yaml` files.
+pub mod generate;
+/// Relationship graph of source files, resource slugs and locales.
+pub mod config_graph;
+/// Streaming of the filters of large `transifex.yaml` files.
+pub mod filter_stream;
+/// Quoting-preserving serialization of `transifex.yaml` files.
+pub mod yaml_quoting;
+/// Loading `transifex.yaml` files over HTTP(S).
+#[cfg(feature = "http")]
+pub mod yaml_http;
+/// `.tx/config` and `.transifexrc` files of the Transifex CLI.
 pub mod tx_config_file;
-pub mod rest_api;
\ No newline at end of file
+/// Transifex REST API client.
+#[cfg(feature = "rest-api")]
+pub mod rest_api;
+/// Resource slug helpers.
+pub mod resource_slug;
+/// Language codes supported by Transifex.
+pub mod supported_locales;
+/// English language names, for human friendly sorting of language codes.
+pub mod locale_names;
+/// On-disk cache of matched translation files.
+pub mod match_cache;
+/// Local stats joined with the stats reported by Transifex.
+pub mod resource_stats;
+/// Checks across several `transifex.yaml` files.
+pub mod workspace;
+
+pub use supported_locales::{is_known_locale, is_supported_locale};
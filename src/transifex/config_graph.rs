@@ -0,0 +1,152 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+// Relationship graph of the source files, resource slugs and locales of a transifex.yaml file.
+
+use std::path::PathBuf;
+use serde::Serialize;
+
+use super::yaml_file::{MatchError, TransifexYaml, TxResourceLookupEntry};
+
+/// Kind of a [`ConfigGraphNode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConfigGraphNodeKind {
+    /// Source file of a filter.
+    SourceFile,
+    /// Full resource slug.
+    ResourceSlug,
+    /// Language code of matched translation files.
+    Locale,
+}
+
+impl ConfigGraphNodeKind {
+    fn id_prefix(&self) -> &'static str {
+        match self {
+            Self::SourceFile => "file",
+            Self::ResourceSlug => "slug",
+            Self::Locale => "locale",
+        }
+    }
+}
+
+/// A node of a [`ConfigGraph`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConfigGraphNode {
+    /// Unique id, i.e. the kind and the label, e.g. `file:translations/app.ts`.
+    pub id: String,
+    /// What the node is.
+    pub kind: ConfigGraphNodeKind,
+    /// Source file path, slug or language code.
+    pub label: String,
+}
+
+/// A directed edge of a [`ConfigGraph`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConfigGraphEdge {
+    /// Id of the node the edge starts from.
+    pub from: String,
+    /// Id of the node the edge points to.
+    pub to: String,
+    /// Translation file of edges from a slug to a locale.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+/// Graph returned by [`TransifexYaml::dependency_graph`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ConfigGraph {
+    /// Nodes, in the order they are found. Each node is only listed once.
+    pub nodes: Vec<ConfigGraphNode>,
+    /// Edges, in the order they are found.
+    pub edges: Vec<ConfigGraphEdge>,
+}
+
+impl ConfigGraph {
+    /// Add a node unless it already exists, and return its id.
+    fn add_node(&mut self, kind: ConfigGraphNodeKind, label: &str) -> String {
+        let id = format!("{}:{label}", kind.id_prefix());
+        if !self.nodes.iter().any(|node| node.id == id) {
+            self.nodes.push(ConfigGraphNode { id: id.clone(), kind, label: label.to_string() });
+        }
+        id
+    }
+
+    /// Render the graph in the Graphviz DOT language.
+    pub fn to_dot(&self) -> String {
+        let quote = |text: &str| format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""));
+        let mut dot = String::from("digraph config {\n");
+        for node in &self.nodes {
+            let shape = match node.kind {
+                ConfigGraphNodeKind::SourceFile => "note",
+                ConfigGraphNodeKind::ResourceSlug => "box",
+                ConfigGraphNodeKind::Locale => "ellipse",
+            };
+            dot.push_str(&format!("  {} [label={}, shape={shape}];\n", quote(&node.id), quote(&node.label)));
+        }
+        for edge in &self.edges {
+            match &edge.label {
+                Some(label) => dot.push_str(&format!("  {} -> {} [label={}];\n", quote(&edge.from), quote(&edge.to), quote(label))),
+                None => dot.push_str(&format!("  {} -> {};\n", quote(&edge.from), quote(&edge.to))),
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+impl TransifexYaml {
+    /// Export the relationship source file → resource slug → locales as a graph, e.g. to visualize it as JSON or DOT.
+    ///
+    /// Slugs are resolved the same way as [`Self::to_tx_config`]. Edges from a slug to a locale are
+    /// labeled with the matched translation file. Filters whose target directory doesn't exist have no locales.
+    pub fn dependency_graph(&self, github_repository: &str, lookup_table: &[TxResourceLookupEntry], project_root: &PathBuf) -> Result<ConfigGraph, MatchError> {
+        let mut graph = ConfigGraph::default();
+        for filter in &self.filters {
+            let source_id = graph.add_node(ConfigGraphNodeKind::SourceFile, &filter.source);
+            let slug = filter.resolve_resource_full_slug(github_repository, lookup_table, None);
+            let slug_id = graph.add_node(ConfigGraphNodeKind::ResourceSlug, &slug);
+            graph.edges.push(ConfigGraphEdge { from: source_id, to: slug_id.clone(), label: None });
+            let mut matched = filter.matched_files(project_root)?;
+            matched.sort();
+            for (lang_code, target_file) in matched {
+                let locale_id = graph.add_node(ConfigGraphNodeKind::Locale, &lang_code);
+                let label = target_file.strip_prefix(project_root).unwrap_or(&target_file).to_string_lossy().replace('\\', "/");
+                graph.edges.push(ConfigGraphEdge { from: slug_id.clone(), to: locale_id, label: Some(label) });
+            }
+        }
+        Ok(graph)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::fs;
+    use super::*;
+    use crate::transifex::yaml_file::tests::{create_test_project_dir, yaml_with_filters};
+
+    #[test]
+    fn tst_dependency_graph() {
+        let project_root = create_test_project_dir("tst_dependency_graph");
+        fs::create_dir_all(project_root.join("translations")).unwrap();
+        fs::write(project_root.join("translations/app_zh_CN.ts"), "<TS/>").unwrap();
+        fs::write(project_root.join("translations/app_ja.ts"), "<TS/>").unwrap();
+        let tx_yaml = TransifexYaml::from_str(&yaml_with_filters(&[("translations/app.ts", "translations/app_<lang>.ts")])).unwrap();
+        let lookup_table = vec![TxResourceLookupEntry {
+            repository: "linuxdeepin/app".to_string(),
+            branch: "master".to_string(),
+            resource: "translations/app.ts".to_string(),
+            transifex_resource_id: "o:linuxdeepin:p:app:r:app".to_string(),
+        }];
+        let graph = tx_yaml.dependency_graph("linuxdeepin/app", &lookup_table, &project_root).unwrap();
+        let node_ids: Vec<&str> = graph.nodes.iter().map(|node| node.id.as_str()).collect();
+        assert_eq!(node_ids, vec!["file:translations/app.ts", "slug:o:linuxdeepin:p:app:r:app", "locale:ja", "locale:zh_CN"]);
+        assert_eq!(graph.edges, vec![
+            ConfigGraphEdge { from: "file:translations/app.ts".to_string(), to: "slug:o:linuxdeepin:p:app:r:app".to_string(), label: None },
+            ConfigGraphEdge { from: "slug:o:linuxdeepin:p:app:r:app".to_string(), to: "locale:ja".to_string(), label: Some("translations/app_ja.ts".to_string()) },
+            ConfigGraphEdge { from: "slug:o:linuxdeepin:p:app:r:app".to_string(), to: "locale:zh_CN".to_string(), label: Some("translations/app_zh_CN.ts".to_string()) },
+        ]);
+        assert!(graph.to_dot().contains("  \"file:translations/app.ts\" -> \"slug:o:linuxdeepin:p:app:r:app\";\n"));
+    }
+}
@@ -0,0 +1,165 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+// Streaming of the filters of a transifex.yaml file, without loading all of them at once.
+
+use std::path::Path;
+use thiserror::Error as TeError;
+
+use crate::error::ResultExt;
+use super::yaml_file::{Filter, Settings, TxYamlLoadError, read_yaml_file, yaml_parse_error};
+
+/// Error of [`for_each_filter`].
+#[derive(TeError, Debug)]
+pub enum ForEachFilterError<E> {
+    /// The file can't be read or parsed.
+    #[error(transparent)]
+    Load(#[from] TxYamlLoadError),
+    /// The callback failed, no further filters were processed.
+    #[error("Fail to process filter because: {0}")]
+    Callback(#[source] E),
+}
+
+/// Pass the filters of the given `transifex.yaml` file to `f` one by one as they are parsed, without
+/// collecting them into a `Vec`, and return the settings.
+///
+/// The file is parsed in a single pass, so `settings` is only checked when it's reached: if it comes
+/// after `filters`, as in files written by this crate, `f` has already seen every filter when a broken
+/// or missing `settings` fails. Processing stops at the first error of `f`.
+pub fn for_each_filter<E, F: FnMut(Filter) -> Result<(), E>>(transifex_yaml_file: &Path, mut f: F) -> Result<Settings, ForEachFilterError<E>> {
+    let source_content = read_yaml_file(transifex_yaml_file)?;
+    let mut callback_error = None;
+    let result = serde::de::DeserializeSeed::deserialize(
+        FilterStream { f: &mut f, error: &mut callback_error },
+        serde_yml::Deserializer::from_str(&source_content),
+    );
+    if let Some(e) = callback_error {
+        return Err(ForEachFilterError::Callback(e));
+    }
+    Ok(result.map_err(yaml_parse_error).with_path(transifex_yaml_file)?)
+}
+
+/// Visits the top level mapping of `transifex.yaml`, passing each entry of `filters` to `f`.
+struct FilterStream<'a, F, E> {
+    f: &'a mut F,
+    /// Error of `f`, which stops deserialization.
+    error: &'a mut Option<E>,
+}
+
+impl<'de, F: FnMut(Filter) -> Result<(), E>, E> serde::de::DeserializeSeed<'de> for FilterStream<'_, F, E> {
+    type Value = Settings;
+
+    fn deserialize<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<Settings, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, F: FnMut(Filter) -> Result<(), E>, E> serde::de::Visitor<'de> for FilterStream<'_, F, E> {
+    type Value = Settings;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a transifex.yaml mapping")
+    }
+
+    fn visit_map<A: serde::de::MapAccess<'de>>(self, mut map: A) -> Result<Settings, A::Error> {
+        let mut settings = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "filters" => map.next_value_seed(FilterSeq { f: &mut *self.f, error: &mut *self.error })?,
+                "settings" => settings = Some(map.next_value::<Settings>()?),
+                _ => {
+                    map.next_value::<serde::de::IgnoredAny>()?;
+                },
+            }
+        }
+        settings.ok_or_else(|| serde::de::Error::missing_field("settings"))
+    }
+}
+
+/// Visits the `filters` sequence, see [`FilterStream`].
+struct FilterSeq<'a, F, E> {
+    f: &'a mut F,
+    error: &'a mut Option<E>,
+}
+
+impl<'de, F: FnMut(Filter) -> Result<(), E>, E> serde::de::DeserializeSeed<'de> for FilterSeq<'_, F, E> {
+    type Value = ();
+
+    fn deserialize<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, F: FnMut(Filter) -> Result<(), E>, E> serde::de::Visitor<'de> for FilterSeq<'_, F, E> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a sequence of filters")
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        while let Some(filter) = seq.next_element::<Filter>()? {
+            if let Err(e) = (self.f)(filter) {
+                *self.error = Some(e);
+                return Err(serde::de::Error::custom("filter callback failed"));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::fs;
+    use super::*;
+    use crate::transifex::yaml_file::DEFAULT_BRANCH_TEMPLATE;
+    use crate::transifex::yaml_file::tests::{TEST_SETTINGS, create_test_project_dir, yaml_with_filters};
+
+    #[test]
+    fn tst_for_each_filter() {
+        let project_root = create_test_project_dir("tst_for_each_filter");
+        let transifex_yaml_file = project_root.join("transifex.yaml");
+        let content = yaml_with_filters(&[
+            ("translations/app.ts", "translations/app_<lang>.ts"),
+            ("translations/desktop.ts", "translations/desktop_<lang>.ts"),
+            ("translations/dock.ts", "translations/dock_<lang>.ts"),
+            ("shell-launcher-applet/translations/org.deepin.ds.dock.launcherapplet.ts", "shell-launcher-applet/translations/org.deepin.ds.dock.launcherapplet_<lang>.ts"),
+        ]);
+        fs::write(&transifex_yaml_file, &content).unwrap();
+
+        let mut sources = Vec::<String>::new();
+        let settings = for_each_filter(&transifex_yaml_file, |filter| {
+            sources.push(filter.source);
+            Ok::<(), std::io::Error>(())
+        }).unwrap();
+        assert_eq!(settings.branch_template, DEFAULT_BRANCH_TEMPLATE);
+        assert_eq!(sources, vec![
+            "translations/app.ts",
+            "translations/desktop.ts",
+            "translations/dock.ts",
+            "shell-launcher-applet/translations/org.deepin.ds.dock.launcherapplet.ts",
+        ]);
+
+        // the first callback error stops processing
+        let mut calls = 0;
+        let err = for_each_filter(&transifex_yaml_file, |_| {
+            calls += 1;
+            if calls == 2 { Err(std::io::Error::other("stop")) } else { Ok(()) }
+        }).unwrap_err();
+        assert!(matches!(err, ForEachFilterError::Callback(e) if e.to_string() == "stop"));
+        assert_eq!(calls, 2);
+
+        // settings may come first, but must not be missing
+        fs::write(&transifex_yaml_file, format!("{TEST_SETTINGS}{}", content.replace(TEST_SETTINGS, ""))).unwrap();
+        let mut calls = 0;
+        assert!(for_each_filter(&transifex_yaml_file, |_| {
+            calls += 1;
+            Ok::<(), std::io::Error>(())
+        }).is_ok());
+        assert_eq!(calls, 4);
+        fs::write(&transifex_yaml_file, content.replace(TEST_SETTINGS, "")).unwrap();
+        let err = for_each_filter(&transifex_yaml_file, |_| Ok::<(), std::io::Error>(())).unwrap_err();
+        assert!(matches!(err, ForEachFilterError::Load(_)));
+    }
+}
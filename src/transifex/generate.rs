@@ -0,0 +1,257 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+// Generation of .tx/config files from the transifex.yaml of a project.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use thiserror::Error as TeError;
+
+use crate::crate_config::{CrateConfig, CrateConfigLoadError};
+use super::tx_config_file::{TxConfig, format_lang_map, normalize_host, parse_lang_map};
+use super::yaml_file::{FormatError, TransifexYaml, TxResourceLookupEntry, TxYamlLoadError, try_laod_transifex_yaml_file};
+
+/// Error of [`render_tx_config`] and [`generate_tx_config`].
+#[derive(TeError, Debug)]
+pub enum GenerateError {
+    /// The `transifex.yaml` of the project can't be loaded.
+    #[error("Fail to load transifex.yaml file because: {0}")]
+    Load(#[from] TxYamlLoadError),
+    /// The source file of a filter doesn't exist, and [`GenerateOptions::ensure_sources`] is not set.
+    #[error("Source file {0:?} not found")]
+    MissingSource(PathBuf),
+    /// The placeholder of a missing source file can't be created.
+    #[error("Fail to create source file {0:?} because: {1}")]
+    CreateSource(PathBuf, #[source] std::io::Error),
+    /// See [`FormatError`].
+    #[error(transparent)]
+    Format(#[from] FormatError),
+    /// The `.txconfig.toml` of the project exists but can't be loaded.
+    #[error("Fail to load tool config file because: {0}")]
+    CrateConfig(#[from] CrateConfigLoadError),
+}
+
+/// Options of [`generate_tx_config`].
+#[derive(Debug, Clone, Default)]
+pub struct GenerateOptions {
+    /// Content of placeholder source files, e.g. an empty TS file. If set, missing source files are
+    /// created from it instead of failing, e.g. for CI setting up a brand-new resource.
+    ///
+    /// **This writes to disk**, parent directories are created as well.
+    pub ensure_sources: Option<String>,
+    /// Set `replace_edited_strings = false` and `keep_translations = true` on every resource, so
+    /// pushing sources doesn't clobber reviewed translations.
+    pub preserve_edited_strings: bool,
+    /// `minimum_perc` of the `[main]` section, e.g. for the tx CLI `--use-git-timestamps` workflow.
+    pub minimum_perc: Option<i64>,
+    /// Boolean flags of the `[main]` section, see
+    /// [`TxConfigSectionMain::flags`](super::tx_config_file::TxConfigSectionMain::flags).
+    pub main_flags: BTreeMap<String, bool>,
+    /// Transifex host of the `[main]` section, defaults to `https://www.transifex.com`.
+    pub host: Option<String>,
+    /// Template of slugs of resources missing in the lookup table, see
+    /// [`TransifexYaml::to_tx_config_with_fallback_template`](super::yaml_file::TransifexYaml::to_tx_config_with_fallback_template).
+    pub fallback_template: Option<String>,
+    /// `lang_map` entries of the `[main]` section, for languages which the `language_mapping` of
+    /// `transifex.yaml` doesn't map.
+    pub lang_aliases: BTreeMap<String, String>,
+}
+
+impl GenerateOptions {
+    /// Fill options not set explicitly with the defaults of the given tool config, see
+    /// [`CrateConfig::host`], [`CrateConfig::fallback_template`] and [`CrateConfig::lang_aliases`].
+    pub fn with_defaults(mut self, crate_config: &CrateConfig) -> Self {
+        self.host = self.host.or_else(|| crate_config.host.clone());
+        self.fallback_template = self.fallback_template.or_else(|| crate_config.fallback_template.clone());
+        for (remote, local) in &crate_config.lang_aliases {
+            self.lang_aliases.entry(remote.clone()).or_insert_with(|| local.clone());
+        }
+        self
+    }
+}
+
+/// Load the `transifex.yaml` of the project and convert it to `.tx/config`, after checking that the
+/// source file of every filter exists, see [`GenerateOptions`].
+///
+/// Options not set explicitly default to the ones of the `.txconfig.toml` of the project, see
+/// [`GenerateOptions::with_defaults`].
+///
+/// Nothing is written unless [`GenerateOptions::ensure_sources`] is set and a source file is missing.
+/// Placeholder sources are only created once the tool config is loaded and the conversion succeeded.
+pub fn generate_tx_config(project_root: &PathBuf, github_repository: &str, lookup_table: &[TxResourceLookupEntry], opts: &GenerateOptions) -> Result<TxConfig, GenerateError> {
+    let (_, tx_yaml) = try_laod_transifex_yaml_file(project_root)?;
+    let tx_config = build_tx_config(&tx_yaml, project_root, github_repository, lookup_table, opts)?;
+    for filter in &tx_yaml.filters {
+        let source_file = project_root.join(&filter.source);
+        if source_file.exists() {
+            continue;
+        }
+        let Some(template) = &opts.ensure_sources else {
+            return Err(GenerateError::MissingSource(source_file));
+        };
+        if let Some(parent_dir) = source_file.parent() {
+            fs::create_dir_all(parent_dir).map_err(|e| GenerateError::CreateSource(source_file.clone(), e))?;
+        }
+        fs::write(&source_file, template).map_err(|e| GenerateError::CreateSource(source_file.clone(), e))?;
+    }
+    Ok(tx_config)
+}
+
+/// Load the `transifex.yaml` of the project and render the `.tx/config` it converts to, without
+/// touching disk, e.g. to print it for a dry run.
+///
+/// The returned text is the config [`generate_tx_config`] returns with default options, so the
+/// defaults of the `.txconfig.toml` of the project apply as well, but source files are neither
+/// checked nor created. It doesn't account for an existing `.tx/config` of the project.
+pub fn render_tx_config(project_root: &PathBuf, github_repository: &str, lookup_table: &[TxResourceLookupEntry]) -> Result<String, GenerateError> {
+    let (_, tx_yaml) = try_laod_transifex_yaml_file(project_root)?;
+    Ok(build_tx_config(&tx_yaml, project_root, github_repository, lookup_table, &GenerateOptions::default())?.to_str())
+}
+
+/// Convert to `.tx/config` with the given options, filled with the defaults of the `.txconfig.toml`
+/// of the project. Doesn't touch source files.
+fn build_tx_config(tx_yaml: &TransifexYaml, project_root: &PathBuf, github_repository: &str, lookup_table: &[TxResourceLookupEntry], opts: &GenerateOptions) -> Result<TxConfig, GenerateError> {
+    let opts = match CrateConfig::load(project_root)? {
+        Some(crate_config) => opts.clone().with_defaults(&crate_config),
+        None => opts.clone(),
+    };
+    let mut tx_config = tx_yaml.to_tx_config_impl(github_repository, lookup_table, opts.fallback_template.as_deref())?;
+    if let Some(host) = &opts.host {
+        tx_config.main_section.host = normalize_host(host);
+    }
+    if !opts.lang_aliases.is_empty() {
+        let mut lang_map = tx_config.main_section.lang_map.as_deref().map(parse_lang_map).unwrap_or_default();
+        for (remote, local) in &opts.lang_aliases {
+            lang_map.entry(remote.clone()).or_insert_with(|| local.clone());
+        }
+        tx_config.main_section.lang_map = Some(format_lang_map(&lang_map));
+    }
+    if opts.preserve_edited_strings {
+        tx_config.transform(|resource_section| {
+            resource_section.replace_edited_strings = Some(false);
+            resource_section.keep_translations = Some(true);
+        });
+    }
+    tx_config.transform_main(|main_section| {
+        main_section.minimum_prec = opts.minimum_perc.or(main_section.minimum_prec);
+        main_section.flags.extend(opts.main_flags.clone());
+    });
+    Ok(tx_config)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::crate_config::CRATE_CONFIG_FILE_NAME;
+    use crate::transifex::yaml_file::tests::{TEST_TX_YAML_CONTENT, create_test_project_dir};
+
+    #[test]
+    fn tst_render_tx_config() {
+        let project_root = create_test_project_dir("tst_render_tx_config");
+        fs::write(project_root.join("transifex.yaml"), TEST_TX_YAML_CONTENT).unwrap();
+        let lookup_table = vec![TxResourceLookupEntry {
+            repository: "linuxdeepin/dde-shell".to_string(),
+            branch: "master".to_string(),
+            resource: "org.deepin.ds.dock.launcherapplet.ts".to_string(),
+            transifex_resource_id: "o:linuxdeepin:p:deepin-desktop-environment:r:dde-shell-launcherapplet".to_string(),
+        }];
+        let rendered = render_tx_config(&project_root, "linuxdeepin/dde-shell", &lookup_table).unwrap();
+        assert!(rendered.contains("[o:linuxdeepin:p:deepin-desktop-environment:r:dde-shell-launcherapplet]"));
+        assert!(rendered.contains("source_file = shell-launcher-applet/translations/org.deepin.ds.dock.launcherapplet.ts"));
+        assert!(rendered.contains("file_filter = shell-launcher-applet/translations/org.deepin.ds.dock.launcherapplet_<lang>.ts"));
+        assert!(rendered.contains("type = QT"));
+        assert!(!project_root.join(".tx/config").exists());
+
+        // the tool config applies to the preview as well
+        fs::write(project_root.join(CRATE_CONFIG_FILE_NAME), "host = \"https://app.transifex.com/\"\n").unwrap();
+        let rendered = render_tx_config(&project_root, "linuxdeepin/dde-shell", &lookup_table).unwrap();
+        assert_eq!(TxConfig::from_str(&rendered).unwrap().main_section.host, "https://app.transifex.com");
+        let tx_config = generate_tx_config(&project_root, "linuxdeepin/dde-shell", &lookup_table, &GenerateOptions {
+            ensure_sources: Some("<TS/>".to_string()),
+            ..GenerateOptions::default()
+        }).unwrap();
+        assert_eq!(rendered, tx_config.to_str());
+
+        assert!(matches!(render_tx_config(&project_root.join("missing"), "linuxdeepin/dde-shell", &lookup_table), Err(GenerateError::Load(_))));
+    }
+
+    #[test]
+    fn tst_generate_tx_config_crate_config() {
+        let project_root = create_test_project_dir("tst_generate_tx_config_crate_config");
+        fs::write(project_root.join("transifex.yaml"), TEST_TX_YAML_CONTENT).unwrap();
+        fs::create_dir_all(project_root.join("shell-launcher-applet/translations")).unwrap();
+        fs::write(project_root.join("shell-launcher-applet/translations/org.deepin.ds.dock.launcherapplet.ts"), "<TS/>").unwrap();
+        fs::write(project_root.join(CRATE_CONFIG_FILE_NAME), concat!(
+            "host = \"https://app.transifex.com/\"\n",
+            "fallback_template = \"o:linuxdeepin:p:deepin-desktop-environment:r:<resource>\"\n",
+            "[lang_aliases]\n",
+            "zh_Hans = \"zh_CN\"\n",
+        )).unwrap();
+
+        let tx_config = generate_tx_config(&project_root, "linuxdeepin/dde-shell", &[], &GenerateOptions::default()).unwrap();
+        assert_eq!(tx_config.main_section.host, "https://app.transifex.com");
+        assert_eq!(tx_config.main_section.lang_map.as_deref(), Some("zh_Hans: zh_CN"));
+        assert_eq!(tx_config.resource_sections[0].resource_full_slug,
+            "o:linuxdeepin:p:deepin-desktop-environment:r:shell-launcher-applet_translations_org_deepin_ds_dock_launcherapplet");
+
+        // explicit options win
+        let opts = GenerateOptions { host: Some("https://www.transifex.com".to_string()), ..GenerateOptions::default() };
+        let tx_config = generate_tx_config(&project_root, "linuxdeepin/dde-shell", &[], &opts).unwrap();
+        assert_eq!(tx_config.main_section.host, "https://www.transifex.com");
+
+        // a malformed tool config is an error instead of being ignored
+        fs::write(project_root.join(CRATE_CONFIG_FILE_NAME), "host = [").unwrap();
+        let err = generate_tx_config(&project_root, "linuxdeepin/dde-shell", &[], &GenerateOptions::default()).unwrap_err();
+        assert!(matches!(err, GenerateError::CrateConfig(_)));
+        // and nothing is written before it is loaded
+        let source_file = project_root.join("shell-launcher-applet/translations/org.deepin.ds.dock.launcherapplet.ts");
+        fs::remove_file(&source_file).unwrap();
+        let opts = GenerateOptions { ensure_sources: Some("<TS/>".to_string()), ..GenerateOptions::default() };
+        let err = generate_tx_config(&project_root, "linuxdeepin/dde-shell", &[], &opts).unwrap_err();
+        assert!(matches!(err, GenerateError::CrateConfig(_)));
+        assert!(!source_file.exists());
+    }
+
+    #[test]
+    fn tst_generate_tx_config() {
+        let project_root = create_test_project_dir("tst_generate_tx_config");
+        fs::write(project_root.join("transifex.yaml"), TEST_TX_YAML_CONTENT).unwrap();
+        let source_file = project_root.join("shell-launcher-applet/translations/org.deepin.ds.dock.launcherapplet.ts");
+        let err = generate_tx_config(&project_root, "linuxdeepin/dde-shell", &[], &GenerateOptions::default()).unwrap_err();
+        assert!(matches!(err, GenerateError::MissingSource(path) if path == source_file));
+        assert!(!source_file.exists());
+
+        let opts = GenerateOptions { ensure_sources: Some("<TS/>".to_string()), ..GenerateOptions::default() };
+        let tx_config = generate_tx_config(&project_root, "linuxdeepin/dde-shell", &[], &opts).unwrap();
+        assert_eq!(fs::read_to_string(&source_file).unwrap(), "<TS/>");
+        assert_eq!(tx_config.resource_sections.len(), 1);
+        assert_eq!(tx_config.resource_sections[0].source_file, "shell-launcher-applet/translations/org.deepin.ds.dock.launcherapplet.ts");
+        // existing sources are kept
+        fs::write(&source_file, "<TS version=\"2.1\"/>").unwrap();
+        generate_tx_config(&project_root, "linuxdeepin/dde-shell", &[], &opts).unwrap();
+        assert_eq!(fs::read_to_string(&source_file).unwrap(), "<TS version=\"2.1\"/>");
+        assert!(!tx_config.to_str().contains("replace_edited_strings"));
+
+        let opts = GenerateOptions { preserve_edited_strings: true, ..GenerateOptions::default() };
+        let content = generate_tx_config(&project_root, "linuxdeepin/dde-shell", &[], &opts).unwrap().to_str();
+        assert!(content.contains("keep_translations = true"));
+        assert!(content.contains("replace_edited_strings = false"));
+        let reparsed = TxConfig::from_str(&content).unwrap();
+        assert_eq!(reparsed.resource_sections[0].replace_edited_strings, Some(false));
+        assert_eq!(reparsed.resource_sections[0].keep_translations, Some(true));
+
+        let opts = GenerateOptions {
+            minimum_perc: Some(80),
+            main_flags: BTreeMap::from([("use_git_timestamps".to_string(), true)]),
+            ..GenerateOptions::default()
+        };
+        let content = generate_tx_config(&project_root, "linuxdeepin/dde-shell", &[], &opts).unwrap().to_str().replace("\r\n", "\n");
+        let (main_section, _) = content.split_once("\n\n").unwrap();
+        assert_eq!(main_section, "[main]\nhost = https://www.transifex.com\nminimum_perc = 80\nuse_git_timestamps = true");
+        let reparsed = TxConfig::from_str(&content).unwrap();
+        assert_eq!(reparsed.main_section.minimum_prec, Some(80));
+        assert_eq!(reparsed.main_section.flags, opts.main_flags);
+    }
+}
@@ -0,0 +1,194 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+// English names of the language subtags of the supported locales, for human friendly sorting.
+
+use std::cmp::Ordering;
+
+/// English names of language subtags, sorted bytewise by subtag.
+pub static LANGUAGE_NAMES: &[(&str, &str)] = &[
+    ("af", "Afrikaans"),
+    ("ak", "Akan"),
+    ("am", "Amharic"),
+    ("an", "Aragonese"),
+    ("ar", "Arabic"),
+    ("as", "Assamese"),
+    ("ast", "Asturian"),
+    ("az", "Azerbaijani"),
+    ("ba", "Bashkir"),
+    ("be", "Belarusian"),
+    ("bg", "Bulgarian"),
+    ("bn", "Bengali"),
+    ("bo", "Tibetan"),
+    ("br", "Breton"),
+    ("bs", "Bosnian"),
+    ("ca", "Catalan"),
+    ("ckb", "Central Kurdish"),
+    ("co", "Corsican"),
+    ("cs", "Czech"),
+    ("cv", "Chuvash"),
+    ("cy", "Welsh"),
+    ("da", "Danish"),
+    ("de", "German"),
+    ("dsb", "Lower Sorbian"),
+    ("dv", "Divehi"),
+    ("dz", "Dzongkha"),
+    ("el", "Greek"),
+    ("en", "English"),
+    ("eo", "Esperanto"),
+    ("es", "Spanish"),
+    ("et", "Estonian"),
+    ("eu", "Basque"),
+    ("fa", "Persian"),
+    ("ff", "Fulah"),
+    ("fi", "Finnish"),
+    ("fil", "Filipino"),
+    ("fo", "Faroese"),
+    ("fr", "French"),
+    ("fur", "Friulian"),
+    ("fy", "Western Frisian"),
+    ("ga", "Irish"),
+    ("gd", "Scottish Gaelic"),
+    ("gl", "Galician"),
+    ("gn", "Guarani"),
+    ("gu", "Gujarati"),
+    ("gv", "Manx"),
+    ("ha", "Hausa"),
+    ("haw", "Hawaiian"),
+    ("he", "Hebrew"),
+    ("hi", "Hindi"),
+    ("hr", "Croatian"),
+    ("hsb", "Upper Sorbian"),
+    ("ht", "Haitian Creole"),
+    ("hu", "Hungarian"),
+    ("hy", "Armenian"),
+    ("ia", "Interlingua"),
+    ("id", "Indonesian"),
+    ("ig", "Igbo"),
+    ("is", "Icelandic"),
+    ("it", "Italian"),
+    ("iu", "Inuktitut"),
+    ("ja", "Japanese"),
+    ("jv", "Javanese"),
+    ("ka", "Georgian"),
+    ("kab", "Kabyle"),
+    ("kk", "Kazakh"),
+    ("km", "Khmer"),
+    ("kn", "Kannada"),
+    ("ko", "Korean"),
+    ("ku", "Kurdish"),
+    ("ky", "Kyrgyz"),
+    ("la", "Latin"),
+    ("lb", "Luxembourgish"),
+    ("lg", "Ganda"),
+    ("li", "Limburgish"),
+    ("ln", "Lingala"),
+    ("lo", "Lao"),
+    ("lt", "Lithuanian"),
+    ("lv", "Latvian"),
+    ("mai", "Maithili"),
+    ("mg", "Malagasy"),
+    ("mi", "Maori"),
+    ("mk", "Macedonian"),
+    ("ml", "Malayalam"),
+    ("mn", "Mongolian"),
+    ("mr", "Marathi"),
+    ("ms", "Malay"),
+    ("mt", "Maltese"),
+    ("my", "Burmese"),
+    ("nb", "Norwegian Bokmål"),
+    ("nds", "Low German"),
+    ("ne", "Nepali"),
+    ("nl", "Dutch"),
+    ("nn", "Norwegian Nynorsk"),
+    ("no", "Norwegian"),
+    ("oc", "Occitan"),
+    ("om", "Oromo"),
+    ("or", "Odia"),
+    ("pa", "Punjabi"),
+    ("pl", "Polish"),
+    ("ps", "Pashto"),
+    ("pt", "Portuguese"),
+    ("qu", "Quechua"),
+    ("rm", "Romansh"),
+    ("ro", "Romanian"),
+    ("ru", "Russian"),
+    ("rw", "Kinyarwanda"),
+    ("sa", "Sanskrit"),
+    ("sc", "Sardinian"),
+    ("sd", "Sindhi"),
+    ("si", "Sinhala"),
+    ("sk", "Slovak"),
+    ("sl", "Slovenian"),
+    ("so", "Somali"),
+    ("sq", "Albanian"),
+    ("sr", "Serbian"),
+    ("sv", "Swedish"),
+    ("sw", "Swahili"),
+    ("szl", "Silesian"),
+    ("ta", "Tamil"),
+    ("te", "Telugu"),
+    ("tg", "Tajik"),
+    ("th", "Thai"),
+    ("ti", "Tigrinya"),
+    ("tk", "Turkmen"),
+    ("tl", "Tagalog"),
+    ("tn", "Tswana"),
+    ("tr", "Turkish"),
+    ("tt", "Tatar"),
+    ("ug", "Uyghur"),
+    ("uk", "Ukrainian"),
+    ("ur", "Urdu"),
+    ("uz", "Uzbek"),
+    ("vi", "Vietnamese"),
+    ("wa", "Walloon"),
+    ("wo", "Wolof"),
+    ("xh", "Xhosa"),
+    ("yi", "Yiddish"),
+    ("yo", "Yoruba"),
+    ("zh", "Chinese"),
+    ("zu", "Zulu"),
+];
+
+/// English name of the language subtag of the given code, e.g. `Chinese` for `zh_CN`.
+pub fn language_name(code: &str) -> Option<&'static str> {
+    let language = code.split(['_', '-', '@']).next()?;
+    LANGUAGE_NAMES.binary_search_by(|(subtag, _)| subtag.cmp(&language)).ok().map(|idx| LANGUAGE_NAMES[idx].1)
+}
+
+/// Compare language codes by language name, then by the rest of the code, e.g. region and script.
+///
+/// Languages without a known name sort by their code, compared case-insensitively with the names.
+pub fn cmp_locales_by_name(a: &str, b: &str) -> Ordering {
+    let key = |code: &str| (language_name(code).unwrap_or(code).to_lowercase(), code.to_string());
+    key(a).cmp(&key(b))
+}
+
+/// Sort language codes in a human friendly order, see [`cmp_locales_by_name`].
+pub fn sort_locales_by_name<S: AsRef<str>>(codes: &mut [S]) {
+    codes.sort_by(|a, b| cmp_locales_by_name(a.as_ref(), b.as_ref()));
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn tst_language_names_sorted() {
+        assert!(LANGUAGE_NAMES.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn tst_sort_locales_by_name() {
+        let mut ascii = vec!["zh_TW", "fr", "de", "zh_CN", "ja", "en"];
+        ascii.sort();
+        assert_eq!(ascii, vec!["de", "en", "fr", "ja", "zh_CN", "zh_TW"]);
+        let mut by_name = ascii.clone();
+        sort_locales_by_name(&mut by_name);
+        // Chinese, English, French, German, Japanese
+        assert_eq!(by_name, vec!["zh_CN", "zh_TW", "en", "fr", "de", "ja"]);
+        assert_eq!(language_name("pt-BR"), Some("Portuguese"));
+        assert_eq!(language_name("zz_ZZ"), None);
+    }
+}
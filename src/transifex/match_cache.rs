@@ -0,0 +1,167 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+// On-disk cache of matched translation files, so unchanged target directories are not scanned again between runs.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use serde::{Deserialize, Serialize};
+use thiserror::Error as TeError;
+
+use super::yaml_file::{MatchError, TransifexYaml};
+
+/// Bump this whenever the cache format or the matching rules change, so old caches are discarded.
+pub const MATCH_CACHE_VERSION: u32 = 1;
+
+/// Modification time of a target directory, which changes whenever files are added, removed or renamed in it.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DirModified {
+    /// Seconds since the Unix epoch.
+    pub secs: u64,
+    /// Sub-second part, in nanoseconds.
+    pub nanos: u32,
+}
+
+impl DirModified {
+    /// Modification time of the given directory.
+    pub fn of(dir: &Path) -> std::io::Result<Self> {
+        let modified = fs::metadata(dir)?.modified()?.duration_since(UNIX_EPOCH).unwrap_or_default();
+        Ok(Self { secs: modified.as_secs(), nanos: modified.subsec_nanos() })
+    }
+}
+
+/// Cached files matched by a filter.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchCacheEntry {
+    /// Modification time of the target directory when it was scanned.
+    pub modified: DirModified,
+    /// Matched `(lang_code, path)` pairs, see [`super::yaml_file::Filter::match_target_files`].
+    pub matches: Vec<(String, PathBuf)>,
+}
+
+/// Content of a match cache file.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MatchCache {
+    /// Cache format version, see [`MATCH_CACHE_VERSION`].
+    pub version: u32,
+    /// Keyed by target pattern joined to the project root.
+    pub entries: BTreeMap<PathBuf, MatchCacheEntry>,
+}
+
+impl Default for MatchCache {
+    fn default() -> Self {
+        Self {
+            version: MATCH_CACHE_VERSION,
+            entries: BTreeMap::new(),
+        }
+    }
+}
+
+impl MatchCache {
+    /// Load the cache from the given file.
+    ///
+    /// Missing, corrupted or outdated cache files are silently discarded, and an empty cache is returned instead.
+    pub fn load(cache_file: &Path) -> Self {
+        fs::read(cache_file).ok()
+            .and_then(|content| serde_json::from_slice::<MatchCache>(&content).ok())
+            .filter(|cache| cache.version == MATCH_CACHE_VERSION)
+            .unwrap_or_default()
+    }
+
+    /// Write the cache to the given file, creating its parent directory if needed.
+    pub fn save(&self, cache_file: &Path) -> std::io::Result<()> {
+        if let Some(parent_dir) = cache_file.parent() {
+            fs::create_dir_all(parent_dir)?;
+        }
+        fs::write(cache_file, serde_json::to_vec(self)?)
+    }
+}
+
+/// Files matched by every filter, see [`TransifexYaml::match_all_cached`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CachedMatches {
+    /// Matched `(lang_code, path)` pairs of every filter, in filter order.
+    pub filters: Vec<Vec<(String, PathBuf)>>,
+    /// How many filters were served from the cache instead of scanning their target directory.
+    pub hits: usize,
+}
+
+/// Error of [`TransifexYaml::match_all_cached`].
+#[derive(TeError, Debug)]
+pub enum MatchCacheError {
+    /// Matching translation files failed.
+    #[error(transparent)]
+    Match(#[from] MatchError),
+    /// The updated cache can't be written.
+    #[error("Fail to write match cache file {0:?} because: {1}")]
+    SaveCache(PathBuf, #[source] std::io::Error),
+}
+
+impl TransifexYaml {
+    /// Same as matching the target files of every filter, but reuse the matches stored in `cache_file`
+    /// for filters whose target directory wasn't modified since it was last scanned.
+    ///
+    /// Filters missing from the cache or with a stale directory modification time are scanned again,
+    /// and the cache file is only rewritten if anything was scanned. Modifying files in place doesn't
+    /// change what is matched, so it doesn't invalidate the cache.
+    pub fn match_all_cached(&self, project_root: &PathBuf, cache_file: &Path) -> Result<CachedMatches, MatchCacheError> {
+        let mut cache = MatchCache::load(cache_file);
+        let mut result = CachedMatches::default();
+        let mut updated = false;
+        for filter in &self.filters {
+            let target_pattern_path = project_root.join(&filter.target_pattern);
+            let modified = target_pattern_path.parent().and_then(|target_dir| DirModified::of(target_dir).ok());
+            let cached = cache.entries.get(&target_pattern_path)
+                .filter(|entry| modified.is_some_and(|modified| entry.modified == modified));
+            if let Some(entry) = cached {
+                result.filters.push(entry.matches.clone());
+                result.hits += 1;
+                continue;
+            }
+            let matches = filter.match_target_files(project_root)
+                .map_err(|e| MatchError::Filter(filter.source.clone(), e))?;
+            // target directories whose modification time is unknown are never cached
+            if let Some(modified) = modified {
+                cache.entries.insert(target_pattern_path, MatchCacheEntry { modified, matches: matches.clone() });
+                updated = true;
+            }
+            result.filters.push(matches);
+        }
+        if updated {
+            cache.save(cache_file).map_err(|e| MatchCacheError::SaveCache(cache_file.to_path_buf(), e))?;
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::transifex::yaml_file::tests::{TEST_TX_YAML_CONTENT, create_test_project_dir};
+
+    #[test]
+    fn tst_match_all_cached() {
+        let project_root = create_test_project_dir("tst_match_all_cached");
+        let translations_dir = project_root.join("shell-launcher-applet/translations");
+        fs::create_dir_all(&translations_dir).unwrap();
+        fs::write(translations_dir.join("org.deepin.ds.dock.launcherapplet_zh_CN.ts"), "<TS/>").unwrap();
+        let cache_file = project_root.join("cache").join("matches.json");
+        let tx_yaml = TransifexYaml::from_str(TEST_TX_YAML_CONTENT).unwrap();
+
+        let first = tx_yaml.match_all_cached(&project_root, &cache_file).unwrap();
+        assert_eq!(first.hits, 0);
+        assert_eq!(first.filters[0].len(), 1);
+        assert!(cache_file.exists());
+        // unchanged directory hits the cache
+        let second = tx_yaml.match_all_cached(&project_root, &cache_file).unwrap();
+        assert_eq!(second, CachedMatches { filters: first.filters.clone(), hits: 1 });
+
+        // corrupted cache file is discarded
+        fs::write(&cache_file, "{ not json").unwrap();
+        let third = tx_yaml.match_all_cached(&project_root, &cache_file).unwrap();
+        assert_eq!(third, CachedMatches { filters: first.filters, hits: 0 });
+    }
+}
@@ -0,0 +1,409 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+// Project-wide reports over the translation files matched by all filters of a transifex.yaml file.
+
+use std::{collections::{BTreeMap, BTreeSet}, fs, path::{Path, PathBuf}};
+
+use thiserror::Error as TeError;
+
+use super::supported_locales::is_supported_locale;
+use super::yaml_file::{FileFormat, Filter, MatchError, TransifexYaml};
+use crate::i18n_file::linguist::{Ts, TsLoadError};
+use crate::walk::{WalkOptions, walk};
+
+impl TransifexYaml {
+    /// Compute the completion percentage of every matched translation file of QT filters.
+    ///
+    /// Returns `(source, [(lang_code, percent)])` for every QT filter, in filter order. Other filters are skipped.
+    pub fn completion_report(&self, project_root: &PathBuf) -> Result<Vec<(String, Vec<(String, f32)>)>, CompletionReportError> {
+        let mut report = Vec::new();
+        for filter in self.filters.iter().filter(|filter| filter.file_format() == FileFormat::Qt) {
+            let matched = filter.match_target_files(project_root)
+                .map_err(|e| MatchError::Filter(filter.source.clone(), e))?;
+            let mut locales = Vec::with_capacity(matched.len());
+            for (lang_code, path) in matched {
+                let ts = Ts::load_from_file(&path).map_err(|e| CompletionReportError::LoadTs(path.clone(), e))?;
+                locales.push((lang_code, ts.get_message_stats().completeness_percentage() as f32));
+            }
+            report.push((filter.source.clone(), locales));
+        }
+        Ok(report)
+    }
+
+    /// Find translation files which no filter's target pattern would capture, e.g. leftovers of removed filters.
+    ///
+    /// Every file under `project_root` with one of the given `extensions` (without leading dot) and a supported
+    /// locale suffix in its file name, like `app_zh_CN.ts`, is a candidate. Hidden directories and
+    /// [`crate::walk::DEFAULT_PRUNED_DIRS`] are not scanned.
+    /// Returned paths are sorted.
+    pub fn orphaned_translations(&self, project_root: &PathBuf, extensions: &[&str]) -> Result<Vec<PathBuf>, MatchError> {
+        let mut covered = BTreeSet::<PathBuf>::new();
+        for filter in &self.filters {
+            covered.insert(project_root.join(&filter.source));
+            covered.extend(filter.matched_files(project_root)?.into_iter().map(|(_, path)| path));
+        }
+
+        let mut orphans = Vec::<PathBuf>::new();
+        let walk_opts = WalkOptions { prune_hidden: true, ..WalkOptions::default() };
+        for entry in walk(project_root, &walk_opts) {
+            let entry = entry.map_err(|e| {
+                let dir = e.path().map(Path::to_path_buf).unwrap_or(project_root.clone());
+                MatchError::ReadDir(dir, e.into())
+            })?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let Some(file_name) = entry.file_name().to_str() else {
+                continue;
+            };
+            let Some((stem, ext)) = file_name.rsplit_once('.') else {
+                continue;
+            };
+            if !extensions.iter().any(|wanted| wanted.trim_start_matches('.') == ext) {
+                continue;
+            }
+            if has_locale_suffix(stem) && !covered.contains(entry.path()) {
+                orphans.push(entry.path().to_path_buf());
+            }
+        }
+        orphans.sort();
+        Ok(orphans)
+    }
+
+    /// Create the translation file of a new locale under every filter at once, with the content
+    /// returned by `template_for` for the filter. Existing files are left untouched.
+    ///
+    /// Parent directories are created as needed. Returns the created paths, in filter order.
+    pub fn add_locale(&self, project_root: &PathBuf, locale: &str, template_for: impl Fn(&Filter) -> String) -> Result<Vec<PathBuf>, std::io::Error> {
+        if locale.is_empty() || locale.contains(['/', '\\']) || locale == "." || locale == ".." {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("Invalid locale {locale:?}")));
+        }
+        let mut created = Vec::<PathBuf>::new();
+        for filter in &self.filters {
+            let target_file = project_root.join(filter.target_pattern.replace("<lang>", locale));
+            if target_file.exists() {
+                continue;
+            }
+            if let Some(parent_dir) = target_file.parent() {
+                fs::create_dir_all(parent_dir)?;
+            }
+            fs::write(&target_file, template_for(filter))?;
+            created.push(target_file);
+        }
+        Ok(created)
+    }
+
+    /// Find translation files captured by the target patterns of more than one filter, which would
+    /// be uploaded to every one of their resources.
+    ///
+    /// Filters whose target directory doesn't exist match nothing. Returned paths are sorted, and
+    /// the filters of each path are in filter order.
+    pub fn files_matched_by_multiple_filters(&self, project_root: &PathBuf) -> Result<Vec<(PathBuf, Vec<&Filter>)>, MatchError> {
+        let mut filters_by_file = BTreeMap::<PathBuf, Vec<&Filter>>::new();
+        for filter in &self.filters {
+            for (_, path) in filter.matched_files(project_root)? {
+                filters_by_file.entry(path).or_default().push(filter);
+            }
+        }
+        Ok(filters_by_file.into_iter().filter(|(_, filters)| filters.len() > 1).collect())
+    }
+
+    /// Find matched translation files of zero length, which are usually left behind by a broken sync.
+    ///
+    /// Filters whose target directory doesn't exist match nothing. Returned paths are in filter order.
+    pub fn find_empty_translations(&self, project_root: &PathBuf) -> Result<Vec<PathBuf>, MatchError> {
+        let mut empty_files = Vec::<PathBuf>::new();
+        for filter in &self.filters {
+            for (_, path) in filter.matched_files(project_root)? {
+                let metadata = fs::metadata(&path).map_err(|e| MatchError::Filter(filter.source.clone(), e))?;
+                if metadata.len() == 0 {
+                    empty_files.push(path);
+                }
+            }
+        }
+        Ok(empty_files)
+    }
+
+    /// Filters which match no translation file at all, usually newly added resources which were
+    /// never translated. A missing target directory matches nothing.
+    pub fn resources_without_translations(&self, project_root: &PathBuf) -> Result<Vec<&Filter>, MatchError> {
+        let mut filters = Vec::<&Filter>::new();
+        for filter in &self.filters {
+            match filter.iter_target_files(project_root).next() {
+                None => filters.push(filter),
+                Some(Err(e)) if e.kind() == std::io::ErrorKind::NotFound => filters.push(filter),
+                Some(Err(e)) => return Err(MatchError::Filter(filter.source.clone(), e)),
+                Some(Ok(_)) => {},
+            }
+        }
+        Ok(filters)
+    }
+
+    /// Locales matched by every filter, e.g. to only ship locales translated in every resource.
+    ///
+    /// Filters whose target directory doesn't exist match no locale, so the result is empty then,
+    /// and also if there are no filters.
+    pub fn locales_in_all_filters(&self, project_root: &PathBuf) -> Result<BTreeSet<String>, MatchError> {
+        let mut common_locales: Option<BTreeSet<String>> = None;
+        for filter in &self.filters {
+            let locales = filter.matched_locales(project_root)?;
+            common_locales = Some(match common_locales {
+                Some(common_locales) => common_locales.intersection(&locales).cloned().collect(),
+                None => locales,
+            });
+        }
+        Ok(common_locales.unwrap_or_default())
+    }
+
+    /// For every locale matched by some filters but not all, the filters lacking it, in filter order,
+    /// so translators know which resources still need that language.
+    ///
+    /// Filters whose target directory doesn't exist match no locale. Locales matched by every filter
+    /// are omitted, see [`Self::locales_in_all_filters`].
+    pub fn locale_coverage_gaps(&self, project_root: &PathBuf) -> Result<BTreeMap<String, Vec<&Filter>>, MatchError> {
+        let mut locales_by_filter = Vec::<BTreeSet<String>>::with_capacity(self.filters.len());
+        for filter in &self.filters {
+            locales_by_filter.push(filter.matched_locales(project_root)?);
+        }
+        let all_locales = locales_by_filter.iter().flatten().cloned().collect::<BTreeSet<String>>();
+        let mut gaps = BTreeMap::<String, Vec<&Filter>>::new();
+        for locale in all_locales {
+            let lacking = self.filters.iter().zip(&locales_by_filter)
+                .filter(|(_, locales)| !locales.contains(&locale))
+                .map(|(filter, _)| filter)
+                .collect::<Vec<&Filter>>();
+            if !lacking.is_empty() {
+                gaps.insert(locale, lacking);
+            }
+        }
+        Ok(gaps)
+    }
+
+    /// Compare the locales matched by every filter to the desired ones, e.g. for release gating.
+    ///
+    /// Filters whose target directory doesn't exist match no locale. Returns one status per filter,
+    /// in filter order, with sorted locales so reports diff nicely.
+    pub fn locale_status(&self, project_root: &PathBuf, desired: &[&str]) -> Result<Vec<LocaleStatus>, MatchError> {
+        let desired = desired.iter().map(|locale| locale.to_string()).collect::<BTreeSet<String>>();
+        let mut statuses = Vec::<LocaleStatus>::with_capacity(self.filters.len());
+        for filter in &self.filters {
+            let on_disk = filter.matched_locales(project_root)?;
+            statuses.push(LocaleStatus {
+                source: filter.source.clone(),
+                present: desired.intersection(&on_disk).cloned().collect(),
+                missing: desired.difference(&on_disk).cloned().collect(),
+                extra: on_disk.difference(&desired).cloned().collect(),
+            });
+        }
+        Ok(statuses)
+    }
+}
+
+/// Locales of a filter compared to the desired ones, see [`TransifexYaml::locale_status`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LocaleStatus {
+    /// Source file of the filter.
+    pub source: String,
+    /// Desired locales with a translation file.
+    pub present: Vec<String>,
+    /// Desired locales without a translation file.
+    pub missing: Vec<String>,
+    /// Locales with a translation file which are not desired.
+    pub extra: Vec<String>,
+}
+
+/// Error of [`TransifexYaml::completion_report`].
+#[derive(TeError, Debug)]
+pub enum CompletionReportError {
+    /// Matching translation files failed.
+    #[error("{0}")]
+    Match(#[from] MatchError),
+    /// A matched TS file can't be loaded.
+    #[error("Fail to load TS file {0:?} because: {1}")]
+    LoadTs(PathBuf, #[source] TsLoadError),
+}
+
+/// Whether the file stem ends with `_<locale>`, where the locale is supported by Transifex, e.g. `app_zh_CN`.
+fn has_locale_suffix(stem: &str) -> bool {
+    let parts: Vec<&str> = stem.split('_').collect();
+    // the part before the locale must not be empty
+    (1..=2).filter(|len| parts.len() > *len)
+        .any(|len| is_supported_locale(&parts[parts.len() - len..].join("_")))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::transifex::yaml_file::tests::{TEST_TX_YAML_CONTENT, create_test_project_dir, yaml_with_filters};
+
+    #[test]
+    fn tst_orphaned_translations() {
+        let project_root = create_test_project_dir("tst_orphaned_translations");
+        fs::create_dir_all(project_root.join("translations")).unwrap();
+        fs::create_dir_all(project_root.join("removed")).unwrap();
+        fs::write(project_root.join("translations/app.ts"), "<TS/>").unwrap();
+        fs::write(project_root.join("translations/app_zh_CN.ts"), "<TS/>").unwrap();
+        fs::write(project_root.join("removed/old_zh_CN.ts"), "<TS/>").unwrap();
+        fs::write(project_root.join("removed/old_zh_CN.qm"), "").unwrap();
+        let tx_yaml = TransifexYaml::from_str(&yaml_with_filters(&[("translations/app.ts", "translations/app_<lang>.ts")])).unwrap();
+        assert_eq!(tx_yaml.orphaned_translations(&project_root, &["ts"]).unwrap(), vec![project_root.join("removed/old_zh_CN.ts")]);
+    }
+
+    #[test]
+    fn tst_find_empty_translations() {
+        let project_root = create_test_project_dir("tst_find_empty_translations");
+        fs::create_dir_all(project_root.join("translations")).unwrap();
+        fs::write(project_root.join("translations/app.ts"), "<TS/>").unwrap();
+        fs::write(project_root.join("translations/app_zh_CN.ts"), "<TS/>").unwrap();
+        fs::write(project_root.join("translations/app_zh_TW.ts"), "").unwrap();
+        let tx_yaml = TransifexYaml::from_str(&yaml_with_filters(&[("translations/app.ts", "translations/app_<lang>.ts")])).unwrap();
+        assert_eq!(tx_yaml.find_empty_translations(&project_root).unwrap(), vec![project_root.join("translations/app_zh_TW.ts")]);
+
+        let tx_yaml = TransifexYaml::from_str(TEST_TX_YAML_CONTENT).unwrap();
+        assert!(tx_yaml.find_empty_translations(&project_root).unwrap().is_empty());
+    }
+
+    #[test]
+    fn tst_locales_in_all_filters() {
+        let project_root = create_test_project_dir("tst_locales_in_all_filters");
+        fs::create_dir_all(project_root.join("translations")).unwrap();
+        for file_name in ["app_zh_CN.ts", "app_de.ts", "desktop_zh_CN.ts"] {
+            fs::write(project_root.join("translations").join(file_name), "<TS/>").unwrap();
+        }
+        let tx_yaml = TransifexYaml::from_str(r#"filters:
+  - filter_type: file
+    source_file: translations/app.ts
+    file_format: QT
+    source_language: en_US
+    translation_files_expression: translations/app_<lang>.ts
+  - filter_type: file
+    source_file: translations/desktop.ts
+    file_format: QT
+    source_language: en_US
+    translation_files_expression: translations/desktop_<lang>.ts
+settings:
+  pr_branch_name: transifex_update_<br_unique_id>
+"#).unwrap();
+        assert_eq!(tx_yaml.locales_in_all_filters(&project_root).unwrap(), BTreeSet::from(["zh_CN".to_string()]));
+    }
+
+    #[test]
+    fn tst_resources_without_translations() {
+        let project_root = create_test_project_dir("tst_resources_without_translations");
+        fs::create_dir_all(project_root.join("translations")).unwrap();
+        fs::write(project_root.join("translations/app_zh_CN.ts"), "<TS/>").unwrap();
+        fs::write(project_root.join("translations/desktop.ts"), "<TS/>").unwrap();
+        let tx_yaml = TransifexYaml::from_str(&yaml_with_filters(&[
+            ("translations/app.ts", "translations/app_<lang>.ts"),
+            ("translations/desktop.ts", "translations/desktop_<lang>.ts"),
+        ])).unwrap();
+        let filters = tx_yaml.resources_without_translations(&project_root).unwrap();
+        let sources: Vec<&str> = filters.iter().map(|filter| filter.source.as_str()).collect();
+        assert_eq!(sources, vec!["translations/desktop.ts"]);
+    }
+
+    #[test]
+    fn tst_completion_report() {
+        use crate::i18n_file::linguist::tests::TEST_ZH_CN_TS_CONTENT;
+        let project_root = create_test_project_dir("tst_completion_report");
+        fs::create_dir_all(project_root.join("translations")).unwrap();
+        fs::write(project_root.join("translations/app.ts"), TEST_ZH_CN_TS_CONTENT).unwrap();
+        fs::write(project_root.join("translations/app_zh_CN.ts"), TEST_ZH_CN_TS_CONTENT).unwrap();
+        let mut untranslated = Ts::from_str(TEST_ZH_CN_TS_CONTENT).unwrap();
+        untranslated.clear_finished_messages();
+        untranslated.save_into_file(&project_root.join("translations/app_zh_TW.ts")).unwrap();
+        let tx_yaml = TransifexYaml::from_str(r#"
+filters:
+  - filter_type: file
+    source_file: translations/app.ts
+    file_format: QT
+    source_language: en_US
+    translation_files_expression: translations/app_<lang>.ts
+  - filter_type: file
+    source_file: translations/app.po
+    file_format: PO
+    source_language: en_US
+    translation_files_expression: translations/app_<lang>.po
+settings:
+  pr_branch_name: transifex_update_<br_unique_id>
+"#).unwrap();
+        assert_eq!(tx_yaml.filters.len(), 2);
+
+        let report = tx_yaml.completion_report(&project_root).unwrap();
+        assert_eq!(report, vec![("translations/app.ts".to_string(), vec![
+            ("zh_CN".to_string(), 75.0),
+            ("zh_TW".to_string(), 0.0),
+        ])]);
+    }
+
+    #[test]
+    fn tst_locale_coverage_gaps() {
+        let project_root = create_test_project_dir("tst_locale_coverage_gaps");
+        fs::create_dir_all(project_root.join("translations")).unwrap();
+        for file_name in ["app_zh_CN.ts", "app_de.ts", "desktop_zh_CN.ts"] {
+            fs::write(project_root.join("translations").join(file_name), "<TS/>").unwrap();
+        }
+        let tx_yaml = TransifexYaml::from_str(&yaml_with_filters(&[
+            ("translations/app.ts", "translations/app_<lang>.ts"),
+            ("translations/desktop.ts", "translations/desktop_<lang>.ts"),
+        ])).unwrap();
+        let gaps = tx_yaml.locale_coverage_gaps(&project_root).unwrap();
+        assert_eq!(gaps.keys().collect::<Vec<&String>>(), vec!["de"]);
+        assert_eq!(gaps["de"].iter().map(|filter| filter.source.as_str()).collect::<Vec<&str>>(), vec!["translations/desktop.ts"]);
+    }
+
+    #[test]
+    fn tst_locale_status() {
+        let project_root = create_test_project_dir("tst_locale_status");
+        fs::create_dir_all(project_root.join("translations")).unwrap();
+        for file_name in ["app_zh_CN.ts", "app_de.ts"] {
+            fs::write(project_root.join("translations").join(file_name), "<TS/>").unwrap();
+        }
+        let tx_yaml = TransifexYaml::from_str(&yaml_with_filters(&[("translations/app.ts", "translations/app_<lang>.ts")])).unwrap();
+        assert_eq!(tx_yaml.locale_status(&project_root, &["zh_CN", "ja"]).unwrap(), vec![LocaleStatus {
+            source: "translations/app.ts".to_string(),
+            present: vec!["zh_CN".to_string()],
+            missing: vec!["ja".to_string()],
+            extra: vec!["de".to_string()],
+        }]);
+    }
+
+    #[test]
+    fn tst_add_locale() {
+        let project_root = create_test_project_dir("tst_add_locale");
+        fs::create_dir_all(project_root.join("translations")).unwrap();
+        fs::write(project_root.join("translations/desktop_de.ts"), "existing").unwrap();
+        let tx_yaml = TransifexYaml::from_str(&yaml_with_filters(&[
+            ("translations/app.ts", "translations/app_<lang>.ts"),
+            ("translations/desktop.ts", "translations/desktop_<lang>.ts"),
+        ])).unwrap();
+        let template_for = |filter: &Filter| format!("<TS language=\"de\"><!-- {} --></TS>", filter.source);
+        let created = tx_yaml.add_locale(&project_root, "de", template_for).unwrap();
+        assert_eq!(created, vec![project_root.join("translations/app_de.ts")]);
+        assert_eq!(fs::read_to_string(project_root.join("translations/app_de.ts")).unwrap(), "<TS language=\"de\"><!-- translations/app.ts --></TS>");
+        assert_eq!(fs::read_to_string(project_root.join("translations/desktop_de.ts")).unwrap(), "existing");
+        assert!(tx_yaml.add_locale(&project_root, "de", template_for).unwrap().is_empty());
+        assert!(tx_yaml.add_locale(&project_root, "../de", template_for).is_err());
+    }
+
+    #[test]
+    fn tst_files_matched_by_multiple_filters() {
+        let project_root = create_test_project_dir("tst_files_matched_by_multiple_filters");
+        fs::create_dir_all(project_root.join("translations")).unwrap();
+        fs::write(project_root.join("translations/app_zh_CN.ts"), "<TS/>").unwrap();
+        fs::write(project_root.join("translations/app_ja.ts"), "<TS/>").unwrap();
+        fs::write(project_root.join("translations/app_ja_JP.ts"), "<TS/>").unwrap();
+        let tx_yaml = TransifexYaml::from_str(&yaml_with_filters(&[
+            ("translations/app.ts", "translations/app_<lang>.ts"),
+            // overlaps with the pattern above for every file with a `ja` prefixed language
+            ("translations/app_ja.ts", "translations/app_ja<lang>.ts"),
+        ])).unwrap();
+        let duplicates = tx_yaml.files_matched_by_multiple_filters(&project_root).unwrap();
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates[0].0, project_root.join("translations/app_ja_JP.ts"));
+        let sources: Vec<&str> = duplicates[0].1.iter().map(|filter| filter.source.as_str()).collect();
+        assert_eq!(sources, vec!["translations/app.ts", "translations/app_ja.ts"]);
+    }
+}
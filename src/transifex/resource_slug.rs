@@ -0,0 +1,151 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+// Transifex resource slugs, i.e. the `r:` segment of `o:org:p:proj:r:res`.
+
+use super::tx_config_file::normalize_host;
+use super::yaml_file::normalize_source_path;
+
+/// Placeholder in slug templates which is replaced by the slugified source file path.
+pub const RESOURCE_PLACEHOLDER: &str = "<resource>";
+
+/// Placeholder in slug templates which is replaced by [`source_path_hash`] of the source file path.
+///
+/// Use it together with [`RESOURCE_PLACEHOLDER`], e.g. `<resource>-<hash>`, to keep slugs unique when
+/// different source files slugify to the same name.
+pub const HASH_PLACEHOLDER: &str = "<hash>";
+
+/// Short stable hash of the given source file path, i.e. 8 lowercase hex digits of its 32-bit FNV-1a hash.
+///
+/// The path is normalized first, see [`normalize_source_path`]. Unlike [`std::hash::DefaultHasher`],
+/// the result never changes between releases, so generated slugs stay the same.
+pub fn source_path_hash(source_file: &str) -> String {
+    let mut hash: u32 = 0x811c9dc5;
+    for byte in normalize_source_path(source_file).bytes() {
+        hash ^= byte as u32;
+        hash = hash.wrapping_mul(0x01000193);
+    }
+    format!("{hash:08x}")
+}
+
+/// Turn a source file path into a Transifex-valid resource slug.
+///
+/// The file extension is stripped, the path is lowercased, every character other than ASCII
+/// alphanumerics, `-` and `_` (including `/` and `.`) is replaced by `_`, and repeated `_` are collapsed.
+pub fn slugify_resource(path: &str) -> String {
+    let path = path.trim();
+    let file_name_start = path.rfind('/').map(|idx| idx + 1).unwrap_or(0);
+    let path = match path[file_name_start..].rfind('.') {
+        Some(idx) if idx > 0 => &path[..file_name_start + idx],
+        _ => path,
+    };
+
+    let mut slug = String::with_capacity(path.len());
+    for c in path.chars() {
+        let c = if c.is_ascii_alphanumeric() || c == '-' { c.to_ascii_lowercase() } else { '_' };
+        if c == '_' && (slug.is_empty() || slug.ends_with('_')) {
+            continue;
+        }
+        slug.push(c);
+    }
+    while slug.ends_with('_') {
+        slug.pop();
+    }
+    slug
+}
+
+/// Whether the given string is a valid resource slug, i.e. non-empty and only made of ASCII
+/// alphanumerics, `-` and `_`.
+pub fn is_valid_resource_slug(slug: &str) -> bool {
+    !slug.is_empty() && slug.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Create a full resource slug from the given template, by replacing [`RESOURCE_PLACEHOLDER`]
+/// with the slugified source file path, and [`HASH_PLACEHOLDER`] with its hash.
+///
+/// e.g. `o:linuxdeepin:p:deepin-desktop-environment:r:<resource>`
+pub fn resource_slug_from_template(template: &str, source_file: &str) -> String {
+    fill_slug_template(template, &slugify_resource(source_file), source_file)
+}
+
+/// Same as [`resource_slug_from_template`], but with the given resource slug instead of the
+/// slugified source file path.
+pub fn fill_slug_template(template: &str, resource_slug: &str, source_file: &str) -> String {
+    template.replace(RESOURCE_PLACEHOLDER, resource_slug)
+        .replace(HASH_PLACEHOLDER, &source_path_hash(source_file))
+}
+
+/// A full resource slug `o:org:p:proj:r:res`, split into its components.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourceSlug {
+    /// Organization slug, the `o:` segment.
+    pub organization: String,
+    /// Project slug, the `p:` segment.
+    pub project: String,
+    /// Resource slug, the `r:` segment.
+    pub resource: String,
+}
+
+impl ResourceSlug {
+    /// Parse a full resource slug like `o:linuxdeepin:p:deepin-home:r:deepin-home`. Returns `None` if
+    /// a segment is missing or empty.
+    pub fn parse(full_slug: &str) -> Option<Self> {
+        let segments: Vec<&str> = full_slug.trim().split(':').collect();
+        let ["o", organization, "p", project, "r", resource] = segments.as_slice() else {
+            return None;
+        };
+        if organization.is_empty() || project.is_empty() || resource.is_empty() {
+            return None;
+        }
+        Some(Self {
+            organization: organization.to_string(),
+            project: project.to_string(),
+            resource: resource.to_string(),
+        })
+    }
+
+    /// Browsable page of the resource on the given Transifex web host, e.g.
+    /// `https://app.transifex.com/linuxdeepin/deepin-home/deepin-home/`. Trailing slashes of the host are ignored.
+    pub fn web_url(&self, host: &str) -> String {
+        format!("{}/{}/{}/{}/", normalize_host(host), self.organization, self.project, self.resource)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn tst_slugify_resource() {
+        assert_eq!(slugify_resource("shell-launcher-applet/translations/org.deepin.ds.ts"), "shell-launcher-applet_translations_org_deepin_ds");
+        assert_eq!(slugify_resource("./Translations//App.ts"), "translations_app");
+        assert_eq!(slugify_resource("translations/.hidden"), "translations_hidden");
+        assert!(is_valid_resource_slug(&slugify_resource("shell-launcher-applet/translations/org.deepin.ds.ts")));
+        assert!(!is_valid_resource_slug("dock launcher"));
+        assert!(!is_valid_resource_slug(""));
+        assert_eq!(
+            resource_slug_from_template("o:linuxdeepin:p:deepin-home:r:<resource>", "translations/deepin-home.ts"),
+            "o:linuxdeepin:p:deepin-home:r:translations_deepin-home"
+        );
+    }
+
+    #[test]
+    fn tst_hash_placeholder() {
+        let template = "o:linuxdeepin:p:deepin-home:r:<resource>-<hash>";
+        // both slugify to translations_app
+        assert_eq!(resource_slug_from_template(template, "translations/app.ts"), "o:linuxdeepin:p:deepin-home:r:translations_app-183f370c");
+        assert_eq!(resource_slug_from_template(template, "translations/app.po"), "o:linuxdeepin:p:deepin-home:r:translations_app-0c3496cc");
+        assert_eq!(source_path_hash("./translations//app.ts"), source_path_hash("translations/app.ts"));
+        assert_eq!(fill_slug_template("<resource>", "app", "translations/app.ts"), "app");
+    }
+
+    #[test]
+    fn tst_resource_slug_web_url() {
+        let slug = ResourceSlug::parse("o:linuxdeepin:p:deepin-desktop-environment:r:dde-control-center").unwrap();
+        assert_eq!(slug.organization, "linuxdeepin");
+        assert_eq!(slug.web_url("https://app.transifex.com/"), "https://app.transifex.com/linuxdeepin/deepin-desktop-environment/dde-control-center/");
+        assert_eq!(ResourceSlug::parse("o:linuxdeepin:p:deepin-home"), None);
+        assert_eq!(ResourceSlug::parse("o:linuxdeepin:p::r:deepin-home"), None);
+    }
+}
@@ -0,0 +1,189 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+// Joins local translation file stats with the stats reported by Transifex, keyed by resource slug.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use crate::i18n_file::common::MessageStats;
+use crate::report::{Finding, RemoteStats, Report, Severity, StatsEntry};
+use super::tx_config_file::TxConfig;
+
+/// Issue id of [`LanguageStats`] whose local and remote completeness diverge.
+pub const STATS_DIVERGENCE: &str = "stats-divergence";
+
+/// Remote stats of every resource, keyed by full resource slug and then by Transifex language code.
+pub type RemoteResourceStats = BTreeMap<String, BTreeMap<String, RemoteStats>>;
+
+/// Local and remote stats of a single language of a resource.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LanguageStats {
+    /// Local language code, i.e. with `lang_map` applied.
+    pub language: String,
+    /// The local translation file, relative to the project root.
+    pub path: PathBuf,
+    /// Stats of the local translation file, `None` if the file doesn't exist.
+    pub local: Option<MessageStats>,
+    /// Stats reported by Transifex, `None` if not fetched or the language doesn't exist remotely.
+    pub remote: Option<RemoteStats>,
+}
+
+impl LanguageStats {
+    /// Absolute difference between local and remote completeness, in percentage points.
+    ///
+    /// A missing side counts as 0% complete, `None` if both sides are missing.
+    pub fn divergence(&self) -> Option<f64> {
+        if self.local.is_none() && self.remote.is_none() {
+            return None;
+        }
+        let local = self.local.as_ref().map_or(0.0, MessageStats::completeness_percentage);
+        let remote = self.remote.as_ref().map_or(0.0, RemoteStats::completeness_percentage);
+        Some((local - remote).abs())
+    }
+
+    /// Whether both sides are known and diverge by more than `threshold` percentage points,
+    /// which usually means someone forgot to push or pull translations.
+    pub fn is_divergent(&self, threshold: f64) -> bool {
+        self.local.is_some() && self.remote.is_some() && self.divergence().is_some_and(|divergence| divergence > threshold)
+    }
+}
+
+/// Local and remote stats of a resource of `.tx/config`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourceStats {
+    /// Full resource slug, i.e. `o:org:p:proj:r:res`.
+    pub slug: String,
+    /// Source file, relative to the project root.
+    pub source_file: String,
+    /// Stats of every language found locally or remotely, sorted by language code.
+    pub languages: Vec<LanguageStats>,
+}
+
+impl ResourceStats {
+    /// Convert to report stats entries, one for each language.
+    pub fn to_stats_entries(&self) -> Vec<StatsEntry> {
+        self.languages.iter().map(|language_stats| StatsEntry {
+            resource: self.source_file.clone(),
+            language: Some(language_stats.language.clone()),
+            path: Some(language_stats.path.clone()),
+            stats: language_stats.local.clone().unwrap_or_default(),
+            slug: Some(self.slug.clone()),
+            remote: language_stats.remote,
+        }).collect()
+    }
+
+    /// Create a finding for every language diverging by more than `threshold` percentage points.
+    pub fn to_findings(&self, threshold: f64) -> Vec<Finding> {
+        self.languages.iter().filter(|language_stats| language_stats.is_divergent(threshold)).map(|language_stats| {
+            // divergent languages always have both sides
+            let local = language_stats.local.as_ref().unwrap();
+            let remote = language_stats.remote.as_ref().unwrap();
+            Finding::new(Severity::Warning, STATS_DIVERGENCE, format!(
+                "Resource {} of language {} is {:.2}% complete locally but {:.2}% complete on Transifex",
+                self.slug, language_stats.language, local.completeness_percentage(), remote.completeness_percentage()))
+                .with_path(&language_stats.path)
+                .with_data(serde_json::json!({
+                    "slug": self.slug,
+                    "language": language_stats.language,
+                    "divergence": language_stats.divergence(),
+                }))
+        }).collect()
+    }
+}
+
+/// Extract the language code from a path matching the given `file_filter`.
+fn language_of_path(file_filter: &str, path: &str) -> Option<String> {
+    let (prefix, suffix) = file_filter.split_once("<lang>")?;
+    let language = path.strip_prefix(prefix)?.strip_suffix(suffix)?;
+    (!language.is_empty() && !language.contains('/')).then(|| language.to_string())
+}
+
+/// Join local stats with remote stats for every resource section of the given `.tx/config`.
+///
+/// `local` holds the stats of local translation files, keyed by path relative to the project root.
+/// Remote language codes are mapped to local ones with the `lang_map` of each resource, so both
+/// sides of a language end up in the same [`LanguageStats`].
+pub fn join_resource_stats(tx_config: &TxConfig, local: &BTreeMap<PathBuf, MessageStats>, remote: &RemoteResourceStats) -> Vec<ResourceStats> {
+    let mut resources = Vec::<ResourceStats>::new();
+    for section in &tx_config.resource_sections {
+        let lang_map = tx_config.lang_map_for(&section.source_file);
+        let mut remote_by_language = BTreeMap::<String, RemoteStats>::new();
+        for (remote_language, stats) in remote.get(&section.resource_full_slug).into_iter().flatten() {
+            let language = lang_map.get(remote_language).unwrap_or(remote_language);
+            remote_by_language.insert(language.clone(), *stats);
+        }
+
+        let mut languages = remote_by_language.keys().cloned().collect::<BTreeSet<String>>();
+        languages.extend(local.keys()
+            .filter_map(|path| language_of_path(&section.file_filter, &path.to_string_lossy().replace('\\', "/"))));
+        // the source language of the resource is not a translation
+        languages.remove(&section.source_lang);
+
+        let languages = languages.into_iter().map(|language| {
+            let path = Path::new(&section.file_filter.replace("<lang>", &language)).to_path_buf();
+            LanguageStats {
+                local: local.get(&path).cloned(),
+                remote: remote_by_language.get(&language).copied(),
+                language,
+                path,
+            }
+        }).collect();
+        resources.push(ResourceStats {
+            slug: section.resource_full_slug.clone(),
+            source_file: section.source_file.clone(),
+            languages,
+        });
+    }
+    resources
+}
+
+/// Build a report of the given resources, with a finding for every divergence above `threshold`.
+pub fn resource_stats_report(resources: &[ResourceStats], threshold: f64) -> Report {
+    let mut report = Report::default();
+    for resource in resources {
+        report.stats.extend(resource.to_stats_entries());
+        report.extend(resource.to_findings(threshold));
+    }
+    report
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::transifex::tx_config_file::tests::TEST_TX_CONFIG_CONTENT;
+
+    #[test]
+    fn tst_join_resource_stats() {
+        let tx_config = TxConfig::from_str(TEST_TX_CONFIG_CONTENT).unwrap();
+        let local = BTreeMap::from([
+            (PathBuf::from("translations/dde-control-center_zh_CN.ts"), MessageStats { finished: 9, unfinished: 1, vanished: 0, obsolete: 0 }),
+            (PathBuf::from("translations/dde-control-center_ja.ts"), MessageStats { finished: 2, unfinished: 8, vanished: 0, obsolete: 0 }),
+            (PathBuf::from("translations/dde-control-center_en.ts"), MessageStats { finished: 0, unfinished: 10, vanished: 0, obsolete: 0 }),
+        ]);
+        let remote: RemoteResourceStats = BTreeMap::from([
+            ("o:linuxdeepin:p:deepin-desktop-environment:r:dde-control-center".to_string(), BTreeMap::from([
+                ("zh_CN".to_string(), RemoteStats { translated: 9, total: 10 }),
+                ("ja".to_string(), RemoteStats { translated: 10, total: 10 }),
+                ("de".to_string(), RemoteStats { translated: 5, total: 10 }),
+            ])),
+        ]);
+        let resources = join_resource_stats(&tx_config, &local, &remote);
+        assert_eq!(resources.len(), tx_config.resource_sections.len());
+        let resource = resources.iter().find(|resource| resource.slug.ends_with(":r:dde-control-center")).unwrap();
+        let languages: Vec<&str> = resource.languages.iter().map(|language_stats| language_stats.language.as_str()).collect();
+        assert_eq!(languages, vec!["de", "ja", "zh_CN"]);
+        // only known on one side
+        assert_eq!(resource.languages[0].local, None);
+        assert!(!resource.languages[0].is_divergent(5.0));
+        assert!(resource.languages[1].is_divergent(5.0));
+        assert_eq!(resource.languages[2].divergence(), Some(0.0));
+
+        let report = resource_stats_report(&resources, 5.0);
+        assert_eq!(report.filter_by_id(STATS_DIVERGENCE).count(), 1);
+        let entry = report.stats.iter().find(|entry| entry.language.as_deref() == Some("ja")).unwrap();
+        assert_eq!(entry.slug.as_deref(), Some(resource.slug.as_str()));
+        assert_eq!(entry.remote, Some(RemoteStats { translated: 10, total: 10 }));
+    }
+}
@@ -4,39 +4,50 @@
 
 // Transifex OpenAPI doc: https://transifex.github.io/openapi/
 
+use std::collections::BTreeMap;
 use directories::BaseDirs;
 use serde::Deserialize;
 use thiserror::Error as TeError;
 
+use crate::report::RemoteStats;
 use super::{tx_config_file::{load_transifexrc_file, TxConfigLoadError}, yaml_file::TxResourceLookupEntry};
 
+/// Client of the Transifex REST API.
 pub struct TransifexRestApi {
     rest_hostname: String,
     token: String,
 }
 
+/// Error of Transifex REST API requests.
 #[derive(TeError, Debug)]
 pub enum TransifexRestApiError {
+    /// The HTTP request failed.
     #[error("Error making request: {0}")]
     UreqError(#[from] ureq::Error),
+    /// The response is not the expected JSON.
     #[error("Error parsing response: {0}")]
     SerdeError(#[from] serde_json::Error),
 }
 
+/// Attributes of a Transifex project or resource.
 #[derive(Deserialize, Clone, Debug)]
 pub struct TransifexDataAttributes {
+    /// Categories, which also record the GitHub integration link of a resource.
     pub categories: Option<Vec<String>>,
 }
 
+/// A Transifex project or resource returned by the REST API.
 #[derive(Deserialize, Clone, Debug)]
 pub struct TransifexData {
     /// This field should follow format:
     /// `o:organization_slug:p:project_slug:r:resource_slug`
     pub id: String,
+    /// Attributes of the project or resource.
     pub attributes: TransifexDataAttributes,
 }
 
 impl TransifexData {
+    /// Parse the GitHub integration link recorded in the categories of a resource, if any.
     pub fn parse_linked_resource_category(&self) -> Option<TxResourceLookupEntry> {
         let binding = self.attributes.categories.clone()?;
         let category = binding.iter()
@@ -57,15 +68,55 @@ impl TransifexData {
     }
 }
 
+/// Attributes of the stats of a resource language.
+#[derive(Deserialize, Clone, Debug)]
+pub struct TransifexLanguageStatsAttributes {
+    /// Translated strings.
+    pub translated_strings: u64,
+    /// All strings of the resource.
+    pub total_strings: u64,
+}
+
+/// Stats of a resource language returned by the REST API.
+#[derive(Deserialize, Clone, Debug)]
+pub struct TransifexLanguageStats {
+    /// This field should follow format:
+    /// `o:organization_slug:p:project_slug:r:resource_slug:l:language_code`
+    pub id: String,
+    /// Attributes of the stats.
+    pub attributes: TransifexLanguageStatsAttributes,
+}
+
+impl TransifexLanguageStats {
+    /// The Transifex language code of these stats, parsed from the id.
+    pub fn language_code(&self) -> Option<&str> {
+        self.id.rsplit_once(":l:").map(|(_, language_code)| language_code)
+    }
+
+    /// Convert to the report representation.
+    pub fn to_remote_stats(&self) -> RemoteStats {
+        RemoteStats {
+            translated: self.attributes.translated_strings,
+            total: self.attributes.total_strings,
+        }
+    }
+}
+
+/// A page of a paginated REST API response.
 #[derive(Deserialize, Debug)]
 pub struct TransifexPaginationResponse<T> {
+    /// Items of this page.
     pub data: Vec<T>,
     links: TransifexPaginationLinks,
 }
 
+/// A paginated REST API response.
 pub trait Paginated {
+    /// Type of the items.
     type T;
+    /// URL of the next page, if this is not the last page.
     fn next_page_url(&self) -> Option<&str>;
+    /// Items of this page.
     fn items(self) -> Vec<Self::T>;
 }
 
@@ -89,6 +140,7 @@ struct TransifexPaginationLinks {
 }
 
 impl TransifexRestApi {
+    /// Create a client with the given REST API host name and API token.
     pub fn new(rest_hostname: &str, token: &str) -> Self {
         Self {
             rest_hostname: rest_hostname.to_string(),
@@ -96,6 +148,7 @@ impl TransifexRestApi {
         }
     }
 
+    /// Create a client with the host name and API token from `~/.transifexrc`.
     pub fn new_from_transifexrc() -> Result<Self, TxConfigLoadError> {
         let xdg_dirs = BaseDirs::new().expect("Not able to get xdg base directories");
         let transifexrc_file = xdg_dirs.home_dir().join(".transifexrc");
@@ -103,6 +156,7 @@ impl TransifexRestApi {
         Ok(TransifexRestApi::new(&transifexrc.rest_hostname, &transifexrc.token))
     }
     
+    /// Fetch all pages of the given API path, e.g. `/projects?...`, and return all items.
     pub fn fetch_paginated<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<Vec<T>, TransifexRestApiError> {
         let mut all_items = Vec::<T>::new();
         let mut next_page_url = Some(self.rest_hostname.clone() + url);
@@ -119,11 +173,13 @@ impl TransifexRestApi {
         Ok(all_items)
     }
 
+    /// Get all projects under the given organization.
     pub fn get_all_projects(&self, organization_slug: &str) -> Result<Vec<TransifexData>, TransifexRestApiError> {
         let url = format!("/projects?filter[organization]=o:{}", organization_slug);
         self.fetch_paginated::<TransifexData>(&url)
     }
 
+    /// Get all resources of the given project which are linked to a GitHub repository.
     pub fn get_all_linked_resources(&self, organization_slug: &str, project_slug: &str) -> Result<Vec<TransifexData>, TransifexRestApiError> {
         let url = format!("/resources?filter[project]=o:{}:p:{}", organization_slug, project_slug);
         let resources = self.fetch_paginated::<TransifexData>(&url)?;
@@ -136,6 +192,19 @@ impl TransifexRestApi {
         }).collect();
         Ok(linked_resources)
     }
+
+    /// Get the stats of every language of the given resource, keyed by Transifex language code.
+    ///
+    /// `resource_full_slug` should follow format `o:organization_slug:p:project_slug:r:resource_slug`.
+    pub fn get_resource_language_stats(&self, resource_full_slug: &str) -> Result<BTreeMap<String, RemoteStats>, TransifexRestApiError> {
+        // the project filter is mandatory
+        let project_id = resource_full_slug.split_once(":r:").map_or(resource_full_slug, |(project_id, _)| project_id);
+        let url = format!("/resource_language_stats?filter[project]={}&filter[resource]={}", project_id, resource_full_slug);
+        let stats = self.fetch_paginated::<TransifexLanguageStats>(&url)?;
+        Ok(stats.iter()
+            .filter_map(|language_stats| Some((language_stats.language_code()?.to_string(), language_stats.to_remote_stats())))
+            .collect())
+    }
 }
 
 #[cfg(test)]
@@ -180,4 +249,30 @@ pub mod tests {
         let resp_json: TransifexPaginationResponse<TransifexData> = serde_json::from_str(resp_text).unwrap();
         println!("{:?}", resp_json);
     }
+
+    #[test]
+    fn tst_parse_language_stats_response() {
+        let resp_text = r#"{
+    "data": [
+        {
+            "id": "o:linuxdeepin:p:deepin-home:r:bad354a0c370deff052c13b687289331:l:zh_CN",
+            "type": "resource_language_stats",
+            "attributes": {
+                "untranslated_strings": 3,
+                "translated_strings": 97,
+                "reviewed_strings": 0,
+                "total_strings": 100
+            }
+        }
+    ],
+    "links": {
+        "self": "https://rest.api.transifex.com/resource_language_stats?filter[project]=o:linuxdeepin:p:deepin-home",
+        "next": null,
+        "previous": null
+    }
+}"#;
+        let resp_json: TransifexPaginationResponse<TransifexLanguageStats> = serde_json::from_str(resp_text).unwrap();
+        assert_eq!(resp_json.data[0].language_code(), Some("zh_CN"));
+        assert_eq!(resp_json.data[0].to_remote_stats(), RemoteStats { translated: 97, total: 100 });
+    }
 }
\ No newline at end of file
@@ -0,0 +1,389 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+// Language codes accepted by Transifex.
+// Generated by `scripts/gen_supported_locales.sh`, please don't edit the table by hand.
+
+/// Transifex-supported language codes, sorted bytewise.
+pub static SUPPORTED_LOCALES: &[&str] = &[
+    "af",
+    "af_ZA",
+    "ak",
+    "am",
+    "am_ET",
+    "an",
+    "ar",
+    "ar_AE",
+    "ar_BH",
+    "ar_DZ",
+    "ar_EG",
+    "ar_IQ",
+    "ar_JO",
+    "ar_KW",
+    "ar_LB",
+    "ar_LY",
+    "ar_MA",
+    "ar_OM",
+    "ar_QA",
+    "ar_SA",
+    "ar_SD",
+    "ar_SY",
+    "ar_TN",
+    "ar_YE",
+    "as",
+    "ast",
+    "az",
+    "az_AZ",
+    "ba",
+    "be",
+    "be_BY",
+    "bg",
+    "bg_BG",
+    "bn",
+    "bn_BD",
+    "bn_IN",
+    "bo",
+    "br",
+    "bs",
+    "bs_BA",
+    "ca",
+    "ca@valencia",
+    "ca_ES",
+    "ckb",
+    "co",
+    "cs",
+    "cs_CZ",
+    "cv",
+    "cy",
+    "cy_GB",
+    "da",
+    "da_DK",
+    "de",
+    "de_AT",
+    "de_CH",
+    "de_DE",
+    "de_LU",
+    "dsb",
+    "dv",
+    "dz",
+    "el",
+    "el_CY",
+    "el_GR",
+    "en",
+    "en_AU",
+    "en_CA",
+    "en_GB",
+    "en_IE",
+    "en_IN",
+    "en_NZ",
+    "en_PH",
+    "en_SG",
+    "en_US",
+    "en_ZA",
+    "eo",
+    "es",
+    "es_419",
+    "es_AR",
+    "es_BO",
+    "es_CL",
+    "es_CO",
+    "es_CR",
+    "es_DO",
+    "es_EC",
+    "es_ES",
+    "es_GT",
+    "es_HN",
+    "es_MX",
+    "es_NI",
+    "es_PA",
+    "es_PE",
+    "es_PR",
+    "es_PY",
+    "es_SV",
+    "es_US",
+    "es_UY",
+    "es_VE",
+    "et",
+    "et_EE",
+    "eu",
+    "eu_ES",
+    "fa",
+    "fa_AF",
+    "fa_IR",
+    "ff",
+    "fi",
+    "fi_FI",
+    "fil",
+    "fo",
+    "fr",
+    "fr_BE",
+    "fr_CA",
+    "fr_CH",
+    "fr_FR",
+    "fr_LU",
+    "fur",
+    "fy",
+    "ga",
+    "ga_IE",
+    "gd",
+    "gl",
+    "gl_ES",
+    "gn",
+    "gu",
+    "gu_IN",
+    "gv",
+    "ha",
+    "haw",
+    "he",
+    "he_IL",
+    "hi",
+    "hi_IN",
+    "hr",
+    "hr_HR",
+    "hsb",
+    "ht",
+    "hu",
+    "hu_HU",
+    "hy",
+    "hy_AM",
+    "ia",
+    "id",
+    "id_ID",
+    "ig",
+    "is",
+    "is_IS",
+    "it",
+    "it_CH",
+    "it_IT",
+    "iu",
+    "ja",
+    "ja_JP",
+    "jv",
+    "ka",
+    "ka_GE",
+    "kab",
+    "kk",
+    "kk_KZ",
+    "km",
+    "km_KH",
+    "kn",
+    "kn_IN",
+    "ko",
+    "ko_KR",
+    "ku",
+    "ky",
+    "ky_KG",
+    "la",
+    "lb",
+    "lg",
+    "li",
+    "ln",
+    "lo",
+    "lt",
+    "lt_LT",
+    "lv",
+    "lv_LV",
+    "mai",
+    "mg",
+    "mi",
+    "mk",
+    "mk_MK",
+    "ml",
+    "ml_IN",
+    "mn",
+    "mn_MN",
+    "mr",
+    "mr_IN",
+    "ms",
+    "ms_MY",
+    "mt",
+    "my",
+    "nb",
+    "nb_NO",
+    "nds",
+    "ne",
+    "ne_NP",
+    "nl",
+    "nl_BE",
+    "nl_NL",
+    "nn",
+    "nn_NO",
+    "no",
+    "oc",
+    "om",
+    "or",
+    "pa",
+    "pa_IN",
+    "pa_PK",
+    "pl",
+    "pl_PL",
+    "ps",
+    "pt",
+    "pt_BR",
+    "pt_PT",
+    "qu",
+    "rm",
+    "ro",
+    "ro_RO",
+    "ru",
+    "ru_RU",
+    "ru_UA",
+    "rw",
+    "sa",
+    "sc",
+    "sd",
+    "si",
+    "si_LK",
+    "sk",
+    "sk_SK",
+    "sl",
+    "sl_SI",
+    "so",
+    "sq",
+    "sq_AL",
+    "sr",
+    "sr@latin",
+    "sr_RS",
+    "sv",
+    "sv_FI",
+    "sv_SE",
+    "sw",
+    "sw_KE",
+    "szl",
+    "ta",
+    "ta_IN",
+    "ta_LK",
+    "te",
+    "te_IN",
+    "tg",
+    "th",
+    "th_TH",
+    "ti",
+    "tk",
+    "tl",
+    "tn",
+    "tr",
+    "tr_TR",
+    "tt",
+    "ug",
+    "uk",
+    "uk_UA",
+    "ur",
+    "ur_IN",
+    "ur_PK",
+    "uz",
+    "uz@Cyrl",
+    "uz@Latn",
+    "vi",
+    "vi_VN",
+    "wa",
+    "wo",
+    "xh",
+    "yi",
+    "yo",
+    "zh",
+    "zh_CN",
+    "zh_HK",
+    "zh_SG",
+    "zh_TW",
+    "zu",
+    "zu_ZA",
+];
+
+fn normalize_locale_separator(code: &str) -> String {
+    code.trim().replace('-', "_")
+}
+
+/// Normalize a language code to the canonical case and separator, e.g. `PT-BR` becomes `pt_BR`
+/// and `ZH_HANS_CN` becomes `zh_Hans_CN`.
+///
+/// The language subtag is lowercased, 4-letter script subtags are titlecased, other subtags are
+/// uppercased, and `@` variants are kept as-is.
+pub fn normalize_locale(code: &str) -> String {
+    let normalized = normalize_locale_separator(code);
+    let (code, variant) = match normalized.split_once('@') {
+        Some((code, variant)) => (code, Some(variant)),
+        None => (normalized.as_str(), None),
+    };
+    let mut locale = code.split('_').enumerate().map(|(idx, subtag)| {
+        if idx == 0 {
+            subtag.to_ascii_lowercase()
+        } else if subtag.len() == 4 {
+            subtag[..1].to_ascii_uppercase() + &subtag[1..].to_ascii_lowercase()
+        } else {
+            subtag.to_ascii_uppercase()
+        }
+    }).collect::<Vec<String>>().join("_");
+    if let Some(variant) = variant {
+        locale.push('@');
+        locale.push_str(variant);
+    }
+    locale
+}
+
+/// Whether the given language code is accepted by Transifex as-is.
+pub fn is_supported_locale(code: &str) -> bool {
+    SUPPORTED_LOCALES.binary_search(&code).is_ok()
+}
+
+/// Whether the given language code is a known locale, in POSIX (`zh_CN`) or BCP-47 (`zh-CN`) form
+/// and in any case, i.e. whether it is in [`SUPPORTED_LOCALES`] after [`normalize_locale`].
+///
+/// Unlike [`is_supported_locale`], the code doesn't need to be in the exact form Transifex accepts,
+/// so this tells real locales apart from arbitrary words.
+pub fn is_known_locale(code: &str) -> bool {
+    is_supported_locale(&normalize_locale(code))
+}
+
+/// Find the supported language code closest to the given one.
+///
+/// Tries the code with `-` replaced by `_` and compared case-insensitively first,
+/// then falls back to the bare language subtag, e.g. `de_XX` is suggested as `de`.
+pub fn closest_supported_locale(code: &str) -> Option<&'static str> {
+    let normalized = normalize_locale_separator(code);
+    if let Some(supported) = SUPPORTED_LOCALES.iter().copied().find(|supported| supported.eq_ignore_ascii_case(&normalized)) {
+        return Some(supported);
+    }
+    let language = normalized.split(['_', '@']).next()?.to_ascii_lowercase();
+    SUPPORTED_LOCALES.binary_search(&language.as_str()).ok().map(|idx| SUPPORTED_LOCALES[idx])
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn tst_supported_locales_sorted() {
+        assert!(SUPPORTED_LOCALES.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn tst_is_supported_locale() {
+        assert!(is_supported_locale("zh_CN"));
+        assert!(is_supported_locale("pt_BR"));
+        assert!(!is_supported_locale("zh-CN"));
+        assert!(!is_supported_locale("zz_ZZ"));
+        assert_eq!(closest_supported_locale("zh-cn"), Some("zh_CN"));
+        assert_eq!(closest_supported_locale("de_XX"), Some("de"));
+        assert_eq!(closest_supported_locale("zz_ZZ"), None);
+    }
+
+    #[test]
+    fn tst_is_known_locale() {
+        assert!(is_known_locale("zh_CN"));
+        assert!(is_known_locale("zh-cn"));
+        assert!(is_known_locale("sr@latin"));
+        assert!(!is_known_locale("zz_ZZ"));
+        assert!(!is_known_locale("backup"));
+    }
+
+    #[test]
+    fn tst_normalize_locale() {
+        assert_eq!(normalize_locale("PT_BR"), "pt_BR");
+        assert_eq!(normalize_locale("zh-cn"), "zh_CN");
+        assert_eq!(normalize_locale("ZH_HANS_CN"), "zh_Hans_CN");
+        assert_eq!(normalize_locale("uz@Latn"), "uz@Latn");
+        assert_eq!(normalize_locale("de"), "de");
+    }
+}
@@ -0,0 +1,1086 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+// Matching translation files against the target patterns of transifex.yaml filters.
+
+use std::{collections::{BTreeMap, BTreeSet}, fs, path::{Path, PathBuf}};
+
+use regex::Regex;
+use serde::{Serialize, Deserialize};
+use thiserror::Error as TeError;
+
+use super::supported_locales::{is_known_locale, normalize_locale};
+use super::locale_names::cmp_locales_by_name;
+use super::yaml_file::{Filter, MatchError, TransifexYaml, normalize_source_path};
+use crate::i18n_file::sniff::{SNIFF_PREFIX_LEN, read_prefix};
+use crate::walk::{DirEntry, Walk, WalkOptions, walk};
+
+impl TransifexYaml {
+    /// Lazily iterate over translation files matched by all filters, one filter after another.
+    ///
+    /// At most one directory is open at a time. An error ends the files of the current filter
+    /// only, iteration continues with the next filter.
+    pub fn iter_target_files<'a>(&'a self, project_root: &'a PathBuf) -> impl Iterator<Item = Result<(&'a Filter, MatchedFile), std::io::Error>> + 'a {
+        self.filters.iter().flat_map(move |filter| {
+            filter.iter_target_files(project_root).map(move |matched| matched.map(|matched| (filter, matched)))
+        })
+    }
+}
+
+impl Filter {
+    /// Matched translation files whose language code is not a known locale, see [`is_known_locale`],
+    /// e.g. `app_backup.ts` matched by `app_<lang>.ts`. These are usually not translation files at all.
+    pub fn suspicious_matches(&self, project_root: &PathBuf) -> Result<Vec<(String, PathBuf)>, std::io::Error> {
+        Ok(self.match_target_files(project_root)?.into_iter()
+            .filter(|(lang_code, _)| !is_known_locale(lang_code))
+            .collect())
+    }
+
+    /// Check the leading bytes of every matched translation file for a BOM or invalid UTF-8, which
+    /// break loaders like Qt's. Returned files are in match order.
+    pub fn check_matched_encodings(&self, project_root: &PathBuf) -> Result<Vec<(PathBuf, EncodingIssue)>, MatchError> {
+        let matched = self.match_target_files(project_root)
+            .map_err(|e| MatchError::Filter(self.source.clone(), e))?;
+        let mut issues = Vec::<(PathBuf, EncodingIssue)>::new();
+        for (_, path) in matched {
+            let prefix = read_prefix(&path, SNIFF_PREFIX_LEN).map_err(|e| MatchError::Filter(self.source.clone(), e))?;
+            if let Some(issue) = EncodingIssue::from_prefix(&prefix) {
+                issues.push((path, issue));
+            }
+        }
+        Ok(issues)
+    }
+
+    /// Find existing translation files of this filter, with default [`MatchOptions`].
+    pub fn match_target_files(&self, project_root: &PathBuf) -> Result<Vec<(String, PathBuf)>, std::io::Error> {
+        self.match_target_files_with_options(project_root, &MatchOptions::default())
+    }
+
+    /// Same as [`Self::match_target_files`], but a missing target directory matches nothing, and
+    /// other errors are a [`MatchError::Filter`] of this filter.
+    pub fn matched_files(&self, project_root: &PathBuf) -> Result<Vec<(String, PathBuf)>, MatchError> {
+        match self.match_target_files(project_root) {
+            Ok(matched) => Ok(matched),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(MatchError::Filter(self.source.clone(), e)),
+        }
+    }
+
+    /// The language codes of the translation files of this filter, see [`Self::matched_files`].
+    pub fn matched_locales(&self, project_root: &PathBuf) -> Result<BTreeSet<String>, MatchError> {
+        Ok(self.matched_files(project_root)?.into_iter().map(|(lang_code, _)| lang_code).collect())
+    }
+
+    /// Match translation files against the target pattern, with the given options.
+    pub fn match_target_files_with_options(&self, project_root: &PathBuf, opts: &MatchOptions) -> Result<Vec<(String, PathBuf)>, std::io::Error> {
+        if self.exceeds_max_depth(opts) {
+            return Ok(Vec::new());
+        }
+        let pred = |entry: &DirEntry| {
+            if opts.skip_hidden && entry.file_name().to_str().is_some_and(|name| name.starts_with('.')) {
+                return false;
+            }
+            if !opts.follow_symlinks && entry.path_is_symlink() {
+                return false;
+            }
+            if opts.text_only {
+                // binary files, e.g. compiled .qm files, usually contain NUL bytes early
+                let Ok(prefix) = read_prefix(&entry.path(), SNIFF_PREFIX_LEN) else {
+                    return false;
+                };
+                if prefix.contains(&0) {
+                    return false;
+                }
+            }
+            true
+        };
+        let mut matches = Vec::new();
+        for entry in TargetEntries::new(self, project_root, opts, pred) {
+            // unreadable entries, e.g. dangling symlinks, are reported by `match_target_files_with_skipped`
+            if let TargetEntry::Matched(matched) = entry? {
+                matches.push((matched.lang_code, matched.path));
+            }
+            if let Some(max_matches) = opts.max_matches.filter(|max_matches| matches.len() > *max_matches) {
+                return Err(std::io::Error::other(format!("More than {max_matches} files match {:?}", self.target_pattern)));
+            }
+        }
+        if opts.sort_locales_by_name {
+            matches.sort_by(|(a, _), (b, _)| cmp_locales_by_name(a, b));
+        }
+        Ok(matches)
+    }
+
+    /// Whether the target directory is nested deeper than [`MatchOptions::max_depth`].
+    fn exceeds_max_depth(&self, opts: &MatchOptions) -> bool {
+        let Some(max_depth) = opts.max_depth else {
+            return false;
+        };
+        let depth = Path::new(&self.target_pattern).components()
+            .filter(|component| matches!(component, std::path::Component::Normal(_)))
+            .count()
+            .saturating_sub(1);
+        depth > max_depth
+    }
+
+    /// Match the given candidate files against the target pattern instead of reading the target
+    /// directory, e.g. for a file list from `git ls-files`, with default [`MatchOptions`].
+    ///
+    /// Relative candidates are relative to the project root. Returned paths are joined with the project
+    /// root, in candidate order. An invalid target pattern matches nothing.
+    pub fn match_against(&self, project_root: &PathBuf, candidates: &[PathBuf]) -> Vec<(String, PathBuf)> {
+        self.match_against_with_options(project_root, candidates, &MatchOptions::default())
+    }
+
+    /// Same as [`Self::match_against`], with the given options.
+    ///
+    /// Options which need to read the files, i.e. [`MatchOptions::follow_symlinks`] and
+    /// [`MatchOptions::text_only`], and [`MatchOptions::max_matches`] are ignored.
+    pub fn match_against_with_options(&self, project_root: &PathBuf, candidates: &[PathBuf], opts: &MatchOptions) -> Vec<(String, PathBuf)> {
+        let Ok((pattern, target_parent)) = resolve_target_pattern(self, project_root, opts) else {
+            return Vec::new();
+        };
+        if self.excluded_target_dir().is_some() || self.exceeds_max_depth(opts) {
+            return Vec::new();
+        }
+        let mut matches: Vec<(String, PathBuf)> = candidates.iter()
+            .map(|candidate| project_root.join(candidate))
+            .filter(|path| path.parent() == Some(target_parent.as_path()))
+            .filter_map(|path| {
+                let file_name = path.file_name()?.to_str()?;
+                if opts.skip_hidden && file_name.starts_with('.') {
+                    return None;
+                }
+                let lang_code = pattern.captures(file_name)?.get(1)?.as_str();
+                Some((canonical_lang_code(&opts.locale_order.to_canonical_order(lang_code)), path))
+            })
+            .collect();
+        if opts.sort_locales_by_name {
+            matches.sort_by(|(a, _), (b, _)| cmp_locales_by_name(a, b));
+        }
+        matches
+    }
+
+    /// Match translation files split into numbered parts, like `app_zh_CN_1.ts` and `app_zh_CN_2.ts`
+    /// for `app_<lang>.ts`, and group the parts by language code.
+    ///
+    /// Files without index, e.g. `app_zh_CN.ts`, are grouped with the indexed parts of the same language.
+    /// Groups are sorted by language code and parts by index, with the file without index first.
+    pub fn match_indexed_target_files(&self, project_root: &PathBuf, opts: &MatchOptions) -> Result<Vec<(String, Vec<PathBuf>)>, std::io::Error> {
+        let opts = MatchOptions { indexed_parts: true, ..opts.clone() };
+        let mut groups = BTreeMap::<String, Vec<PathBuf>>::new();
+        for (lang_code, path) in self.match_target_files_with_options(project_root, &opts)? {
+            groups.entry(lang_code).or_default().push(path);
+        }
+        Ok(groups.into_iter().map(|(lang_code, mut parts)| {
+            // parts only differ in their index, so shorter paths have smaller indexes
+            parts.sort_by(|a, b| a.as_os_str().len().cmp(&b.as_os_str().len()).then_with(|| a.cmp(b)));
+            (lang_code, parts)
+        }).collect())
+    }
+
+    /// The target pattern of the compiled `.qm` files of a QT filter, i.e. the target pattern with
+    /// its `.ts` suffix replaced by `.qm`. `None` if the target pattern doesn't end with `.ts`.
+    pub fn qm_target_pattern(&self) -> Option<String> {
+        self.target_pattern.strip_suffix(".ts").map(|stem| format!("{stem}.qm"))
+    }
+
+    /// Match the `.ts` translation files and their compiled `.qm` files, see [`Self::qm_target_pattern`],
+    /// to verify every translated locale is shipped compiled.
+    ///
+    /// Fails with [`std::io::ErrorKind::InvalidInput`] if the target pattern doesn't end with `.ts`.
+    pub fn match_compiled_qm_files(&self, project_root: &PathBuf) -> Result<QmCoverage, std::io::Error> {
+        let Some(qm_target_pattern) = self.qm_target_pattern() else {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "Target pattern doesn't end with .ts"));
+        };
+        let qm_filter = Filter { target_pattern: qm_target_pattern, ..self.clone() };
+        let mut qm_files = qm_filter.match_target_files(project_root)?.into_iter().collect::<BTreeMap<String, PathBuf>>();
+        let ts_locales = self.match_target_files(project_root)?.into_iter()
+            .map(|(lang_code, _)| lang_code)
+            .collect::<BTreeSet<String>>();
+        let mut coverage = QmCoverage::default();
+        for lang_code in ts_locales {
+            match qm_files.remove(&lang_code) {
+                Some(qm_file) => coverage.compiled.push((lang_code, qm_file)),
+                None => coverage.missing.push(lang_code),
+            }
+        }
+        Ok(coverage)
+    }
+
+    /// Same as [`Self::match_target_files`], but also reads the size and modification time of every
+    /// matched file.
+    ///
+    /// Files whose metadata can't be read, e.g. removed while matching, are returned in
+    /// [`DetailedTargetFileMatches::skipped`] with their errors. A missing target directory matches nothing.
+    pub fn match_target_files_detailed(&self, project_root: &PathBuf) -> Result<DetailedTargetFileMatches, MatchError> {
+        let mut matches = DetailedTargetFileMatches::default();
+        for (lang_code, path) in self.matched_files(project_root)? {
+            match fs::metadata(&path).and_then(|metadata| Ok((metadata.len(), metadata.modified()?))) {
+                Ok((size, modified)) => matches.matched.push(MatchedFileDetails { lang_code, path, size, modified }),
+                Err(e) => matches.skipped.push((path, e)),
+            }
+        }
+        Ok(matches)
+    }
+
+    /// Lazily iterate over translation files matched by the target pattern.
+    ///
+    /// The target directory is only opened on the first call to `next()`, and closed once the
+    /// iterator is exhausted or dropped. After an error is yielded, the iterator ends.
+    pub fn iter_target_files(&self, project_root: &PathBuf) -> TargetFiles {
+        TargetFiles {
+            entries: TargetEntries::new(self, project_root, &MatchOptions::default(), accept_all_entries as fn(&DirEntry) -> bool),
+        }
+    }
+
+    /// Same as [`Self::match_target_files`], but only directory entries accepted by `pred` are
+    /// matched against the target pattern.
+    ///
+    /// `pred` receives a [`walkdir`] entry, see [`crate::walk::DirEntry`], not a [`std::fs::DirEntry`].
+    pub fn match_target_files_with_predicate(&self, project_root: &PathBuf, pred: impl Fn(&DirEntry) -> bool) -> Result<Vec<(String, PathBuf)>, std::io::Error> {
+        TargetEntries::new(self, project_root, &MatchOptions::default(), pred)
+            .filter_map(|entry| match entry {
+                Ok(TargetEntry::Matched(matched)) => Some(Ok((matched.lang_code, matched.path))),
+                Ok(TargetEntry::SkippedNonUtf8(_) | TargetEntry::SkippedUnreadable(..)) => None,
+                Err(e) => Some(Err(e)),
+            })
+            .collect()
+    }
+
+    /// Same as [`Self::match_target_files`], but also reports the files skipped because their
+    /// file names are not valid UTF-8, or because they can't be read, e.g. dangling symlinks,
+    /// instead of silently ignoring them.
+    pub fn match_target_files_with_skipped(&self, project_root: &PathBuf) -> Result<TargetFileMatches, std::io::Error> {
+        let mut matches = TargetFileMatches::default();
+        for entry in TargetEntries::new(self, project_root, &MatchOptions::default(), accept_all_entries) {
+            match entry? {
+                TargetEntry::Matched(matched) => matches.matched.push((matched.lang_code, matched.path)),
+                TargetEntry::SkippedNonUtf8(path) => matches.skipped_non_utf8.push(path),
+                TargetEntry::SkippedUnreadable(path, e) => matches.skipped_unreadable.push((path, e)),
+            }
+        }
+        Ok(matches)
+    }
+
+    /// Same as [`Self::match_target_files`], but matches files as they exist at the given git
+    /// revision of the repository at `repo_root`, without checking it out.
+    ///
+    /// Requires `git` in `PATH`. Returned paths are relative to `repo_root`, and can be read
+    /// with `git show <rev>:<path>`.
+    pub fn match_target_files_at_rev(&self, repo_root: &Path, rev: &str) -> Result<Vec<(String, PathBuf)>, std::io::Error> {
+        if rev.starts_with('-') {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "Revision must not start with '-'"));
+        }
+        if self.excluded_target_dir().is_some() {
+            return Ok(Vec::new());
+        }
+        let target_pattern = normalize_source_path(&self.target_pattern);
+        let target_pattern_path = Path::new(&target_pattern);
+        let Some(target_filename_pattern) = target_pattern_path.file_name().and_then(|name| name.to_str()) else {
+            return Err(std::io::Error::other("File name not valid"));
+        };
+        let Some(target_filter_pattern) = create_filter_pattern(target_filename_pattern, false) else {
+            return Err(std::io::Error::other("Filter pattern not valid"));
+        };
+
+        let mut command = std::process::Command::new("git");
+        command.arg("-C").arg(repo_root).args(["ls-tree", "-z", rev, "--"]);
+        if let Some(target_parent) = target_pattern_path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            // the trailing slash lists the content of the directory instead of the directory itself
+            command.arg(format!("{}/", target_parent.to_string_lossy()));
+        }
+        let output = command.output()?;
+        if !output.status.success() {
+            return Err(std::io::Error::other(format!("git ls-tree failed: {}", String::from_utf8_lossy(&output.stderr).trim())));
+        }
+
+        let mut matched = Vec::new();
+        for entry in output.stdout.split(|byte| *byte == 0) {
+            // each entry is `<mode> <type> <object>\t<path>`, entries with non UTF-8 paths are
+            // ignored, same as when matching files on disk
+            let Some((info, path)) = std::str::from_utf8(entry).ok().and_then(|entry| entry.split_once('\t')) else {
+                continue;
+            };
+            if info.split(' ').nth(1) != Some("blob") {
+                continue;
+            }
+            let file_name = path.rsplit('/').next().unwrap_or(path);
+            if let Some(lang_code) = target_filter_pattern.captures(file_name).and_then(|captures| captures.get(1)) {
+                matched.push((canonical_lang_code(lang_code.as_str()), PathBuf::from(path)));
+            }
+        }
+        Ok(matched)
+    }
+}
+
+/// A translation file matched by the target pattern of a filter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchedFile {
+    /// Language code captured from the `<lang>` placeholder.
+    pub lang_code: String,
+    /// Path of the matched file, under the project root.
+    pub path: PathBuf,
+}
+
+/// A translation file matched by the target pattern of a filter, with its metadata.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchedFileDetails {
+    /// Language code captured from the `<lang>` placeholder.
+    pub lang_code: String,
+    /// Path of the matched file, under the project root.
+    pub path: PathBuf,
+    /// File size in bytes.
+    pub size: u64,
+    /// Last modification time.
+    pub modified: std::time::SystemTime,
+}
+
+#[derive(Serialize)]
+struct MatchedFileLine<'a> {
+    lang: &'a str,
+    path: std::borrow::Cow<'a, str>,
+}
+
+/// Write matched files as JSON Lines, i.e. one `{"lang": ..., "path": ...}` object per line.
+///
+/// Each result is written as soon as it's produced, so this can be fed with [`Filter::iter_target_files`]
+/// directly. Writing stops at the first error. Returns the number of written lines.
+pub fn write_matches_jsonl<W: std::io::Write>(writer: &mut W, results: impl IntoIterator<Item = Result<MatchedFile, std::io::Error>>) -> Result<usize, std::io::Error> {
+    let mut written = 0;
+    for matched in results {
+        let matched = matched?;
+        let line = MatchedFileLine { lang: &matched.lang_code, path: matched.path.to_string_lossy() };
+        serde_json::to_writer(&mut *writer, &line)?;
+        writer.write_all(b"\n")?;
+        written += 1;
+    }
+    Ok(written)
+}
+
+/// Iterator returned by [`Filter::iter_target_files`].
+pub struct TargetFiles {
+    entries: TargetEntries<fn(&DirEntry) -> bool>,
+}
+
+impl Iterator for TargetFiles {
+    type Item = Result<MatchedFile, std::io::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.entries.next()? {
+                Ok(TargetEntry::Matched(matched)) => return Some(Ok(matched)),
+                Ok(TargetEntry::SkippedNonUtf8(_) | TargetEntry::SkippedUnreadable(..)) => continue,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+fn accept_all_entries(_: &DirEntry) -> bool {
+    true
+}
+
+enum TargetEntry {
+    Matched(MatchedFile),
+    SkippedNonUtf8(PathBuf),
+    SkippedUnreadable(PathBuf, std::io::Error),
+}
+
+enum TargetEntriesState {
+    /// Pattern is resolved, but the directory is not opened yet.
+    Pending(Regex, PathBuf, bool),
+    Reading(Regex, Walk),
+    Failed(std::io::Error),
+    Done,
+}
+
+/// Directory entries accepted by `pred` and matched against the target pattern, not valid UTF-8, or
+/// unreadable.
+struct TargetEntries<P> {
+    state: TargetEntriesState,
+    pred: P,
+    locale_order: LocaleComponentOrder,
+}
+
+impl<P: Fn(&DirEntry) -> bool> TargetEntries<P> {
+    fn new(filter: &Filter, project_root: &PathBuf, opts: &MatchOptions, pred: P) -> Self {
+        let state = match resolve_target_pattern(filter, project_root, opts) {
+            Ok(_) if filter.excluded_target_dir().is_some() => TargetEntriesState::Done,
+            Ok((pattern, target_parent)) => TargetEntriesState::Pending(pattern, target_parent, opts.follow_symlinks),
+            Err(e) => TargetEntriesState::Failed(e),
+        };
+        Self { state, pred, locale_order: opts.locale_order }
+    }
+}
+
+/// Directories whose contents are never translation files, e.g. version control or build output.
+/// Target patterns inside them match nothing, see [`Filter::excluded_target_dir`].
+pub const EXCLUDED_TARGET_DIRS: &[&str] = &[".git", ".tx", "target"];
+
+/// Compile the file name part of the target pattern of the filter, and resolve the directory of its
+/// translation files.
+fn resolve_target_pattern(filter: &Filter, project_root: &PathBuf, opts: &MatchOptions) -> Result<(Regex, PathBuf), std::io::Error> {
+    let target_pattern_path = project_root.join(&filter.target_pattern);
+    let Some(target_filename_pattern) = target_pattern_path.file_name() else {
+        return Err(std::io::Error::new(std::io::ErrorKind::Other, "File name not found"));
+    };
+    let Some(target_filename_pattern) = target_filename_pattern.to_str() else {
+        return Err(std::io::Error::new(std::io::ErrorKind::Other, "File name not valid"));
+    };
+    let Some(target_filter_pattern) = create_filter_pattern_with_index(target_filename_pattern, opts.case_insensitive, opts.indexed_parts) else {
+        return Err(std::io::Error::new(std::io::ErrorKind::Other, "Filter pattern not valid"));
+    };
+    let Some(target_parent) = target_pattern_path.parent() else {
+        return Err(std::io::Error::new(std::io::ErrorKind::Other, "Parent dir not found"));
+    };
+    Ok((target_filter_pattern, target_parent.to_path_buf()))
+}
+
+impl<P: Fn(&DirEntry) -> bool> Iterator for TargetEntries<P> {
+    type Item = Result<TargetEntry, std::io::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match std::mem::replace(&mut self.state, TargetEntriesState::Done) {
+                TargetEntriesState::Pending(pattern, target_parent, follow_symlinks) => {
+                    let walk_opts = WalkOptions { max_depth: Some(1), follow_symlinks, ..WalkOptions::default() };
+                    self.state = TargetEntriesState::Reading(pattern, walk(&target_parent, &walk_opts));
+                },
+                TargetEntriesState::Reading(pattern, mut entries) => {
+                    let file = match entries.next()? {
+                        Ok(file) => file,
+                        // an unreadable entry, e.g. a dangling symlink, doesn't stop the others
+                        Err(e) if e.depth() > 0 => {
+                            let path = e.path().map(Path::to_path_buf).unwrap_or_default();
+                            self.state = TargetEntriesState::Reading(pattern, entries);
+                            return Some(Ok(TargetEntry::SkippedUnreadable(path, e.into())));
+                        },
+                        Err(e) => return Some(Err(e.into())),
+                    };
+                    let entry = if file.depth() == 0 || !(self.pred)(&file) {
+                        None
+                    } else if let Some(file_name) = file.file_name().to_str() {
+                        pattern.captures(file_name).and_then(|captures| captures.get(1)).map(|lang_code| {
+                            let lang_code = canonical_lang_code(&self.locale_order.to_canonical_order(lang_code.as_str()));
+                            TargetEntry::Matched(MatchedFile { lang_code, path: file.path().to_path_buf() })
+                        })
+                    } else {
+                        Some(TargetEntry::SkippedNonUtf8(file.path().to_path_buf()))
+                    };
+                    self.state = TargetEntriesState::Reading(pattern, entries);
+                    if let Some(entry) = entry {
+                        return Some(Ok(entry));
+                    }
+                },
+                TargetEntriesState::Failed(e) => return Some(Err(e)),
+                TargetEntriesState::Done => return None,
+            }
+        }
+    }
+}
+
+/// Options of matching translation files against target patterns.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MatchOptions {
+    /// Ignore files whose names start with `.`.
+    pub skip_hidden: bool,
+    /// Match the parts of the target pattern around `<lang>` case-insensitively.
+    pub case_insensitive: bool,
+    /// Match symbolic links as well. If disabled, symbolic links are ignored.
+    pub follow_symlinks: bool,
+    /// Match nothing if the target directory is nested deeper than this under the project root.
+    pub max_depth: Option<usize>,
+    /// Ignore files which look like binary files.
+    pub text_only: bool,
+    /// Fail if more files than this match, which usually means the pattern is too broad.
+    pub max_matches: Option<usize>,
+    /// Also match files with a `_N` index between the language code and the rest of the pattern,
+    /// e.g. `app_zh_CN_1.ts` for `app_<lang>.ts`, see [`Filter::match_indexed_target_files`].
+    pub indexed_parts: bool,
+    /// Sort matches by language name, then region, see [`super::locale_names::cmp_locales_by_name`].
+    /// Otherwise matches are sorted by file name, i.e. by language code bytewise.
+    pub sort_locales_by_name: bool,
+    /// Order of the language code components in file names, e.g. [`LocaleComponentOrder::RegionFirst`]
+    /// to match `CN_zh_app.ts` as `zh_CN`.
+    pub locale_order: LocaleComponentOrder,
+}
+
+/// Order of the `_` separated components of language codes in file names.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LocaleComponentOrder {
+    /// Language first, e.g. `zh_CN`, the canonical order.
+    #[default]
+    LanguageFirst,
+    /// Region first, i.e. the components in reverse order, e.g. `CN_zh` for `zh_CN`.
+    RegionFirst,
+}
+
+impl LocaleComponentOrder {
+    /// Reassemble a language code matched in a file name in the canonical order.
+    pub fn to_canonical_order(self, lang_code: &str) -> String {
+        match self {
+            Self::LanguageFirst => lang_code.to_string(),
+            Self::RegionFirst => lang_code.rsplit('_').collect::<Vec<&str>>().join("_"),
+        }
+    }
+}
+
+impl Default for MatchOptions {
+    fn default() -> Self {
+        Self {
+            skip_hidden: false,
+            case_insensitive: false,
+            follow_symlinks: true,
+            max_depth: None,
+            text_only: false,
+            max_matches: None,
+            indexed_parts: false,
+            sort_locales_by_name: false,
+            locale_order: LocaleComponentOrder::LanguageFirst,
+        }
+    }
+}
+
+/// Result of [`Filter::match_compiled_qm_files`].
+#[derive(Debug, Default, PartialEq)]
+pub struct QmCoverage {
+    /// Locales whose `.ts` file has a compiled `.qm` file, and the `.qm` file, sorted by locale.
+    pub compiled: Vec<(String, PathBuf)>,
+    /// Locales whose `.ts` file has no compiled `.qm` file, sorted.
+    pub missing: Vec<String>,
+}
+
+/// Result of [`Filter::match_target_files_with_skipped`].
+#[derive(Debug, Default)]
+pub struct TargetFileMatches {
+    /// Language code and path of matched files.
+    pub matched: Vec<(String, PathBuf)>,
+    /// Files ignored because their file names are not valid UTF-8.
+    pub skipped_non_utf8: Vec<PathBuf>,
+    /// Files ignored because they can't be read, e.g. dangling symlinks, with the error.
+    pub skipped_unreadable: Vec<(PathBuf, std::io::Error)>,
+}
+
+/// Result of [`Filter::match_target_files_detailed`].
+#[derive(Debug, Default)]
+pub struct DetailedTargetFileMatches {
+    /// Matched files with their metadata.
+    pub matched: Vec<MatchedFileDetails>,
+    /// Matched files whose metadata can't be read, with the error.
+    pub skipped: Vec<(PathBuf, std::io::Error)>,
+}
+
+/// An encoding problem found by [`Filter::check_matched_encodings`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EncodingIssue {
+    /// Starts with a UTF-8 byte order mark.
+    Utf8Bom,
+    /// Starts with a UTF-16 byte order mark, either endianness.
+    Utf16Bom,
+    /// The content is not valid UTF-8.
+    InvalidUtf8,
+}
+
+impl EncodingIssue {
+    /// Check the leading bytes of a file. A multi-byte character cut off at the end of the prefix is not an issue.
+    fn from_prefix(prefix: &[u8]) -> Option<Self> {
+        if prefix.starts_with(&[0xEF, 0xBB, 0xBF]) {
+            Some(Self::Utf8Bom)
+        } else if prefix.starts_with(&[0xFF, 0xFE]) || prefix.starts_with(&[0xFE, 0xFF]) {
+            Some(Self::Utf16Bom)
+        } else {
+            match std::str::from_utf8(prefix) {
+                Err(e) if e.error_len().is_some() => Some(Self::InvalidUtf8),
+                _ => None,
+            }
+        }
+    }
+}
+
+/// Longest target file name pattern accepted, patterns come from untrusted repositories.
+pub const MAX_FILTER_PATTERN_LEN: usize = 1024;
+
+/// Upper bound of the compiled size of a filter pattern regex.
+const FILTER_PATTERN_SIZE_LIMIT: usize = 1 << 20;
+
+/// Match a file name against a target file name pattern like `app_<lang>.ts`, and return the
+/// captured language code.
+///
+/// Returns `None` if the file name doesn't match, or if the pattern is invalid, i.e. longer than
+/// [`MAX_FILTER_PATTERN_LEN`] or without exactly one `<lang>` placeholder.
+pub fn match_lang_code(pattern: &str, file_name: &str, case_insensitive: bool) -> Option<String> {
+    create_filter_pattern(pattern, case_insensitive)?
+        .captures(file_name)
+        .and_then(|caps| caps.get(1))
+        .map(|lang_code| canonical_lang_code(lang_code.as_str()))
+}
+
+/// Legacy exports may uppercase the whole language code, e.g. `app_PT_BR.ts`. Such codes are
+/// returned in their canonical form, see [`normalize_locale`], other codes are returned as matched.
+fn canonical_lang_code(lang_code: &str) -> String {
+    if lang_code.chars().any(|c| c.is_ascii_lowercase()) {
+        lang_code.to_string()
+    } else {
+        normalize_locale(lang_code)
+    }
+}
+
+/// Error of [`compile_lang_regex`].
+#[derive(TeError, Debug)]
+pub enum LangRegexError {
+    /// The pattern is not a valid regex, or too large.
+    #[error("Fail to compile language regex {0:?} because: {1}")]
+    Invalid(String, #[source] regex::Error),
+    /// The pattern doesn't have exactly one capturing group for the language code.
+    #[error("Language regex {pattern:?} must have exactly one capturing group for the language code, found {found}")]
+    CaptureGroups {
+        /// The offending pattern.
+        pattern: String,
+        /// Number of capturing groups found.
+        found: usize,
+    },
+}
+
+/// Compile a user supplied regex matching translation file names, e.g. `^app-(.+)\.ts$`, whose only
+/// capturing group captures the language code, like `<lang>` of a target pattern does.
+///
+/// Patterns with zero or several capturing groups are rejected, since it's ambiguous which group
+/// holds the language code. Non-capturing groups like `(?:...)` are fine.
+pub fn compile_lang_regex(pattern: &str) -> Result<Regex, LangRegexError> {
+    let regex = regex::RegexBuilder::new(pattern)
+        .size_limit(FILTER_PATTERN_SIZE_LIMIT)
+        .build()
+        .map_err(|e| LangRegexError::Invalid(pattern.to_string(), e))?;
+    // `captures_len` counts the implicit group of the whole match too
+    let found = regex.captures_len() - 1;
+    if found != 1 {
+        return Err(LangRegexError::CaptureGroups { pattern: pattern.to_string(), found });
+    }
+    Ok(regex)
+}
+
+pub(crate) fn create_filter_pattern(pattern: &str, case_insensitive: bool) -> Option<Regex> {
+    create_filter_pattern_with_index(pattern, case_insensitive, false)
+}
+
+/// Same as [`create_filter_pattern`], but optionally allows a `_N` index after the language code.
+///
+/// The language code is always the first capture group, language codes never contain digits.
+fn create_filter_pattern_with_index(pattern: &str, case_insensitive: bool, indexed: bool) -> Option<Regex> {
+    if pattern.len() > MAX_FILTER_PATTERN_LEN {
+        return None;
+    }
+    let (prefix, suffix) = pattern.split_once("<lang>")?;
+    if suffix.contains("<lang>") {
+        return None;
+    }
+
+    let regex_pattern = format!(
+        r#"^{}([a-z_A-Z]{{2,6}}){}{}$"#,
+        regex::escape(prefix),
+        if indexed { r"(?:_[0-9]+)?" } else { "" },
+        regex::escape(suffix)
+    );
+
+    regex::RegexBuilder::new(&regex_pattern)
+        .case_insensitive(case_insensitive)
+        .size_limit(FILTER_PATTERN_SIZE_LIMIT)
+        .build()
+        .ok()
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::transifex::yaml_file::tests::{TEST_TX_YAML_CONTENT, create_test_project_dir, yaml_with_filters};
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn tst_match_target_files_with_skipped() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let project_root = create_test_project_dir("tst_match_target_files_with_skipped");
+        fs::write(project_root.join("app_zh_CN.ts"), "<TS/>").unwrap();
+        let non_utf8_file = project_root.join(OsStr::from_bytes(b"app_\xff\xfe.ts"));
+        fs::write(&non_utf8_file, "<TS/>").unwrap();
+        let tx_yaml = TransifexYaml::from_str(&yaml_with_filters(&[("app.ts", "app_<lang>.ts")])).unwrap();
+        let matches = tx_yaml.filters[0].match_target_files_with_skipped(&project_root).unwrap();
+        assert_eq!(matches.matched, vec![("zh_CN".to_string(), project_root.join("app_zh_CN.ts"))]);
+        assert_eq!(matches.skipped_non_utf8, vec![non_utf8_file]);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn tst_match_target_files_dangling_symlink() {
+        let project_root = create_test_project_dir("tst_match_target_files_dangling_symlink");
+        fs::write(project_root.join("app_zh_CN.ts"), "<TS/>").unwrap();
+        std::os::unix::fs::symlink(project_root.join("missing.ts"), project_root.join("app_ja.ts")).unwrap();
+        let tx_yaml = TransifexYaml::from_str(&yaml_with_filters(&[("app.ts", "app_<lang>.ts")])).unwrap();
+        let opts = MatchOptions { follow_symlinks: true, ..MatchOptions::default() };
+        let matched = tx_yaml.filters[0].match_target_files_with_options(&project_root, &opts).unwrap();
+        assert_eq!(matched, vec![("zh_CN".to_string(), project_root.join("app_zh_CN.ts"))]);
+        let matched = tx_yaml.filters[0].match_target_files_with_predicate(&project_root, |_| true).unwrap();
+        assert_eq!(matched.len(), 1);
+        let matches = tx_yaml.filters[0].match_target_files_with_skipped(&project_root).unwrap();
+        assert_eq!(matches.matched, vec![("zh_CN".to_string(), project_root.join("app_zh_CN.ts"))]);
+        let skipped: Vec<&PathBuf> = matches.skipped_unreadable.iter().map(|(path, _)| path).collect();
+        assert_eq!(skipped, vec![&project_root.join("app_ja.ts")]);
+    }
+
+    #[test]
+    fn tst_match_target_files_with_options() {
+        let project_root = create_test_project_dir("tst_match_target_files_with_options");
+        fs::write(project_root.join("App_zh_CN.ts"), "<TS/>").unwrap();
+        fs::write(project_root.join("app_zh_TW.ts"), "<TS/>").unwrap();
+        fs::write(project_root.join("app_ja.ts"), b"\x3c\x00\x01").unwrap();
+        let tx_yaml = TransifexYaml::from_str(&yaml_with_filters(&[("app.ts", "app_<lang>.ts")])).unwrap();
+        let filter = &tx_yaml.filters[0];
+        assert_eq!(filter.match_target_files(&project_root).unwrap().len(), 2);
+
+        let opts = toml::from_str::<MatchOptions>("case_insensitive = true\ntext_only = true\n").unwrap();
+        assert!(opts.follow_symlinks);
+        let mut matched = filter.match_target_files_with_options(&project_root, &opts).unwrap();
+        matched.sort();
+        assert_eq!(matched, vec![
+            ("zh_CN".to_string(), project_root.join("App_zh_CN.ts")),
+            ("zh_TW".to_string(), project_root.join("app_zh_TW.ts")),
+        ]);
+
+        let opts = MatchOptions { max_depth: Some(0), ..opts };
+        assert_eq!(filter.match_target_files_with_options(&project_root, &opts).unwrap().len(), 2);
+        let nested = TransifexYaml::from_str(&TEST_TX_YAML_CONTENT).unwrap();
+        assert!(nested.filters[0].match_target_files_with_options(&project_root, &opts).unwrap().is_empty());
+
+        let opts = MatchOptions { max_matches: Some(2), ..MatchOptions::default() };
+        assert_eq!(filter.match_target_files_with_options(&project_root, &opts).unwrap().len(), 2);
+        let opts = MatchOptions { max_matches: Some(1), ..MatchOptions::default() };
+        let err = filter.match_target_files_with_options(&project_root, &opts).unwrap_err();
+        assert_eq!(err.to_string(), "More than 1 files match \"app_<lang>.ts\"");
+    }
+
+    #[test]
+    fn tst_suspicious_matches() {
+        let project_root = create_test_project_dir("tst_suspicious_matches");
+        fs::write(project_root.join("app_zh_CN.ts"), "<TS/>").unwrap();
+        fs::write(project_root.join("app_backup.ts"), "<TS/>").unwrap();
+        let tx_yaml = TransifexYaml::from_str(&yaml_with_filters(&[("app.ts", "app_<lang>.ts")])).unwrap();
+        assert_eq!(tx_yaml.filters[0].suspicious_matches(&project_root).unwrap(), vec![("backup".to_string(), project_root.join("app_backup.ts"))]);
+    }
+
+    #[test]
+    fn tst_check_matched_encodings() {
+        let project_root = create_test_project_dir("tst_check_matched_encodings");
+        fs::write(project_root.join("app_zh_CN.ts"), "<TS/>").unwrap();
+        fs::write(project_root.join("app_ja.ts"), b"\xEF\xBB\xBF<TS/>").unwrap();
+        fs::write(project_root.join("app_de.ts"), b"<TS>\xFF</TS>").unwrap();
+        let tx_yaml = TransifexYaml::from_str(&yaml_with_filters(&[("app.ts", "app_<lang>.ts")])).unwrap();
+        let mut issues = tx_yaml.filters[0].check_matched_encodings(&project_root).unwrap();
+        issues.sort_by(|(a, _), (b, _)| a.cmp(b));
+        assert_eq!(issues, vec![
+            (project_root.join("app_de.ts"), EncodingIssue::InvalidUtf8),
+            (project_root.join("app_ja.ts"), EncodingIssue::Utf8Bom),
+        ]);
+        // a character cut off at the end of the sniffed prefix is fine
+        assert_eq!(EncodingIssue::from_prefix("中".as_bytes().split_last().unwrap().1), None);
+    }
+
+    #[test]
+    fn tst_excluded_target_dirs() {
+        let project_root = create_test_project_dir("tst_excluded_target_dirs");
+        fs::create_dir_all(project_root.join(".git")).unwrap();
+        fs::write(project_root.join(".git/app_zh_CN.ts"), "<TS/>").unwrap();
+        let mut tx_yaml = TransifexYaml::from_str(&yaml_with_filters(&[("app.ts", ".git/app_<lang>.ts")])).unwrap();
+        let filter = &tx_yaml.filters[0];
+        assert_eq!(filter.excluded_target_dir(), Some(".git"));
+        assert!(filter.match_target_files(&project_root).unwrap().is_empty());
+        assert!(filter.match_against(&project_root, &[PathBuf::from(".git/app_zh_CN.ts")]).is_empty());
+        let diagnostics = tx_yaml.validate();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "translations \".git/app_<lang>.ts\" are inside of the excluded directory \".git\", nothing is matched");
+
+        tx_yaml.filters[0].target_pattern = "translations/target/app_<lang>.ts".to_string();
+        assert_eq!(tx_yaml.filters[0].excluded_target_dir(), Some("target"));
+        tx_yaml.filters[0].target_pattern = "translations/app_<lang>.ts".to_string();
+        assert_eq!(tx_yaml.filters[0].excluded_target_dir(), None);
+    }
+
+    #[test]
+    fn tst_match_against() {
+        let project_root = PathBuf::from("/nonexistent/project");
+        let tx_yaml = TransifexYaml::from_str(&yaml_with_filters(&[("translations/app.ts", "translations/app_<lang>.ts")])).unwrap();
+        let candidates = [
+            PathBuf::from("translations/app_zh_CN.ts"),
+            PathBuf::from("translations/app.ts"),
+            PathBuf::from("translations/nested/app_de.ts"),
+            PathBuf::from("app_fr.ts"),
+            project_root.join("translations/app_ja.ts"),
+        ];
+        assert_eq!(tx_yaml.filters[0].match_against(&project_root, &candidates), vec![
+            ("zh_CN".to_string(), project_root.join("translations/app_zh_CN.ts")),
+            ("ja".to_string(), project_root.join("translations/app_ja.ts")),
+        ]);
+
+        let candidates = [PathBuf::from("translations/App_CN_zh.ts"), PathBuf::from("translations/app_de.ts")];
+        assert_eq!(tx_yaml.filters[0].match_against(&project_root, &candidates), vec![
+            ("de".to_string(), project_root.join("translations/app_de.ts")),
+        ]);
+        let opts = MatchOptions { case_insensitive: true, locale_order: LocaleComponentOrder::RegionFirst, ..MatchOptions::default() };
+        assert_eq!(tx_yaml.filters[0].match_against_with_options(&project_root, &candidates, &opts), vec![
+            ("zh_CN".to_string(), project_root.join("translations/App_CN_zh.ts")),
+            ("de".to_string(), project_root.join("translations/app_de.ts")),
+        ]);
+        let opts = MatchOptions { max_depth: Some(0), ..MatchOptions::default() };
+        assert!(tx_yaml.filters[0].match_against_with_options(&project_root, &candidates, &opts).is_empty());
+    }
+
+    #[test]
+    fn tst_match_compiled_qm_files() {
+        let project_root = create_test_project_dir("tst_match_compiled_qm_files");
+        for file_name in ["app_zh_CN.ts", "app_zh_CN.qm", "app_ja.ts"] {
+            fs::write(project_root.join(file_name), "<TS/>").unwrap();
+        }
+        let tx_yaml = TransifexYaml::from_str(&yaml_with_filters(&[("app.ts", "app_<lang>.ts")])).unwrap();
+        assert_eq!(tx_yaml.filters[0].qm_target_pattern().as_deref(), Some("app_<lang>.qm"));
+        assert_eq!(tx_yaml.filters[0].match_compiled_qm_files(&project_root).unwrap(), QmCoverage {
+            compiled: vec![("zh_CN".to_string(), project_root.join("app_zh_CN.qm"))],
+            missing: vec!["ja".to_string()],
+        });
+    }
+
+    #[test]
+    fn tst_match_target_files_sorted_by_name() {
+        let project_root = create_test_project_dir("tst_match_target_files_sorted_by_name");
+        for lang_code in ["de", "fr", "zh_CN", "zh_TW"] {
+            fs::write(project_root.join(format!("app_{lang_code}.ts")), "<TS/>").unwrap();
+        }
+        let tx_yaml = TransifexYaml::from_str(&yaml_with_filters(&[("app.ts", "app_<lang>.ts")])).unwrap();
+        let lang_codes = |opts: &MatchOptions| tx_yaml.filters[0].match_target_files_with_options(&project_root, opts).unwrap()
+            .into_iter().map(|(lang_code, _)| lang_code).collect::<Vec<String>>();
+        assert_eq!(lang_codes(&MatchOptions::default()), vec!["de", "fr", "zh_CN", "zh_TW"]);
+        assert_eq!(lang_codes(&MatchOptions { sort_locales_by_name: true, ..MatchOptions::default() }), vec!["zh_CN", "zh_TW", "fr", "de"]);
+    }
+
+    #[test]
+    fn tst_match_region_first_locale() {
+        let project_root = create_test_project_dir("tst_match_region_first_locale");
+        for file_name in ["CN_zh_app.ts", "de_app.ts"] {
+            fs::write(project_root.join(file_name), "<TS/>").unwrap();
+        }
+        let tx_yaml = TransifexYaml::from_str(&yaml_with_filters(&[("app.ts", "<lang>_app.ts")])).unwrap();
+        let lang_codes = |opts: &MatchOptions| tx_yaml.filters[0].match_target_files_with_options(&project_root, opts).unwrap()
+            .into_iter().map(|(lang_code, _)| lang_code).collect::<Vec<String>>();
+        assert_eq!(lang_codes(&MatchOptions::default()), vec!["CN_zh", "de"]);
+        let opts = MatchOptions { locale_order: LocaleComponentOrder::RegionFirst, ..MatchOptions::default() };
+        assert_eq!(lang_codes(&opts), vec!["zh_CN", "de"]);
+    }
+
+    #[test]
+    fn tst_match_uppercase_locale() {
+        let project_root = create_test_project_dir("tst_match_uppercase_locale");
+        fs::write(project_root.join("app_PT_BR.ts"), "<TS/>").unwrap();
+        let tx_yaml = TransifexYaml::from_str(&yaml_with_filters(&[("app.ts", "app_<lang>.ts")])).unwrap();
+        assert_eq!(tx_yaml.filters[0].match_target_files(&project_root).unwrap(), vec![("pt_BR".to_string(), project_root.join("app_PT_BR.ts"))]);
+        assert_eq!(match_lang_code("app_<lang>.ts", "app_PT_BR.ts", false), Some("pt_BR".to_string()));
+    }
+
+    #[test]
+    fn tst_match_indexed_target_files() {
+        let project_root = create_test_project_dir("tst_match_indexed_target_files");
+        for file_name in ["app_zh_CN_2.ts", "app_zh_CN_1.ts", "app_ja.ts", "app_zh_CN_x.ts"] {
+            fs::write(project_root.join(file_name), "<TS/>").unwrap();
+        }
+        let tx_yaml = TransifexYaml::from_str(&yaml_with_filters(&[("app.ts", "app_<lang>.ts")])).unwrap();
+        let filter = &tx_yaml.filters[0];
+        // indexed parts are not matched by default
+        assert_eq!(filter.match_target_files(&project_root).unwrap(), vec![("ja".to_string(), project_root.join("app_ja.ts"))]);
+        assert_eq!(filter.match_indexed_target_files(&project_root, &MatchOptions::default()).unwrap(), vec![
+            ("ja".to_string(), vec![project_root.join("app_ja.ts")]),
+            ("zh_CN".to_string(), vec![project_root.join("app_zh_CN_1.ts"), project_root.join("app_zh_CN_2.ts")]),
+        ]);
+    }
+
+    #[test]
+    fn tst_match_target_files_detailed() {
+        let project_root = create_test_project_dir("tst_match_target_files_detailed");
+        fs::write(project_root.join("app_zh_CN.ts"), "<TS/>").unwrap();
+        let tx_yaml = TransifexYaml::from_str(&yaml_with_filters(&[("app.ts", "app_<lang>.ts")])).unwrap();
+        let matches = tx_yaml.filters[0].match_target_files_detailed(&project_root).unwrap();
+        assert!(matches.skipped.is_empty());
+        let matched = matches.matched;
+        assert_eq!(matched.len(), 1);
+        assert_eq!(matched[0].lang_code, "zh_CN");
+        assert_eq!(matched[0].size, 5);
+        assert_eq!(matched[0].modified, fs::metadata(project_root.join("app_zh_CN.ts")).unwrap().modified().unwrap());
+
+        let nested = TransifexYaml::from_str(TEST_TX_YAML_CONTENT).unwrap();
+        assert!(nested.filters[0].match_target_files_detailed(&project_root).unwrap().matched.is_empty());
+    }
+
+    #[test]
+    fn tst_write_matches_jsonl() {
+        let project_root = create_test_project_dir("tst_write_matches_jsonl");
+        fs::write(project_root.join("app_zh_CN.ts"), "<TS/>").unwrap();
+        fs::write(project_root.join("app_zh_TW.ts"), "<TS/>").unwrap();
+        let tx_yaml = TransifexYaml::from_str(&yaml_with_filters(&[("app.ts", "app_<lang>.ts")])).unwrap();
+        let mut output = Vec::<u8>::new();
+        let written = write_matches_jsonl(&mut output, tx_yaml.filters[0].iter_target_files(&project_root)).unwrap();
+        assert_eq!(written, 2);
+        let lines = String::from_utf8(output).unwrap();
+        let values: Vec<serde_json::Value> = lines.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(values[0]["lang"], "zh_CN");
+        assert_eq!(values[0]["path"], project_root.join("app_zh_CN.ts").to_string_lossy().as_ref());
+        assert_eq!(values[1]["lang"], "zh_TW");
+    }
+
+    #[test]
+    fn tst_iter_target_files() {
+        let project_root = create_test_project_dir("tst_iter_target_files");
+        fs::write(project_root.join("app_zh_CN.ts"), "<TS/>").unwrap();
+        fs::write(project_root.join("app_zh_TW.ts"), "<TS/>").unwrap();
+        // the first filter points to a missing directory
+        let yaml_content = r#"filters:
+  - filter_type: file
+    source_file: missing/app.ts
+    file_format: QT
+    source_language: en_US
+    translation_files_expression: missing/app_<lang>.ts
+  - filter_type: file
+    source_file: app.ts
+    file_format: QT
+    source_language: en_US
+    translation_files_expression: app_<lang>.ts
+settings:
+  pr_branch_name: transifex_update_<br_unique_id>
+"#;
+        let tx_yaml = TransifexYaml::from_str(yaml_content).unwrap();
+        assert_eq!(tx_yaml.filters.len(), 2);
+
+        // collecting stops at the first error
+        assert!(tx_yaml.iter_target_files(&project_root).collect::<Result<Vec<_>, _>>().is_err());
+        assert!(tx_yaml.filters[0].iter_target_files(&project_root).next().unwrap().is_err());
+        assert!(tx_yaml.filters[0].iter_target_files(&project_root).nth(1).is_none());
+
+        // callers can skip errors and stop early
+        let mut iter = tx_yaml.iter_target_files(&project_root);
+        assert!(iter.next().unwrap().is_err());
+        let (filter, matched) = iter.next().unwrap().unwrap();
+        assert_eq!(filter.target_pattern, "app_<lang>.ts");
+        assert!(matched.lang_code == "zh_CN" || matched.lang_code == "zh_TW");
+        assert_eq!(tx_yaml.filters[1].iter_target_files(&project_root).take(1).count(), 1);
+        assert_eq!(tx_yaml.filters[1].match_target_files(&project_root).unwrap().len(), 2);
+    }
+
+    /// Run git in the given directory, returns whether it succeeded.
+    fn run_git(repo_root: &Path, args: &[&str]) -> bool {
+        std::process::Command::new("git")
+            .arg("-C").arg(repo_root)
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com", "-c", "commit.gpgsign=false"])
+            .args(args)
+            .output()
+            .is_ok_and(|output| output.status.success())
+    }
+
+    #[test]
+    fn tst_match_target_files_at_rev() {
+        let project_root = create_test_project_dir("tst_match_target_files_at_rev");
+        // the fixture repository can only be created if git is available
+        if !run_git(&project_root, &["init", "-q"]) {
+            return;
+        }
+        fs::create_dir_all(project_root.join("translations")).unwrap();
+        fs::write(project_root.join("translations/app.ts"), "<TS/>").unwrap();
+        fs::write(project_root.join("translations/app_zh_CN.ts"), "<TS/>").unwrap();
+        assert!(run_git(&project_root, &["add", "-A"]));
+        assert!(run_git(&project_root, &["commit", "-q", "-m", "Add zh_CN"]));
+        fs::remove_file(project_root.join("translations/app_zh_CN.ts")).unwrap();
+        fs::write(project_root.join("translations/app_zh_TW.ts"), "<TS/>").unwrap();
+        fs::create_dir_all(project_root.join("target")).unwrap();
+        fs::write(project_root.join("target/app_de.ts"), "<TS/>").unwrap();
+        assert!(run_git(&project_root, &["add", "-A"]));
+        assert!(run_git(&project_root, &["commit", "-q", "-m", "Replace zh_CN with zh_TW"]));
+
+        let tx_yaml = TransifexYaml::from_str(&yaml_with_filters(&[
+            ("translations/app.ts", "translations/app_<lang>.ts"),
+            ("target/app.ts", "target/app_<lang>.ts"),
+            ("translations/app.ts", "translations\\app_<lang>.ts"),
+        ])).unwrap();
+        // committed files in excluded directories are not matched either
+        assert!(tx_yaml.filters[1].match_target_files_at_rev(&project_root, "HEAD").unwrap().is_empty());
+        assert_eq!(tx_yaml.filters[2].match_target_files_at_rev(&project_root, "HEAD").unwrap(), vec![
+            ("zh_TW".to_string(), PathBuf::from("translations/app_zh_TW.ts")),
+        ]);
+        let filter = &tx_yaml.filters[0];
+        assert_eq!(filter.match_target_files_at_rev(&project_root, "HEAD~1").unwrap(), vec![
+            ("zh_CN".to_string(), PathBuf::from("translations/app_zh_CN.ts")),
+        ]);
+        assert_eq!(filter.match_target_files_at_rev(&project_root, "HEAD").unwrap(), vec![
+            ("zh_TW".to_string(), PathBuf::from("translations/app_zh_TW.ts")),
+        ]);
+        assert!(filter.match_target_files_at_rev(&project_root, "no-such-rev").is_err());
+        assert_eq!(filter.match_target_files_at_rev(&project_root, "--output=x").unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn tst_matched_locales() {
+        let project_root = create_test_project_dir("tst_matched_locales");
+        let tx_yaml = TransifexYaml::from_str(&yaml_with_filters(&[("translations/app.ts", "translations/app_<lang>.ts")])).unwrap();
+        let filter = &tx_yaml.filters[0];
+        // the target directory doesn't exist yet.
+        assert!(filter.matched_locales(&project_root).unwrap().is_empty());
+        assert!(filter.matched_files(&project_root).unwrap().is_empty());
+
+        fs::create_dir_all(project_root.join("translations")).unwrap();
+        fs::write(project_root.join("translations/app_zh_CN.ts"), "<TS/>").unwrap();
+        fs::write(project_root.join("translations/app_ja.ts"), "<TS/>").unwrap();
+        assert_eq!(filter.matched_locales(&project_root).unwrap(), BTreeSet::from(["ja".to_string(), "zh_CN".to_string()]));
+    }
+
+    #[test]
+    fn tst_compile_lang_regex() {
+        let regex = compile_lang_regex(r"^app-(?:v[0-9]+-)?([a-zA-Z_]+)\.ts$").unwrap();
+        assert_eq!(regex.captures("app-v2-zh_CN.ts").and_then(|caps| caps.get(1)).map(|lang_code| lang_code.as_str()), Some("zh_CN"));
+        assert!(matches!(compile_lang_regex(r"^(app)-([a-zA-Z_]+)\.ts$"), Err(LangRegexError::CaptureGroups { found: 2, .. })));
+        assert!(matches!(compile_lang_regex(r"^app-[a-zA-Z_]+\.ts$"), Err(LangRegexError::CaptureGroups { found: 0, .. })));
+        assert!(matches!(compile_lang_regex(r"^app-([a-z"), Err(LangRegexError::Invalid(..))));
+    }
+
+    #[test]
+    fn tst_match_lang_code() {
+        assert_eq!(match_lang_code("app_<lang>.ts", "app_zh_CN.ts", false), Some("zh_CN".to_string()));
+        assert_eq!(match_lang_code("app_<lang>.ts", "App_zh_CN.TS", true), Some("zh_CN".to_string()));
+        assert_eq!(match_lang_code("app_<lang>.ts", "app.ts", false), None);
+        assert_eq!(match_lang_code("app_<lang>_<lang>.ts", "app_zh_CN_zh_CN.ts", false), None);
+        assert_eq!(match_lang_code("(.*)+$[<lang>", "(.*)+$[de", false), Some("de".to_string()));
+        let long_pattern = format!("{}_<lang>.ts", "a".repeat(MAX_FILTER_PATTERN_LEN));
+        assert_eq!(match_lang_code(&long_pattern, &long_pattern.replace("<lang>", "de"), false), None);
+    }
+
+    #[test]
+    fn test_pathbuf() {
+        let path = PathBuf::from("/example/sample_<lang>.ts");
+        assert_eq!(path.file_name(), Some(std::ffi::OsStr::new("sample_<lang>.ts")));
+        let pattern = create_filter_pattern(path.to_str().unwrap(), false).unwrap();
+        let matched = pattern.captures("/example/sample_zh_CN.ts").and_then(|caps| caps.get(1)).map(|m| {
+            m.as_str().to_string()
+        });
+        assert_eq!(matched, Some("zh_CN".to_string()));
+    }
+
+    #[test]
+    fn tst_match_target_files_with_predicate() {
+        let project_root = create_test_project_dir("tst_match_target_files_with_predicate");
+        fs::write(project_root.join("app_zh_CN.ts"), "<TS/>").unwrap();
+        fs::write(project_root.join("app_zh_TW.ts"), "<TS>too large</TS>").unwrap();
+        let tx_yaml = TransifexYaml::from_str(&yaml_with_filters(&[("app.ts", "app_<lang>.ts")])).unwrap();
+        let filter = &tx_yaml.filters[0];
+        assert_eq!(filter.match_target_files(&project_root).unwrap().len(), 2);
+        let matched = filter.match_target_files_with_predicate(&project_root, |entry| {
+            entry.metadata().map(|metadata| metadata.len() <= 8).unwrap_or(false)
+        }).unwrap();
+        assert_eq!(matched, vec![("zh_CN".to_string(), project_root.join("app_zh_CN.ts"))]);
+    }
+}
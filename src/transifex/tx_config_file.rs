@@ -5,58 +5,89 @@
 // .transifexrc content: https://github.com/transifex/cli/blob/devel/examples/exampleconf/.transifexrc
 // .tx/config file spec: https://developers.transifex.com/docs/using-the-client
 
-use std::{fs, path::PathBuf};
+use std::{collections::BTreeMap, fs, path::{Path, PathBuf}};
 use configparser::ini::{Ini, WriteOptions};
 use thiserror::Error as TeError;
+use crate::error::{ContextError, ResultExt};
 use super::yaml_file::{self, TransifexYaml};
 
+/// Error kind of loading `.tx/config` or `.transifexrc` files.
 #[derive(TeError, Debug)]
-pub enum TxConfigLoadError {
+pub enum TxConfigLoadErrorKind {
+    /// The file doesn't exist.
     #[error("File not found")]
     FileNotFound,
-    #[error("Can not read file")]
-    ReadFile(#[from] std::io::Error),
-    #[error("Fail to deserialize file: {0}")]
+    /// The file or reader can't be read.
+    #[error("Can not read content because: {0}")]
+    Read(#[source] std::io::Error),
+    /// The content is malformed.
+    #[error("Fail to deserialize content: {0}")]
     ParseError(String),
 }
 
+/// Error of loading `.tx/config` or `.transifexrc` files.
+pub type TxConfigLoadError = ContextError<TxConfigLoadErrorKind>;
+
+fn parse_error(message: impl Into<String>) -> TxConfigLoadError {
+    TxConfigLoadErrorKind::ParseError(message.into()).into()
+}
+
+/// Get a boolean key, `None` if it's absent. Values other than `true` or `false` are errors.
+fn get_flag(config: &Ini, section: &str, key: &str) -> Result<Option<bool>, TxConfigLoadError> {
+    config.getbool(section, key)
+        .map_err(|e| parse_error(format!("invalid {key} key of section {section}: {e}")))
+}
+
+/// A host section of `.transifexrc`.
 #[derive(Default)]
 pub struct TransifexRcSection {
+    /// Section name, i.e. the host.
     pub host_section: String,
+    /// Host name of the REST API.
     pub rest_hostname: String,
+    /// API token.
     pub token: String,
 }
 
+/// Load `project_root/.tx/config`, and return its path and content.
 pub fn try_laod_tx_config_file(project_root: &PathBuf) -> Result<(PathBuf, TxConfig), TxConfigLoadError> {
     let tx_config_file = project_root.join(".tx").join("config");
     if tx_config_file.is_file() {
         let tx_config = load_tx_config_file(&tx_config_file)?;
         return Ok((tx_config_file, tx_config));
     }
-    Err(TxConfigLoadError::FileNotFound)
+    Err(TxConfigLoadError::new(TxConfigLoadErrorKind::FileNotFound).with_path(tx_config_file))
 }
 
-pub fn load_transifexrc_file(transifexrc_file: &PathBuf) -> Result<TransifexRcSection, TxConfigLoadError> {
-    if !transifexrc_file.is_file() {
-        return Err(TxConfigLoadError::FileNotFound);
+fn read_config_file(config_file: &Path) -> Result<String, TxConfigLoadError> {
+    if !config_file.is_file() {
+        return Err(TxConfigLoadError::new(TxConfigLoadErrorKind::FileNotFound).with_path(config_file));
     }
-    let source_content = fs::read_to_string(&transifexrc_file)?;
-    TransifexRcSection::from_str(&source_content)
+    fs::read_to_string(config_file)
+        .map_err(TxConfigLoadErrorKind::Read)
+        .map_err(TxConfigLoadError::in_file(config_file))
+}
+
+/// Load the given `.transifexrc` file.
+pub fn load_transifexrc_file(transifexrc_file: &PathBuf) -> Result<TransifexRcSection, TxConfigLoadError> {
+    let source_content = read_config_file(transifexrc_file)?;
+    TransifexRcSection::from_str(&source_content).with_path(transifexrc_file)
 }
 
 impl TransifexRcSection {
+    /// Parse `.transifexrc` content.
     pub fn from_str(content: &str) -> Result<Self, TxConfigLoadError> {
         let mut config = Ini::new();
         config.read(content.to_string())
-          .map_err(|err| TxConfigLoadError::ParseError(err.to_string()))?;
+          .map_err(parse_error)?;
 
         let mut tx_section = TransifexRcSection::default();
 
         let sections = config.sections();
         for section in sections {
             tx_section.host_section = section.to_string();
-            tx_section.rest_hostname = config.get(&section, "rest_hostname").ok_or(TxConfigLoadError::ParseError("missing rest_hostname key".to_string()))?;
-            tx_section.token = config.get(&section, "token").ok_or(TxConfigLoadError::ParseError("missing token key".to_string()))?;
+            tx_section.rest_hostname = config.get(&section, "rest_hostname").ok_or_else(|| parse_error("missing rest_hostname key"))?;
+            tx_section.token = config.get(&section, "token").ok_or_else(|| parse_error("missing token key"))?;
 
             break;
         };
@@ -64,29 +95,45 @@ impl TransifexRcSection {
     }
 }
 
+/// Content of a `.tx/config` file.
 #[derive(Default)]
 pub struct TxConfig {
+    /// The `[main]` section.
     pub main_section: TxConfigSectionMain,
+    /// Resource sections, in file order.
     pub resource_sections: Vec<TxConfigSectionResource>,
 }
 
+/// Load the given `.tx/config` file.
 pub fn load_tx_config_file(tx_config_file: &PathBuf) -> Result<TxConfig, TxConfigLoadError> {
-    if !tx_config_file.is_file() {
-        return Err(TxConfigLoadError::FileNotFound);
-    }
-    let source_content = fs::read_to_string(&tx_config_file)?;
-    TxConfig::from_str(&source_content)
+    let source_content = read_config_file(tx_config_file)?;
+    TxConfig::from_str(&source_content).with_path(tx_config_file)
 }
 
 impl TxConfig {
+    /// Parse `.tx/config` content.
     pub fn from_str(content: &str) -> Result<Self, TxConfigLoadError> {
         let mut config = Ini::new();
         config.read(content.to_string())
-            .map_err(|err| TxConfigLoadError::ParseError(err.to_string()))?;
+            .map_err(parse_error)?;
         let mut main_section = TxConfigSectionMain::default();
-        main_section.host = config.get("main", "host").unwrap_or("https://www.transifex.com".to_string());
+        main_section.host = config.get("main", "host").map_or("https://www.transifex.com".to_string(), |host| normalize_host(&host));
+        main_section.lang_map = config.get("main", "lang_map");
         main_section.minimum_prec = config.getint("main", "minimum_perc").unwrap_or(None);
         main_section.mode = config.get("main", "mode");
+        for key in config.get_map_ref().get("main").map(|keys| keys.keys()).into_iter().flatten() {
+            if MAIN_SECTION_KEYS.contains(&key.as_str()) {
+                continue;
+            }
+            match config.getbool("main", key) {
+                Ok(Some(flag)) => {
+                    main_section.flags.insert(key.clone(), flag);
+                },
+                _ => {
+                    main_section.extra_keys.insert(key.clone(), config.get("main", key).unwrap_or_default());
+                },
+            }
+        }
 
         let mut tx_config = TxConfig {
             main_section,
@@ -100,33 +147,66 @@ impl TxConfig {
             }
             let resource_section = TxConfigSectionResource {
                 resource_full_slug: section.to_string(),
-                file_filter: config.get(&section, "file_filter").ok_or(TxConfigLoadError::ParseError("missing file_filter key".to_string()))?,
+                file_filter: config.get(&section, "file_filter").ok_or_else(|| parse_error("missing file_filter key"))?,
+                keep_translations: get_flag(&config, &section, "keep_translations")?,
+                lang_map: config.get(&section, "lang_map"),
                 minimum_prec: config.getint(&section, "minimum_perc").unwrap_or(None),
-                source_file: config.get(&section, "source_file").ok_or(TxConfigLoadError::ParseError("missing source_file key".to_string()))?,
-                source_lang: config.get(&section, "source_lang").ok_or(TxConfigLoadError::ParseError("missing source_lang key".to_string()))?,
-                type_attr: config.get(&section, "type").ok_or(TxConfigLoadError::ParseError("missing type key".to_string()))?,
+                replace_edited_strings: get_flag(&config, &section, "replace_edited_strings")?,
+                resource_name: config.get(&section, "resource_name"),
+                source_file: config.get(&section, "source_file").ok_or_else(|| parse_error("missing source_file key"))?,
+                source_lang: config.get(&section, "source_lang").ok_or_else(|| parse_error("missing source_lang key"))?,
+                type_attr: config.get(&section, "type").ok_or_else(|| parse_error("missing type key"))?,
             };
             tx_config.resource_sections.push(resource_section);
         };
         Ok(tx_config)
     }
 
+    /// Parse `.tx/config` content from the given reader.
+    pub fn from_reader(mut reader: impl std::io::Read) -> Result<Self, TxConfigLoadError> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content).map_err(TxConfigLoadErrorKind::Read)?;
+        Self::from_str(&content)
+    }
+
+    /// Serialize to `.tx/config` content.
     pub fn to_str(&self) -> String {
         let mut config = Ini::new();
-        config.setstr("main", "host", Some(&self.main_section.host));
+        config.setstr("main", "host", Some(&normalize_host(&self.main_section.host)));
+        if let Some(lang_map) = &self.main_section.lang_map {
+            config.setstr("main", "lang_map", Some(&lang_map));
+        }
         if let Some(minimum_prec) = self.main_section.minimum_prec {
             config.setstr("main", "minimum_perc", Some(&minimum_prec.to_string()));
-        };
+        }
         if let Some(mode) = &self.main_section.mode {
             config.setstr("main", "mode", Some(&mode));
-        };
+        }
+        for (key, flag) in &self.main_section.flags {
+            config.setstr("main", key, Some(&flag.to_string()));
+        }
+        for (key, value) in &self.main_section.extra_keys {
+            config.setstr("main", key, Some(value));
+        }
 
         for resource_section in &self.resource_sections {
-            config.setstr(&resource_section.resource_full_slug, "file_filter", Some(&resource_section.file_filter));
+            config.setstr(&resource_section.resource_full_slug, "file_filter", Some(&to_forward_slashes(&resource_section.file_filter)));
+            if let Some(keep_translations) = resource_section.keep_translations {
+                config.setstr(&resource_section.resource_full_slug, "keep_translations", Some(&keep_translations.to_string()));
+            }
+            if let Some(lang_map) = &resource_section.lang_map {
+                config.setstr(&resource_section.resource_full_slug, "lang_map", Some(&lang_map));
+            }
             if let Some(minimum_prec) = resource_section.minimum_prec {
                 config.setstr(&resource_section.resource_full_slug, "minimum_perc", Some(&minimum_prec.to_string()));
-            };
-            config.setstr(&resource_section.resource_full_slug, "source_file", Some(&resource_section.source_file));
+            }
+            if let Some(replace_edited_strings) = resource_section.replace_edited_strings {
+                config.setstr(&resource_section.resource_full_slug, "replace_edited_strings", Some(&replace_edited_strings.to_string()));
+            }
+            if let Some(resource_name) = &resource_section.resource_name {
+                config.setstr(&resource_section.resource_full_slug, "resource_name", Some(resource_name));
+            }
+            config.setstr(&resource_section.resource_full_slug, "source_file", Some(&to_forward_slashes(&resource_section.source_file)));
             config.setstr(&resource_section.resource_full_slug, "source_lang", Some(&resource_section.source_lang));
             config.setstr(&resource_section.resource_full_slug, "type", Some(&resource_section.type_attr));
         }
@@ -137,51 +217,231 @@ impl TxConfig {
         config.pretty_writes(&write_options)
     }
 
+    /// Convert to the equivalent `transifex.yaml`.
     pub fn to_transifex_yaml(&self) -> TransifexYaml {
         let mut filters = Vec::<yaml_file::Filter>::new();
         for resource_section in &self.resource_sections {
             let filter = yaml_file::Filter {
                 type_attr: "file".to_string(),
                 source: resource_section.source_file.clone(),
-                format: resource_section.type_attr.clone(),
+                format: yaml_file::FileFormat::from_tx_type(&resource_section.type_attr).to_tx_type(),
                 source_lang: resource_section.source_lang.clone(),
                 target_pattern: resource_section.file_filter.clone(),
+                resource_slug: None,
+                resource_name: resource_section.resource_name.clone(),
             };
             filters.push(filter);
         };
         TransifexYaml {
             filters,
             settings: yaml_file::Settings {
-                branch_template: "transifex_update_<br_unique_id>".to_string()
+                branch_template: yaml_file::DEFAULT_BRANCH_TEMPLATE.to_string(),
+                lang_map: self.main_section.lang_map.as_deref().map(parse_lang_map),
+                default_format: None,
+                primary_language: None,
             }
         }
     }
+
+    /// Cross-check that the source file of every resource section is the source of a filter of `yaml`,
+    /// i.e. that this config was converted from it and not edited since.
+    ///
+    /// Paths are compared with [`yaml_file::normalize_source_path`]. Returns a message for every
+    /// inconsistent resource section otherwise.
+    pub fn verify_source_consistency(&self, yaml: &TransifexYaml) -> Result<(), Vec<String>> {
+        let sources = yaml.filters.iter()
+            .map(|filter| yaml_file::normalize_source_path(&filter.source))
+            .collect::<std::collections::BTreeSet<String>>();
+        let errors = self.resource_sections.iter()
+            .filter(|section| !sources.contains(&yaml_file::normalize_source_path(&section.source_file)))
+            .map(|section| format!("source file {:?} of resource {} is not the source of any filter", section.source_file, section.resource_full_slug))
+            .collect::<Vec<String>>();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Full slugs of resource sections which converting `yaml` would no longer produce, e.g. left
+    /// behind by removed filters, in section order.
+    ///
+    /// The sections of this config act as the lookup table of the conversion, so a section is kept
+    /// if a filter of `yaml` resolves to its slug, see [`TransifexYaml::to_tx_config`].
+    pub fn sections_not_in_yaml(&self, yaml: &TransifexYaml, github_repository: &str) -> Vec<String> {
+        let lookup_table = self.resource_sections.iter().map(|section| yaml_file::TxResourceLookupEntry {
+            repository: github_repository.to_string(),
+            branch: String::new(),
+            resource: section.source_file.clone(),
+            transifex_resource_id: section.resource_full_slug.clone(),
+        }).collect::<Vec<yaml_file::TxResourceLookupEntry>>();
+        let produced = yaml.resource_slugs(github_repository, &lookup_table, true).into_iter()
+            .collect::<std::collections::BTreeSet<String>>();
+        self.resource_sections.iter()
+            .filter(|section| !produced.contains(&section.resource_full_slug))
+            .map(|section| section.resource_full_slug.clone())
+            .collect()
+    }
+
+    /// Apply the given closure to every resource section, e.g. to tweak a generated config.
+    pub fn transform(&mut self, f: impl FnMut(&mut TxConfigSectionResource)) {
+        self.resource_sections.iter_mut().for_each(f);
+    }
+
+    /// Apply the given closure to the main section, see [`TxConfig::transform`].
+    pub fn transform_main(&mut self, f: impl FnOnce(&mut TxConfigSectionMain)) {
+        f(&mut self.main_section);
+    }
+
+    /// Sort resource sections the way the Transifex CLI writes them, i.e. by full slug compared
+    /// case-insensitively, so files rewritten by it don't churn.
+    ///
+    /// Slugs differing only in case keep a bytewise order, so the result doesn't depend on the
+    /// original order.
+    pub fn sort_like_transifex(&mut self) {
+        self.resource_sections.sort_by_cached_key(|section| (section.resource_full_slug.to_lowercase(), section.resource_full_slug.clone()));
+    }
+
+    /// Get the effective `lang_map` of the resource with the given source file,
+    /// that is the main section's one overridden by the resource section's one.
+    ///
+    /// Maps Transifex language codes to local language codes.
+    pub fn lang_map_for(&self, source_file: &str) -> BTreeMap<String, String> {
+        let mut lang_map = self.main_section.lang_map.as_deref().map(parse_lang_map).unwrap_or_default();
+        if let Some(resource_lang_map) = self.resource_sections.iter()
+            .find(|section| section.source_file == source_file)
+            .and_then(|section| section.lang_map.as_deref()) {
+            lang_map.extend(parse_lang_map(resource_lang_map));
+        }
+        lang_map
+    }
+}
+
+/// Strip trailing slashes of a `host` value, e.g. `https://app.transifex.com/` becomes `https://app.transifex.com`.
+///
+/// Transifex is picky about the trailing slash, so hosts are stored and written without it.
+pub fn normalize_host(host: &str) -> String {
+    host.trim().trim_end_matches('/').to_string()
 }
 
+/// Replace backslashes of a path with forward slashes, e.g. `translations\app_<lang>.ts` becomes `translations/app_<lang>.ts`.
+///
+/// Transifex CLI always expects forward slashes, even on Windows.
+pub fn to_forward_slashes(path: &str) -> String {
+    path.replace('\\', "/")
+}
+
+/// Whether the raw `host` of the `[main]` section of the given `.tx/config` content has a trailing slash,
+/// which [`TxConfig::from_str`] silently strips.
+pub fn main_host_has_trailing_slash(content: &str) -> bool {
+    let mut config = Ini::new();
+    config.read(content.to_string()).ok()
+        .and_then(|_| config.get("main", "host"))
+        .is_some_and(|host| host.trim().ends_with('/'))
+}
+
+/// Parse a `lang_map` value like `pt_BR: pt-br, zh_CN: zh-Hans`.
+///
+/// Returned map maps Transifex language codes to local language codes. Malformed entries are ignored.
+pub fn parse_lang_map(lang_map: &str) -> BTreeMap<String, String> {
+    lang_map.split(',')
+        .filter_map(|entry| entry.split_once(':'))
+        .map(|(remote, local)| (remote.trim().to_string(), local.trim().to_string()))
+        .filter(|(remote, local)| !remote.is_empty() && !local.is_empty())
+        .collect()
+}
+
+/// Format a language map into the `lang_map` value format, see [`parse_lang_map`].
+pub fn format_lang_map(lang_map: &BTreeMap<String, String>) -> String {
+    lang_map.iter()
+        .map(|(remote, local)| format!("{remote}: {local}"))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// Keys of the `[main]` section with a dedicated field in [`TxConfigSectionMain`].
+const MAIN_SECTION_KEYS: &[&str] = &["host", "lang_map", "minimum_perc", "mode"];
+
+/// The `[main]` section of `.tx/config`.
 #[derive(Default)]
 pub struct TxConfigSectionMain {
+    /// Transifex host, e.g. `https://www.transifex.com`.
     pub host: String,
+    /// Raw `lang_map` value, see [`parse_lang_map`].
+    pub lang_map: Option<String>,
+    /// Raw `minimum_perc` value.
     pub minimum_prec: Option<i64>,
+    /// Raw `mode` value.
     pub mode: Option<String>,
+    /// Other boolean flags the Transifex CLI reads from `[main]`, e.g. for the `--use-git-timestamps`
+    /// workflow. Written after the keys above, in sorted order.
+    pub flags: BTreeMap<String, bool>,
+    /// Any other keys of `[main]`, with their raw values, so writing back a loaded config keeps
+    /// them. Written after [`Self::flags`], in sorted order.
+    pub extra_keys: BTreeMap<String, String>,
 }
 
+/// A resource section of `.tx/config`.
 #[derive(Default)]
 pub struct TxConfigSectionResource {
+    /// Section name, i.e. the full resource slug `o:org:p:proj:r:res`.
     pub resource_full_slug: String,
+    /// Translation file path pattern, with `<lang>` placeholder.
     pub file_filter: String,
+    /// Whether translations are kept when their source strings change, i.e. the `keep_translations` key.
+    pub keep_translations: Option<bool>,
+    /// Raw `lang_map` value, overrides the one of the main section.
+    pub lang_map: Option<String>,
+    /// Raw `minimum_perc` value.
     pub minimum_prec: Option<i64>,
+    /// Whether pushing sources replaces strings edited on Transifex, i.e. the `replace_edited_strings` key.
+    pub replace_edited_strings: Option<bool>,
+    /// Human friendly resource name, used when the resource is created.
+    pub resource_name: Option<String>,
+    /// Source file path, relative to the project root.
     pub source_file: String,
+    /// Source language code.
     pub source_lang: String,
+    /// File format, i.e. the `type` key.
     pub type_attr: String,
 }
 
+/// Source and target file extensions which are compatible although they differ, i.e. gettext templates.
+const COMPATIBLE_EXTENSION_PAIRS: &[(&str, &str)] = &[("pot", "po")];
+
+/// Lowercased extension of the file name of the given path, `None` if it has no extension.
+fn file_extension(path: &str) -> Option<String> {
+    let file_name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+    file_name.rsplit_once('.')
+        .filter(|(stem, _)| !stem.is_empty())
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+}
+
 impl TxConfigSectionResource {
+    /// Check that `source_file` and `file_filter` share an extension, or are a known source/target
+    /// extension pair like `.pot`/`.po`, see [`COMPATIBLE_EXTENSION_PAIRS`].
+    ///
+    /// Returns a human readable description of the mismatch, e.g. for a `.ts` source with a `.po` filter.
+    pub fn validate_extensions(&self) -> Result<(), String> {
+        let source_ext = file_extension(&self.source_file);
+        let target_ext = file_extension(&self.file_filter);
+        let is_compatible = source_ext == target_ext || COMPATIBLE_EXTENSION_PAIRS.iter()
+            .any(|(source, target)| source_ext.as_deref() == Some(*source) && target_ext.as_deref() == Some(*target));
+        if is_compatible {
+            Ok(())
+        } else {
+            Err(format!("Resource {}: source_file {:?} and file_filter {:?} have incompatible extensions",
+                self.resource_full_slug, self.source_file, self.file_filter))
+        }
+    }
+
     #[cfg(test)]
+    /// Split the full slug into organization, project and resource slugs.
     pub fn get_opr_slugs(&self) -> Result<(String, String, String), TxConfigLoadError> {
         // regex match section name, and extract organization_slug, project_slug, resource_slug.
         // section name format: o:organization_slug:p:project_slug:r:resource_slug
         let re = regex::Regex::new(r"o:(?P<organization_slug>[^:]+):p:(?P<project_slug>[^:]+):r:(?P<resource_slug>[^:]+)").unwrap();
-        let caps = re.captures(&self.resource_full_slug).ok_or(TxConfigLoadError::ParseError("Invalid section name".to_string()))?;
+        let caps = re.captures(&self.resource_full_slug).ok_or_else(|| parse_error("Invalid section name"))?;
         let organization_slug = caps.name("organization_slug").unwrap().as_str();
         let project_slug = caps.name("project_slug").unwrap().as_str();
         let resource_slug = caps.name("resource_slug").unwrap().as_str();
@@ -258,4 +518,184 @@ type = QT
         let content = tx_config.to_str();
         assert_eq!(normalize_eol(&content), TEST_TX_CONFIG_CONTENT);
     }
+
+    #[test]
+    fn tst_parse_tx_config_flags() {
+        let content = TEST_TX_CONFIG_CONTENT.replace("minimum_perc = 0\n", "keep_translations = false\nminimum_perc = 0\nreplace_edited_strings = true\n");
+        let tx_config = TxConfig::from_str(&content).unwrap();
+        assert_eq!(tx_config.resource_sections[0].keep_translations, Some(false));
+        assert_eq!(tx_config.resource_sections[0].replace_edited_strings, Some(true));
+        assert_eq!(tx_config.resource_sections[1].keep_translations, None);
+        assert_eq!(normalize_eol(&tx_config.to_str()), content);
+
+        let content = TEST_TX_CONFIG_CONTENT.replace("minimum_perc = 0\n", "keep_translations = maybe\nminimum_perc = 0\n");
+        assert!(TxConfig::from_str(&content).err().is_some_and(|e| matches!(e.kind(), TxConfigLoadErrorKind::ParseError(_))));
+    }
+
+    #[test]
+    fn tst_roundtrip_unknown_main_keys() {
+        let content = TEST_TX_CONFIG_CONTENT.replace("mode = developer\n", "mode = developer\nuse_git_timestamps = true\nworkers = 4\n");
+        let tx_config = TxConfig::from_str(&content).unwrap();
+        assert_eq!(tx_config.main_section.flags, BTreeMap::from([("use_git_timestamps".to_string(), true)]));
+        assert_eq!(tx_config.main_section.extra_keys, BTreeMap::from([("workers".to_string(), "4".to_string())]));
+        assert_eq!(normalize_eol(&tx_config.to_str()), content);
+    }
+
+    #[test]
+    fn tst_normalize_host() {
+        assert_eq!(normalize_host("https://app.transifex.com/"), "https://app.transifex.com");
+        assert_eq!(normalize_host("https://app.transifex.com"), "https://app.transifex.com");
+        let content = TEST_TX_CONFIG_CONTENT.replace("host = https://www.transifex.com", "host = https://app.transifex.com/");
+        assert!(main_host_has_trailing_slash(&content));
+        assert!(!main_host_has_trailing_slash(TEST_TX_CONFIG_CONTENT));
+        let tx_config = TxConfig::from_str(&content).unwrap();
+        assert_eq!(tx_config.main_section.host, "https://app.transifex.com");
+        let mut tx_config = tx_config;
+        tx_config.main_section.host = "https://app.transifex.com/".to_string();
+        assert!(tx_config.to_str().contains("host = https://app.transifex.com\n"));
+    }
+
+    #[test]
+    fn tst_to_forward_slashes() {
+        let mut tx_config = TxConfig::from_str(TEST_TX_CONFIG_CONTENT).unwrap();
+        tx_config.resource_sections[0].file_filter = "translations\\dde-control-center_<lang>.ts".to_string();
+        tx_config.resource_sections[0].source_file = "translations\\dde-control-center_en.ts".to_string();
+        let content = tx_config.to_str();
+        assert!(content.contains("file_filter = translations/dde-control-center_<lang>.ts\n"));
+        assert!(content.contains("source_file = translations/dde-control-center_en.ts\n"));
+        assert!(!content.contains('\\'));
+    }
+
+    #[test]
+    fn tst_lang_map_for() {
+        let content = TEST_TX_CONFIG_CONTENT
+            .replace("mode = developer\n", "mode = developer\nlang_map = pt_BR: pt-br, zh_CN: zh-Hans\n")
+            .replace("source_file = translations/desktop/desktop.ts\n", "source_file = translations/desktop/desktop.ts\nlang_map = zh_CN: zh_Hans\n");
+        let tx_config = TxConfig::from_str(&content).unwrap();
+        let lang_map = tx_config.lang_map_for("translations/dde-control-center_en.ts");
+        assert_eq!(lang_map.get("pt_BR"), Some(&"pt-br".to_string()));
+        assert_eq!(lang_map.get("zh_CN"), Some(&"zh-Hans".to_string()));
+        let lang_map = tx_config.lang_map_for("translations/desktop/desktop.ts");
+        assert_eq!(lang_map.get("zh_CN"), Some(&"zh_Hans".to_string()));
+        assert_eq!(format_lang_map(&parse_lang_map("zh_CN:zh-Hans,pt_BR : pt-br")), "pt_BR: pt-br, zh_CN: zh-Hans");
+    }
+
+    #[test]
+    fn tst_verify_source_consistency() {
+        let tx_config = TxConfig::from_str(TEST_TX_CONFIG_CONTENT).unwrap();
+        let tx_yaml = tx_config.to_transifex_yaml();
+        assert_eq!(tx_config.verify_source_consistency(&tx_yaml), Ok(()));
+
+        let corrupted = TxConfig::from_str(&TEST_TX_CONFIG_CONTENT.replace(
+            "source_file = translations/desktop/desktop.ts", "source_file = translations/desktop/desktop_en.ts")).unwrap();
+        assert_eq!(corrupted.verify_source_consistency(&tx_yaml), Err(vec![
+            "source file \"translations/desktop/desktop_en.ts\" of resource o:linuxdeepin:p:deepin-desktop-environment:r:dde-control-center-desktop is not the source of any filter".to_string(),
+        ]));
+    }
+
+    #[test]
+    fn tst_sort_like_transifex() {
+        // written by the Transifex CLI
+        const TX_CLI_CONTENT: &str = r#"[main]
+host = https://www.transifex.com
+
+[o:linuxdeepin:p:deepin-desktop-environment:r:dde-control-center]
+file_filter = translations/dde-control-center_<lang>.ts
+source_file = translations/dde-control-center.ts
+source_lang = en
+type = QT
+
+[o:linuxdeepin:p:deepin-desktop-environment:r:dde-control-center-desktop]
+file_filter = translations/desktop/desktop_<lang>.ts
+source_file = translations/desktop/desktop.ts
+source_lang = en
+type = QT
+
+[o:linuxdeepin:p:deepin-desktop-environment:r:dde-dock]
+file_filter = translations/dde-dock_<lang>.ts
+source_file = translations/dde-dock.ts
+source_lang = en
+type = QT
+"#;
+        let mut tx_config = TxConfig::from_str(TX_CLI_CONTENT).unwrap();
+        tx_config.resource_sections.reverse();
+        tx_config.sort_like_transifex();
+        assert_eq!(normalize_eol(&tx_config.to_str()), TX_CLI_CONTENT);
+
+        // case-insensitive
+        tx_config.resource_sections[0].resource_full_slug = "o:linuxdeepin:p:deepin-desktop-environment:r:Dde-Dock".to_string();
+        tx_config.sort_like_transifex();
+        assert_eq!(tx_config.resource_sections[2].resource_full_slug, "o:linuxdeepin:p:deepin-desktop-environment:r:Dde-Dock");
+    }
+
+    #[test]
+    fn tst_sections_not_in_yaml() {
+        let tx_config = TxConfig::from_str(TEST_TX_CONFIG_CONTENT).unwrap();
+        let mut tx_yaml = tx_config.to_transifex_yaml();
+        assert!(tx_config.sections_not_in_yaml(&tx_yaml, "linuxdeepin/dde-control-center").is_empty());
+
+        tx_yaml.filters.retain(|filter| filter.source != "translations/desktop/desktop.ts");
+        assert_eq!(tx_config.sections_not_in_yaml(&tx_yaml, "linuxdeepin/dde-control-center"), vec![
+            "o:linuxdeepin:p:deepin-desktop-environment:r:dde-control-center-desktop".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn tst_transform() {
+        let mut tx_config = TxConfig::from_reader(TEST_TX_CONFIG_CONTENT.as_bytes()).unwrap();
+        tx_config.transform(|section| section.minimum_prec = Some(50));
+        tx_config.transform_main(|main| main.mode = None);
+        assert!(tx_config.resource_sections.iter().all(|section| section.minimum_prec == Some(50)));
+        assert_eq!(tx_config.main_section.mode, None);
+        assert!(!tx_config.to_str().contains("mode"));
+    }
+
+    #[test]
+    fn tst_validate_extensions() {
+        let tx_config = TxConfig::from_str(TEST_TX_CONFIG_CONTENT).unwrap();
+        assert!(tx_config.resource_sections.iter().all(|section| section.validate_extensions().is_ok()));
+
+        let mut section = TxConfigSectionResource {
+            resource_full_slug: "o:org:p:proj:r:app".to_string(),
+            source_file: "po/app.pot".to_string(),
+            file_filter: "po/<lang>.po".to_string(),
+            ..TxConfigSectionResource::default()
+        };
+        assert!(section.validate_extensions().is_ok());
+        section.source_file = "translations/app.ts".to_string();
+        let message = section.validate_extensions().unwrap_err();
+        assert!(message.contains("translations/app.ts") && message.contains("po/<lang>.po"));
+        // the pair only works in one direction
+        section.source_file = "po/app.po".to_string();
+        section.file_filter = "po/<lang>.pot".to_string();
+        assert!(section.validate_extensions().is_err());
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn tst_prop_tx_config_roundtrip(sections in proptest::collection::vec((
+            "[a-z][a-z0-9-]{0,12}",
+            "[a-z][a-z0-9_/.]{0,20}",
+            "[a-z]{2}(_[A-Z]{2})?",
+            proptest::option::of(0i64..=100),
+        ), 0..4)) {
+            let mut tx_config = TxConfig::default();
+            tx_config.main_section.host = "https://www.transifex.com".to_string();
+            for (idx, (slug, source_file, source_lang, minimum_prec)) in sections.into_iter().enumerate() {
+                tx_config.resource_sections.push(TxConfigSectionResource {
+                    resource_full_slug: format!("o:org:p:proj:r:{slug}{idx}"),
+                    file_filter: format!("{source_file}_<lang>.ts"),
+                    minimum_prec,
+                    source_file,
+                    source_lang,
+                    type_attr: "QT".to_string(),
+                    ..TxConfigSectionResource::default()
+                });
+            }
+            let content = tx_config.to_str();
+            let reparsed = TxConfig::from_str(&content).unwrap();
+            proptest::prop_assert_eq!(reparsed.resource_sections.len(), tx_config.resource_sections.len());
+            proptest::prop_assert_eq!(reparsed.to_str(), content);
+        }
+    }
 }
@@ -0,0 +1,239 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+// Checks of a transifex.yaml file and its project files, reported as diagnostics.
+
+use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+
+use crate::report::Severity;
+use super::supported_locales::is_known_locale;
+use super::yaml_file::{BRANCH_UNIQUE_ID_PLACEHOLDER, Filter, ResourceSlugError, TransifexYaml};
+
+impl TransifexYaml {
+    /// Run every check which doesn't need project files, i.e. slugs, branch template, target patterns,
+    /// file formats and source languages, see [`Diagnostic::render`] to print the result.
+    ///
+    /// Diagnostics are in check order. Opt-in rules are not checked, see [`Self::validate_with_options`].
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        self.validate_with_options(&ValidateOptions::default())
+    }
+
+    /// Run [`Self::validate`], then check the files of every filter under `project_root`: a missing
+    /// source file or a target directory which can't be read is an error, a matched translation file
+    /// whose language code is not a known locale is a warning, see [`Filter::suspicious_matches`].
+    ///
+    /// A missing target directory is fine, the resource is just not translated yet.
+    pub fn validate_project(&self, project_root: &PathBuf) -> Vec<Diagnostic> {
+        let mut diagnostics = self.validate();
+        for (index, filter) in self.filters.iter().enumerate() {
+            if !project_root.join(&filter.source).is_file() {
+                diagnostics.push(Diagnostic::new(Severity::Error, Some(index), format!(
+                    "source file {:?} doesn't exist", filter.source)));
+            }
+            match filter.matched_files(project_root) {
+                Ok(matched) => {
+                    for (lang_code, path) in matched.into_iter().filter(|(lang_code, _)| !is_known_locale(lang_code)) {
+                        diagnostics.push(Diagnostic::new(Severity::Warning, Some(index), format!(
+                            "{path:?} is matched by {:?}, but {lang_code:?} is not a known locale", filter.target_pattern)));
+                    }
+                },
+                Err(e) => diagnostics.push(Diagnostic::new(Severity::Error, Some(index), e.to_string())),
+            }
+        }
+        diagnostics
+    }
+
+    /// Same as [`Self::validate_project`], but serialized to a JSON array of diagnostics for CI systems,
+    /// e.g. `[{"severity": "error", "message": "...", "filter_index": 0}]`.
+    pub fn validate_to_json(&self, project_root: &PathBuf) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(&self.validate_project(project_root))
+    }
+
+    /// Same as [`Self::validate`], with the opt-in rules enabled in `opts`.
+    pub fn validate_with_options(&self, opts: &ValidateOptions) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::<Diagnostic>::new();
+        if !self.settings.has_unique_branch_token() {
+            diagnostics.push(Diagnostic::new(Severity::Warning, None, format!(
+                "pr_branch_name {:?} contains no {BRANCH_UNIQUE_ID_PLACEHOLDER}, every pull request would use the same branch",
+                self.settings.branch_template)));
+        }
+        if let Err(errors) = self.validate_resource_slugs() {
+            for error in errors {
+                let filter_index = match &error {
+                    ResourceSlugError::Invalid { source_file, .. } => self.filters.iter().position(|filter| &filter.source == source_file),
+                    ResourceSlugError::Duplicate { .. } => None,
+                };
+                diagnostics.push(Diagnostic::new(Severity::Error, filter_index, error.to_string()));
+            }
+        }
+        for (index, filter) in self.filters.iter().enumerate() {
+            if let Err(e) = filter.effective_format(&self.settings) {
+                diagnostics.push(Diagnostic::new(Severity::Error, Some(index), e.to_string()));
+            }
+            if let Err(reason) = filter.self_check() {
+                diagnostics.push(Diagnostic::new(Severity::Warning, Some(index), reason));
+            }
+            if !is_known_locale(&filter.source_lang) {
+                diagnostics.push(Diagnostic::new(Severity::Warning, Some(index), format!(
+                    "source language {:?} of {:?} is not a known locale", filter.source_lang, filter.source)));
+            }
+            if let Some(excluded_dir) = filter.excluded_target_dir() {
+                diagnostics.push(Diagnostic::new(Severity::Warning, Some(index), format!(
+                    "translations {:?} are inside of the excluded directory {excluded_dir:?}, nothing is matched", filter.target_pattern)));
+            }
+            if opts.require_targets_under_source_dir && !filter.is_target_under_source_dir() {
+                diagnostics.push(Diagnostic::new(Severity::Warning, Some(index), format!(
+                    "translations {:?} are outside of the directory of {:?}", filter.target_pattern, filter.source)));
+            }
+        }
+        diagnostics
+    }
+
+    /// Filters whose translations live outside of the directory tree of their source file, see
+    /// [`Filter::is_target_under_source_dir`]. Often a sign of a wrong path, but some valid layouts
+    /// keep sources and translations apart, so this is opt-in in [`Self::validate_with_options`].
+    pub fn filters_with_targets_outside_source_dir(&self) -> Vec<&Filter> {
+        self.filters.iter().filter(|filter| !filter.is_target_under_source_dir()).collect()
+    }
+}
+
+/// Opt-in rules of [`TransifexYaml::validate_with_options`].
+#[derive(Debug, Clone, Default)]
+pub struct ValidateOptions {
+    /// Warn about filters whose translations are outside of the directory tree of their source
+    /// file, see [`TransifexYaml::filters_with_targets_outside_source_dir`].
+    pub require_targets_under_source_dir: bool,
+}
+
+/// A problem found by [`TransifexYaml::validate`].
+///
+/// The JSON form, see [`TransifexYaml::validate_to_json`], is parsed by CI systems, so field names
+/// never change.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    /// How bad the problem is.
+    pub severity: Severity,
+    /// Human readable description.
+    pub message: String,
+    /// Index of the offending filter in [`TransifexYaml::filters`], `None` for file-wide problems.
+    pub filter_index: Option<usize>,
+}
+
+impl Diagnostic {
+    fn new(severity: Severity, filter_index: Option<usize>, message: impl Into<String>) -> Self {
+        Self { severity, message: message.into(), filter_index }
+    }
+
+    /// Render diagnostics grouped by severity, the most severe group first, e.g.:
+    ///
+    /// ```text
+    /// error (1):
+    ///   filter #0: resource slug "a b" of "app.ts" may only contain ASCII letters, digits, '-' and '_'
+    /// warning (1):
+    ///   pr_branch_name "transifex" contains no <br_unique_id>, every pull request would use the same branch
+    /// ```
+    ///
+    /// Diagnostics keep their order within a group. Returns an empty string if there are none.
+    pub fn render(diagnostics: &[Diagnostic]) -> String {
+        let mut rendered = String::new();
+        for severity in [Severity::Error, Severity::Warning, Severity::Info] {
+            let group = diagnostics.iter().filter(|diagnostic| diagnostic.severity == severity).collect::<Vec<&Diagnostic>>();
+            if group.is_empty() {
+                continue;
+            }
+            rendered.push_str(&format!("{severity} ({}):\n", group.len()));
+            for diagnostic in group {
+                match diagnostic.filter_index {
+                    Some(filter_index) => rendered.push_str(&format!("  filter #{filter_index}: {}\n", diagnostic.message)),
+                    None => rendered.push_str(&format!("  {}\n", diagnostic.message)),
+                }
+            }
+        }
+        rendered
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::fs;
+    use super::*;
+    use crate::transifex::yaml_file::tests::{TEST_TX_YAML_CONTENT, create_test_project_dir, yaml_with_filters};
+
+    #[test]
+    fn tst_validate_to_json() {
+        let project_root = create_test_project_dir("tst_validate_to_json");
+        let translations_dir = project_root.join("shell-launcher-applet/translations");
+        fs::create_dir_all(&translations_dir).unwrap();
+        fs::write(translations_dir.join("org.deepin.ds.dock.launcherapplet.ts"), "<TS/>").unwrap();
+        let tx_yaml = TransifexYaml::from_str(&TEST_TX_YAML_CONTENT
+            .replace("    source_language: en_US\n", "    source_language: en_US\n    resource_slug: dock launcher\n")).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&tx_yaml.validate_to_json(&project_root).unwrap()).unwrap();
+        assert_eq!(json, serde_json::json!([{
+            "severity": "error",
+            "message": "resource slug \"dock launcher\" of \"shell-launcher-applet/translations/org.deepin.ds.dock.launcherapplet.ts\" may only contain ASCII letters, digits, '-' and '_'",
+            "filter_index": 0,
+        }]));
+        let tx_yaml = TransifexYaml::from_str(TEST_TX_YAML_CONTENT).unwrap();
+        assert_eq!(tx_yaml.validate_to_json(&project_root).unwrap(), "[]");
+    }
+
+    #[test]
+    fn tst_validate_project() {
+        let project_root = create_test_project_dir("tst_validate_project");
+        let tx_yaml = TransifexYaml::from_str(TEST_TX_YAML_CONTENT).unwrap();
+        let diagnostics = tx_yaml.validate_project(&project_root);
+        assert_eq!(diagnostics, vec![Diagnostic::new(Severity::Error, Some(0),
+            "source file \"shell-launcher-applet/translations/org.deepin.ds.dock.launcherapplet.ts\" doesn't exist")]);
+
+        let translations_dir = project_root.join("shell-launcher-applet/translations");
+        fs::create_dir_all(&translations_dir).unwrap();
+        fs::write(translations_dir.join("org.deepin.ds.dock.launcherapplet.ts"), "<TS/>").unwrap();
+        fs::write(translations_dir.join("org.deepin.ds.dock.launcherapplet_zh_CN.ts"), "<TS/>").unwrap();
+        fs::write(translations_dir.join("org.deepin.ds.dock.launcherapplet_backup.ts"), "<TS/>").unwrap();
+        let diagnostics = tx_yaml.validate_project(&project_root);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert!(diagnostics[0].message.contains("\"backup\" is not a known locale"));
+    }
+
+    #[test]
+    fn tst_validate() {
+        let tx_yaml = TransifexYaml::from_str(TEST_TX_YAML_CONTENT).unwrap();
+        assert!(tx_yaml.validate().is_empty());
+        assert_eq!(Diagnostic::render(&tx_yaml.validate()), "");
+
+        let tx_yaml = TransifexYaml::from_str(&TEST_TX_YAML_CONTENT
+            .replace("    source_language: en_US\n", "    source_language: en_US\n    resource_slug: dock launcher\n")
+            .replace("transifex_update_<br_unique_id>", "transifex_update")).unwrap();
+        let diagnostics = tx_yaml.validate();
+        assert_eq!(diagnostics.iter().map(|diagnostic| (diagnostic.severity, diagnostic.filter_index)).collect::<Vec<_>>(), vec![
+            (Severity::Warning, None),
+            (Severity::Error, Some(0)),
+        ]);
+        assert_eq!(Diagnostic::render(&diagnostics), concat!(
+            "error (1):\n",
+            "  filter #0: resource slug \"dock launcher\" of \"shell-launcher-applet/translations/org.deepin.ds.dock.launcherapplet.ts\" may only contain ASCII letters, digits, '-' and '_'\n",
+            "warning (1):\n",
+            "  pr_branch_name \"transifex_update\" contains no <br_unique_id>, every pull request would use the same branch\n",
+        ));
+    }
+
+    #[test]
+    fn tst_filters_with_targets_outside_source_dir() {
+        let tx_yaml = TransifexYaml::from_str(&yaml_with_filters(&[
+            ("translations/app.ts", "translations/app_<lang>.ts"),
+            ("src/dock.ts", "translations/dock_<lang>.ts"),
+        ])).unwrap();
+        assert!(tx_yaml.filters[0].is_target_under_source_dir());
+        let violating: Vec<&str> = tx_yaml.filters_with_targets_outside_source_dir().iter().map(|filter| filter.source.as_str()).collect();
+        assert_eq!(violating, vec!["src/dock.ts"]);
+
+        // opt-in
+        assert!(tx_yaml.validate().is_empty());
+        let diagnostics = tx_yaml.validate_with_options(&ValidateOptions { require_targets_under_source_dir: true });
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].filter_index, Some(1));
+    }
+}
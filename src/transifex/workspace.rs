@@ -0,0 +1,132 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+// Helpers for workspaces which contain several transifex.yaml files.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+use super::resource_slug::slugify_resource;
+use super::tx_config_file::TxConfig;
+use super::yaml_file::{TransifexYaml, normalize_source_path};
+
+/// A conflict found by [`validate_workspace`].
+#[derive(Debug, PartialEq)]
+pub enum WorkspaceConflict {
+    /// The same source file is declared by more than one filter.
+    DuplicateSource {
+        /// The normalized source file path.
+        source: String,
+        /// The transifex.yaml file of every filter declaring it.
+        files: Vec<PathBuf>,
+    },
+    /// Different source files resolve to the same resource slug.
+    DuplicateSlug {
+        /// The shared resource slug.
+        slug: String,
+        /// The transifex.yaml file of every filter resolving to it.
+        files: Vec<PathBuf>,
+    },
+}
+
+/// Check that source files and their resolved resource slugs are unique across all given
+/// transifex.yaml files.
+///
+/// Slugs are resolved the same way as templated fallback slugs, see [`super::yaml_file::Filter::effective_resource_slug`].
+/// Each conflict lists the transifex.yaml file of every filter involved.
+pub fn validate_workspace(yamls: &[(PathBuf, TransifexYaml)]) -> Result<(), Vec<WorkspaceConflict>> {
+    let mut files_by_source = BTreeMap::<String, Vec<PathBuf>>::new();
+    let mut sources_by_slug = BTreeMap::<String, (BTreeSet<String>, Vec<PathBuf>)>::new();
+    for (yaml_path, tx_yaml) in yamls {
+        for filter in &tx_yaml.filters {
+            let source = normalize_source_path(&filter.source);
+            let slug = filter.resource_slug.clone().unwrap_or_else(|| slugify_resource(&source));
+            let (slug_sources, slug_files) = sources_by_slug.entry(slug).or_default();
+            slug_sources.insert(source.clone());
+            slug_files.push(yaml_path.clone());
+            files_by_source.entry(source).or_default().push(yaml_path.clone());
+        }
+    }
+
+    let mut conflicts = Vec::<WorkspaceConflict>::new();
+    for (source, files) in files_by_source {
+        if files.len() > 1 {
+            conflicts.push(WorkspaceConflict::DuplicateSource { source, files });
+        }
+    }
+    for (slug, (sources, files)) in sources_by_slug {
+        // the same source declared twice is already reported above
+        if sources.len() > 1 {
+            conflicts.push(WorkspaceConflict::DuplicateSlug { slug, files });
+        }
+    }
+
+    if conflicts.is_empty() {
+        Ok(())
+    } else {
+        Err(conflicts)
+    }
+}
+
+/// Format usage across a batch of repositories, for org-wide reporting.
+pub struct BatchFormatSummary;
+
+impl BatchFormatSummary {
+    /// Count the resources of each format, i.e. the `type` of each resource section, across the
+    /// `.tx/config` of every successfully converted repository. Failed repositories are skipped.
+    pub fn from_results<E>(results: &[(PathBuf, Result<TxConfig, E>)]) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::<String, usize>::new();
+        for tx_config in results.iter().filter_map(|(_, result)| result.as_ref().ok()) {
+            for resource_section in &tx_config.resource_sections {
+                *counts.entry(resource_section.type_attr.clone()).or_default() += 1;
+            }
+        }
+        counts
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::transifex::tx_config_file::tests::TEST_TX_CONFIG_CONTENT;
+    use crate::transifex::yaml_file::tests::TEST_TX_YAML_CONTENT;
+
+    #[test]
+    fn tst_validate_workspace() {
+        let tx_yaml_a = TransifexYaml::from_str(TEST_TX_YAML_CONTENT).unwrap();
+        let tx_yaml_b = TransifexYaml::from_str(TEST_TX_YAML_CONTENT).unwrap();
+        let tx_yaml_c = TransifexYaml::from_str(&TEST_TX_YAML_CONTENT.replace("launcherapplet.ts", "launcherapplet.po")).unwrap();
+        let yamls = vec![
+            (PathBuf::from("a/transifex.yaml"), tx_yaml_a),
+            (PathBuf::from("b/transifex.yaml"), tx_yaml_b),
+        ];
+        assert_eq!(validate_workspace(&yamls), Err(vec![WorkspaceConflict::DuplicateSource {
+            source: "shell-launcher-applet/translations/org.deepin.ds.dock.launcherapplet.ts".to_string(),
+            files: vec![PathBuf::from("a/transifex.yaml"), PathBuf::from("b/transifex.yaml")],
+        }]));
+        assert!(validate_workspace(&yamls[..1]).is_ok());
+
+        let yamls = vec![yamls.into_iter().next().unwrap(), (PathBuf::from("c/transifex.yaml"), tx_yaml_c)];
+        assert_eq!(validate_workspace(&yamls), Err(vec![WorkspaceConflict::DuplicateSlug {
+            slug: "shell-launcher-applet_translations_org_deepin_ds_dock_launcherapplet".to_string(),
+            files: vec![PathBuf::from("a/transifex.yaml"), PathBuf::from("c/transifex.yaml")],
+        }]));
+    }
+
+    #[test]
+    fn tst_batch_format_summary() {
+        let qt_repo = TxConfig::from_str(TEST_TX_CONFIG_CONTENT).unwrap();
+        let po_repo = TxConfig::from_str(&TEST_TX_CONFIG_CONTENT.replacen("type = QT", "type = PO", 1)).unwrap();
+        let results = vec![
+            (PathBuf::from("repo-a"), Ok(qt_repo)),
+            (PathBuf::from("repo-b"), Ok(po_repo)),
+            (PathBuf::from("repo-c"), Err("conversion failed")),
+        ];
+        assert_eq!(BatchFormatSummary::from_results(&results), BTreeMap::from([
+            ("PO".to_string(), 1),
+            ("QT".to_string(), 3),
+        ]));
+        assert!(BatchFormatSummary::from_results::<&str>(&[]).is_empty());
+    }
+}
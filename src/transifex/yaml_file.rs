@@ -4,128 +4,710 @@
 
 // transifex.yaml file spec: https://help.transifex.com/en/articles/6265125-github-installation-and-configuration#h_94380d9cd8
 
-use std::{fs, path::PathBuf};
+use std::{collections::{BTreeMap, HashMap}, fs, path::{Path, PathBuf}};
 
-use regex::Regex;
 use serde::{Serialize, Deserialize};
 use thiserror::Error as TeError;
+use crate::error::{ContextError, ResultExt};
 
 use super::tx_config_file::*;
+use super::resource_slug::{fill_slug_template, is_valid_resource_slug, slugify_resource, source_path_hash};
+use super::supported_locales::normalize_locale;
+use super::target_match::{EXCLUDED_TARGET_DIRS, create_filter_pattern, match_lang_code};
 
+/// Content of a `transifex.yaml` file, used by the Transifex GitHub integration.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TransifexYaml {
+    /// Resources of the project.
     pub filters: Vec<Filter>,
+    /// Integration settings.
     pub settings: Settings,
 }
 
+/// An entry of the resource lookup table, which maps source files to existing Transifex resources.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TxResourceLookupEntry {
+    /// GitHub repository, e.g. `linuxdeepin/dde-shell`.
     pub repository: String,
     /// Git branch name, not transifex branch name
     pub branch: String,
+    /// Source file path, relative to the project root.
     pub resource: String,
     /// Full slug, i.e. `o:org:p:proj:r:res`
     pub transifex_resource_id: String,
 }
 
 impl TransifexYaml {
+    /// Convert to `.tx/config`, looking up resource slugs from `lookup_table`.
+    ///
+    /// A lookup entry matches a filter if it has the same repository, see [`TxResourceLookupEntry::matches_repository`],
+    /// and its resource is either the full source file path, or the basename of it. Full path matches
+    /// take precedence over basename matches, so a basename-only lookup table works as long as
+    /// basenames are unique.
+    ///
+    /// A filter whose format can't be resolved, see [`Filter::effective_format`], gets an empty `type`.
+    /// Use [`Self::try_to_tx_config`] to fail instead.
     pub fn to_tx_config(&self, github_repository: String, lookup_table: Vec<TxResourceLookupEntry>) -> TxConfig {
-        let mut resource_sections = Vec::<TxConfigSectionResource>::new();
+        let resource_sections = self.filters.iter()
+            .map(|filter| filter.to_tx_config_section(&github_repository, &lookup_table, None, filter.effective_tx_type(&self.settings).unwrap_or_default()))
+            .collect();
+        TxConfig {
+            main_section: self.tx_config_main_section(),
+            resource_sections,
+        }
+    }
+
+    /// Same as [`Self::to_tx_config`], but fails if the format of a filter can't be resolved, and
+    /// borrows the lookup table.
+    pub fn try_to_tx_config(&self, github_repository: &str, lookup_table: &[TxResourceLookupEntry]) -> Result<TxConfig, FormatError> {
+        self.to_tx_config_impl(github_repository, lookup_table, None)
+    }
+
+    /// Same as [`Self::try_to_tx_config`], but resources missing in the lookup table get a slug generated
+    /// from `fallback_template` instead of the unknown placeholder.
+    ///
+    /// See [`super::resource_slug::resource_slug_from_template`] for the template format. Filters
+    /// with a [`Filter::resource_slug`] use it instead of the slugified source file path.
+    pub fn to_tx_config_with_fallback_template(&self, github_repository: String, lookup_table: Vec<TxResourceLookupEntry>, fallback_template: &str) -> Result<TxConfig, FormatError> {
+        self.to_tx_config_impl(&github_repository, &lookup_table, Some(fallback_template))
+    }
+
+    /// Same as [`Self::try_to_tx_config`], but fails instead of using the unknown placeholder.
+    ///
+    /// Returns all filters without a lookup table match if there is any.
+    pub fn to_tx_config_strict(&self, github_repository: &str, lookup_table: &[TxResourceLookupEntry]) -> Result<TxConfig, StrictConvertError<'_>> {
+        let missing = self.filters.iter()
+            .filter(|filter| filter.lookup_resource_full_slug(github_repository, lookup_table).is_none())
+            .collect::<Vec<&Filter>>();
+        if !missing.is_empty() {
+            return Err(StrictConvertError::Missing(missing));
+        }
+        Ok(self.to_tx_config_impl(github_repository, lookup_table, None)?)
+    }
+
+    /// Same as [`Self::try_to_tx_config`], but consumes this file so the source file, source language,
+    /// format and target pattern of each filter are moved into the resulting `TxConfig` instead of
+    /// cloned, and borrows the lookup table. Prefer this when the `TransifexYaml` is not needed after
+    /// the conversion.
+    pub fn into_tx_config(self, github_repository: &str, lookup_table: &[TxResourceLookupEntry]) -> Result<TxConfig, FormatError> {
+        let main_section = self.tx_config_main_section();
+        let settings = self.settings;
+        let resource_sections = self.filters.into_iter()
+            .map(|filter| filter.into_tx_config_section(github_repository, lookup_table, &settings))
+            .collect::<Result<Vec<TxConfigSectionResource>, FormatError>>()?;
+        Ok(TxConfig { main_section, resource_sections })
+    }
+
+    /// Resolve the full slug of every filter the same way as [`Self::to_tx_config`], in filter order,
+    /// without building the whole config.
+    ///
+    /// Filters missing in the lookup table get [`UNKNOWN_RESOURCE_FULL_SLUG`], which is only
+    /// included if `include_placeholders` is set.
+    pub fn resource_slugs(&self, github_repository: &str, lookup_table: &[TxResourceLookupEntry], include_placeholders: bool) -> Vec<String> {
+        self.filters.iter()
+            .map(|filter| filter.resolve_resource_full_slug(github_repository, lookup_table, None))
+            .filter(|slug| include_placeholders || slug != UNKNOWN_RESOURCE_FULL_SLUG)
+            .collect()
+    }
+
+    /// Find filters which resolve to the same full slug in the resulting `.tx/config`, e.g. because
+    /// the lookup table maps different source files to one resource, so they would overwrite each other.
+    ///
+    /// Slugs are resolved the same way as [`Self::to_tx_config`], or [`Self::to_tx_config_with_fallback_template`]
+    /// if `fallback_template` is given. Filters missing in the lookup table without a fallback template all
+    /// share [`UNKNOWN_RESOURCE_FULL_SLUG`], which is not reported.
+    pub fn duplicate_resolved_slugs(&self, github_repository: &str, lookup_table: &[TxResourceLookupEntry], fallback_template: Option<&str>) -> Vec<ResourceSlugError> {
+        let mut sources_by_slug = BTreeMap::<String, Vec<String>>::new();
         for filter in &self.filters {
-            let mut resource_section = TxConfigSectionResource::default();
-            resource_section.source_file = filter.source.clone();
-            resource_section.source_lang = filter.source_lang.clone();
-            resource_section.type_attr = filter.format.clone();
-            resource_section.file_filter = filter.target_pattern.clone();
-
-            // from lookup table, find if we have resource have the same repository and resource name
-            if let Some(lookup_entry) = lookup_table.iter().find(|entry| {
-                entry.repository == github_repository && entry.resource == filter.source
-            }) {
-                resource_section.resource_full_slug = lookup_entry.transifex_resource_id.clone();
-            } else {
-                resource_section.resource_full_slug = format!("o:{}:p:{}:r:{}", "unknown-org", "unknown-proj", "unknown-res")
+            let slug = filter.resolve_resource_full_slug(github_repository, lookup_table, fallback_template);
+            if slug != UNKNOWN_RESOURCE_FULL_SLUG {
+                sources_by_slug.entry(slug).or_default().push(filter.source.clone());
             }
-            
-            resource_sections.push(resource_section);
+        }
+        sources_by_slug.into_iter()
+            .filter(|(_, sources)| sources.len() > 1)
+            .map(|(slug, sources)| ResourceSlugError::Duplicate { slug, sources })
+            .collect()
+    }
+
+    /// Find the filter whose source file is the given one, comparing normalized paths, see
+    /// [`normalize_source_path`].
+    pub fn filter_for_source(&self, source: &str) -> Option<&Filter> {
+        let source = normalize_source_path(source);
+        self.filters.iter().find(|filter| normalize_source_path(&filter.source) == source)
+    }
+
+    /// Resolve the full slug the given file would be uploaded to, i.e. the slug of the filter owning it
+    /// as source file or translation file, see [`Filter::owns_file`].
+    ///
+    /// `file` is either absolute under `project_root` or relative to it. Returns `None` if no filter
+    /// owns the file, or if the owning filter is missing in the lookup table.
+    pub fn slug_for_file(&self, github_repository: &str, lookup_table: &[TxResourceLookupEntry], project_root: &Path, file: &Path) -> Option<String> {
+        let relative = file.strip_prefix(project_root).unwrap_or(file);
+        let relative = normalize_source_path(&relative.to_string_lossy());
+        self.filters.iter()
+            .find(|filter| filter.owns_file(&relative))
+            .map(|filter| filter.resolve_resource_full_slug(github_repository, lookup_table, None))
+            .filter(|slug| slug != UNKNOWN_RESOURCE_FULL_SLUG)
+    }
+
+    pub(crate) fn to_tx_config_impl(&self, github_repository: &str, lookup_table: &[TxResourceLookupEntry], fallback_template: Option<&str>) -> Result<TxConfig, FormatError> {
+        let mut resource_sections = Vec::<TxConfigSectionResource>::new();
+        for filter in &self.filters {
+            resource_sections.push(filter.to_tx_config_section(github_repository, lookup_table, fallback_template, filter.effective_tx_type(&self.settings)?));
         };
-        TxConfig {
-            main_section: TxConfigSectionMain {
-                host: "https://www.transifex.com".to_string(),
-                ..TxConfigSectionMain::default()
-            },
+        Ok(TxConfig {
+            main_section: self.tx_config_main_section(),
             resource_sections,
+        })
+    }
+
+    /// Convert to one `.tx/config` per component, for a modular `.tx` layout. Each config only
+    /// contains the resources of the filters of its component, see [`Filter::source_component`].
+    ///
+    /// Resources are resolved the same way as [`Self::to_tx_config`]. Configs are sorted by component.
+    pub fn to_tx_configs_by_component(&self, github_repository: &str, lookup_table: &[TxResourceLookupEntry], depth: usize) -> Result<Vec<(String, TxConfig)>, FormatError> {
+        let mut resource_sections_by_component = BTreeMap::<String, Vec<TxConfigSectionResource>>::new();
+        for filter in &self.filters {
+            resource_sections_by_component.entry(filter.source_component(depth)).or_default()
+                .push(filter.to_tx_config_section(github_repository, lookup_table, None, filter.effective_tx_type(&self.settings)?));
         }
+        Ok(resource_sections_by_component.into_iter()
+            .map(|(component, resource_sections)| (component, TxConfig { main_section: self.tx_config_main_section(), resource_sections }))
+            .collect())
+    }
+
+    fn tx_config_main_section(&self) -> TxConfigSectionMain {
+        TxConfigSectionMain {
+            host: "https://www.transifex.com".to_string(),
+            lang_map: self.settings.lang_map.as_ref().map(format_lang_map),
+            ..TxConfigSectionMain::default()
+        }
+    }
+
+    /// Check that explicit resource slugs are valid Transifex slugs, and that the effective resource
+    /// slugs of all filters are unique, see [`Filter::effective_resource_slug`].
+    pub fn validate_resource_slugs(&self) -> Result<(), Vec<ResourceSlugError>> {
+        let mut errors = Vec::<ResourceSlugError>::new();
+        let mut sources_by_slug = BTreeMap::<String, Vec<String>>::new();
+        for filter in &self.filters {
+            if let Some(resource_slug) = &filter.resource_slug {
+                if !is_valid_resource_slug(resource_slug) {
+                    errors.push(ResourceSlugError::Invalid { source_file: filter.source.clone(), slug: resource_slug.clone() });
+                }
+            }
+            sources_by_slug.entry(filter.effective_resource_slug()).or_default().push(filter.source.clone());
+        }
+        for (slug, sources) in sources_by_slug {
+            if sources.len() > 1 {
+                errors.push(ResourceSlugError::Duplicate { slug, sources });
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Normalize this file in place into a canonical form for diffing and fingerprinting.
+    ///
+    /// All fields are trimmed, paths are normalized with [`normalize_source_path`], formats are
+    /// canonicalized and uppercased, and source and primary languages are normalized with
+    /// [`normalize_locale`]. `language_mapping` is only trimmed, since its local codes name files.
+    pub fn normalize(&mut self) {
+        for filter in &mut self.filters {
+            filter.type_attr = filter.type_attr.trim().to_string();
+            filter.source = normalize_source_path(&filter.source);
+            filter.format = canonicalize_file_format(&filter.format).to_ascii_uppercase();
+            filter.source_lang = normalize_locale(&filter.source_lang);
+            filter.target_pattern = normalize_source_path(&filter.target_pattern);
+            if let Some(resource_slug) = &mut filter.resource_slug {
+                *resource_slug = resource_slug.trim().to_string();
+            }
+            if let Some(resource_name) = &mut filter.resource_name {
+                *resource_name = resource_name.trim().to_string();
+            }
+        }
+        let settings = &mut self.settings;
+        settings.branch_template = settings.branch_template.trim().to_string();
+        if let Some(lang_map) = &mut settings.lang_map {
+            *lang_map = std::mem::take(lang_map).into_iter()
+                .map(|(remote, local)| (remote.trim().to_string(), local.trim().to_string()))
+                .collect();
+        }
+        if let Some(default_format) = &mut settings.default_format {
+            *default_format = canonicalize_file_format(default_format).to_ascii_uppercase();
+        }
+        if let Some(primary_language) = &mut settings.primary_language {
+            *primary_language = normalize_locale(primary_language);
+        }
+    }
+
+    /// Sort filters by their normalized source path, see [`normalize_source_path`], to keep saved files tidy.
+    ///
+    /// The sort is stable, so filters with the same source keep their order. Only the order of saved
+    /// filters changes, [`Self::to_tx_config`] resolves the same resources either way.
+    pub fn sort_filters(&mut self) {
+        self.filters.sort_by_cached_key(|filter| normalize_source_path(&filter.source));
+    }
+
+    /// Upsert the resources derived from this file into an existing `.tx/config`.
+    ///
+    /// Resource sections are matched by their source file. Only the fields derived from this file, i.e.
+    /// `source_file`, `file_filter`, `source_lang`, `type`, and `resource_name` if set, are updated in
+    /// place, so hand-edited keys like `lang_map`, `minimum_perc` or `keep_translations` survive. The
+    /// resource slug is kept if the lookup table doesn't know the resource. Sections which are not
+    /// derived from this file, e.g. manually-added resources, are preserved as-is.
+    ///
+    /// Fails without touching `existing` if the format of a filter can't be resolved.
+    pub fn merge_into_tx_config(&self, existing: &mut TxConfig, github_repository: &str, lookup_table: &[TxResourceLookupEntry]) -> Result<(), FormatError> {
+        let resource_sections = self.filters.iter()
+            .map(|filter| Ok(filter.to_tx_config_section(github_repository, lookup_table, None, filter.effective_tx_type(&self.settings)?)))
+            .collect::<Result<Vec<TxConfigSectionResource>, FormatError>>()?;
+        for resource_section in resource_sections {
+            match existing.resource_sections.iter_mut().find(|section| section.source_file == resource_section.source_file) {
+                Some(existing_section) => {
+                    if resource_section.resource_full_slug != UNKNOWN_RESOURCE_FULL_SLUG {
+                        existing_section.resource_full_slug = resource_section.resource_full_slug;
+                    }
+                    existing_section.source_file = resource_section.source_file;
+                    existing_section.file_filter = resource_section.file_filter;
+                    existing_section.source_lang = resource_section.source_lang;
+                    existing_section.type_attr = resource_section.type_attr;
+                    if resource_section.resource_name.is_some() {
+                        existing_section.resource_name = resource_section.resource_name;
+                    }
+                },
+                None => existing.resource_sections.push(resource_section),
+            }
+        }
+        Ok(())
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Placeholder slug used when the resource can not be found in the lookup table.
+pub const UNKNOWN_RESOURCE_FULL_SLUG: &str = "o:unknown-org:p:unknown-proj:r:unknown-res";
+
+/// A resource, i.e. an entry of `filters`.
+///
+/// `resource_slug` and `resource_name` are extensions of this tool, they are omitted when unset so
+/// files written by us are still accepted by the Transifex GitHub integration.
+///
+/// Keys used by older versions of the integration and the Transifex CLI are accepted when loading,
+/// but the current keys are always written:
+///
+/// | Key                            | Also accepted   |
+/// | ------------------------------ | --------------- |
+/// | `filter_type`                  | `type`          |
+/// | `source_file`                  | `source`        |
+/// | `file_format`                  | `format`        |
+/// | `source_language`              | `source_lang`   |
+/// | `translation_files_expression` | `file_filter`, `translation_files` |
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Filter {
-    #[serde(rename = "filter_type")]
+    /// Filter type, usually `file`.
+    #[serde(rename = "filter_type", alias = "type")]
     pub type_attr: String,
-    #[serde(rename = "source_file")]
+    /// Source file path, relative to the project root.
+    #[serde(rename = "source_file", alias = "source")]
     pub source: String,
-    #[serde(rename = "file_format")]
+    /// Known formats are canonicalized to uppercase, see [`canonicalize_file_format`].
+    ///
+    /// Empty if omitted, see [`Self::effective_format`] for how the format is resolved then.
+    #[serde(rename = "file_format", alias = "format", default, skip_serializing_if = "String::is_empty", deserialize_with = "deserialize_file_format")]
     pub format: String,
-    #[serde(rename = "source_language")]
+    /// Source language code.
+    #[serde(rename = "source_language", alias = "source_lang")]
     pub source_lang: String,
-    #[serde(rename = "translation_files_expression")]
+    /// Translation file path pattern, with `<lang>` placeholder.
+    #[serde(rename = "translation_files_expression", alias = "file_filter", alias = "translation_files")]
     pub target_pattern: String,
+    /// Resource slug used instead of the slugified source file path, e.g. `dock-launcher-applet`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_slug: Option<String>,
+    /// Human friendly resource name, e.g. `Dock — Launcher Applet`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_name: Option<String>,
 }
 
 impl Filter {
-    pub fn match_target_files(&self, project_root: &PathBuf) -> Result<Vec<(String, PathBuf)>, std::io::Error> {
-        let target_pattern_path = project_root.join(&self.target_pattern);
-        let Some(target_filename_pattern) = target_pattern_path.file_name() else {
-            return Err(std::io::Error::new(std::io::ErrorKind::Other, "File name not found"));
-        };
-        let Some(target_filename_pattern) = target_filename_pattern.to_str() else {
-            return Err(std::io::Error::new(std::io::ErrorKind::Other, "File name not valid"));
-        };
-        let Some(target_filter_pattern) = create_filter_pattern(target_filename_pattern) else {
-            return Err(std::io::Error::new(std::io::ErrorKind::Other, "Filter pattern not valid"));
-        };
-        let Some(target_parent) = target_pattern_path.parent() else {
-            return Err(std::io::Error::new(std::io::ErrorKind::Other, "Parent dir not found"));
+    /// Convert to a resource section with the given `type`, see [`Self::effective_tx_type`].
+    fn to_tx_config_section(&self, github_repository: &str, lookup_table: &[TxResourceLookupEntry], fallback_template: Option<&str>, type_attr: String) -> TxConfigSectionResource {
+        let mut resource_section = TxConfigSectionResource::default();
+        resource_section.resource_full_slug = self.resolve_resource_full_slug(github_repository, lookup_table, fallback_template);
+        resource_section.source_file = self.source.clone();
+        resource_section.source_lang = self.source_lang.clone();
+        resource_section.type_attr = type_attr;
+        resource_section.file_filter = self.target_pattern.clone();
+        resource_section.resource_name = self.resource_name.clone();
+        resource_section
+    }
+
+    /// Same as [`Self::to_tx_config_section`], but moves the strings out of the filter instead of cloning them.
+    fn into_tx_config_section(mut self, github_repository: &str, lookup_table: &[TxResourceLookupEntry], settings: &Settings) -> Result<TxConfigSectionResource, FormatError> {
+        let resource_full_slug = self.resolve_resource_full_slug(github_repository, lookup_table, None);
+        let type_attr = match self.format.is_empty() {
+            true => self.effective_tx_type(settings)?,
+            false => into_canonical_file_format(std::mem::take(&mut self.format)),
         };
-        let target_files = target_parent.read_dir()?;
-        let mut matched_files = Vec::<(String, PathBuf)>::new();
-        for file in target_files {
-            let file = file?;
-            let file_name = file.file_name();
-            let Some(file_name) = file_name.to_str() else {
-                continue;
-            };
-            target_filter_pattern.captures(file_name).and_then(|captures| {
-                captures.get(1).map(|lang_code| {
-                    let lang_code = lang_code.as_str();
-                    matched_files.push((lang_code.to_string(), file.path()));
-                })
-            });
+        Ok(TxConfigSectionResource {
+            resource_full_slug,
+            source_file: self.source,
+            source_lang: self.source_lang,
+            type_attr,
+            file_filter: self.target_pattern,
+            resource_name: self.resource_name,
+            ..TxConfigSectionResource::default()
+        })
+    }
+
+    fn lookup_resource_full_slug<'a>(&self, github_repository: &str, lookup_table: &'a [TxResourceLookupEntry]) -> Option<&'a str> {
+        let basename = self.source.rsplit('/').next().unwrap_or(&self.source);
+        let repository_entries = || lookup_table.iter().filter(|entry| entry.matches_repository(github_repository));
+        repository_entries().find(|entry| entry.resource == self.source)
+            .or_else(|| repository_entries().find(|entry| entry.resource == basename))
+            .map(|entry| entry.transifex_resource_id.as_str())
+    }
+
+    pub(crate) fn resolve_resource_full_slug(&self, github_repository: &str, lookup_table: &[TxResourceLookupEntry], fallback_template: Option<&str>) -> String {
+        // from lookup table, find if we have resource have the same repository and resource name,
+        // and fall back to the resource basename. Lookup table entries win over `resource_slug`,
+        // since they point to resources which already exist.
+        if let Some(resource_full_slug) = self.lookup_resource_full_slug(github_repository, lookup_table) {
+            resource_full_slug.to_string()
+        } else if let Some(fallback_template) = fallback_template {
+            fill_slug_template(fallback_template, &self.effective_resource_slug(), &self.source)
+        } else {
+            UNKNOWN_RESOURCE_FULL_SLUG.to_string()
+        }
+    }
+
+    /// Check that the target pattern can match at least one plausible file, i.e. that it compiles and
+    /// matches the file name of a synthetic `xx` locale.
+    ///
+    /// Returns why the generated `file_filter` would never match otherwise, e.g. if `<lang>` is
+    /// part of a directory instead of the file name.
+    pub fn self_check(&self) -> Result<(), String> {
+        let file_name_pattern = self.target_pattern.rsplit('/').next().unwrap_or(&self.target_pattern);
+        if !file_name_pattern.contains("<lang>") {
+            return Err(format!("Target pattern {:?} has no <lang> placeholder in its file name", self.target_pattern));
+        }
+        let Some(pattern) = create_filter_pattern(file_name_pattern, false) else {
+            return Err(format!("Target pattern {:?} is too long or has more than one <lang> placeholder", self.target_pattern));
         };
-        Ok(matched_files)
+        let synthetic_file_name = file_name_pattern.replace("<lang>", "xx");
+        if pattern.captures(&synthetic_file_name).and_then(|captures| captures.get(1)).is_none_or(|lang_code| lang_code.as_str() != "xx") {
+            return Err(format!("Target pattern {:?} doesn't match {synthetic_file_name:?}", self.target_pattern));
+        }
+        Ok(())
+    }
+
+    /// Whether the given file, relative to the project root, is the source file of this filter or a
+    /// translation file matched by its target pattern.
+    pub fn owns_file(&self, file: &str) -> bool {
+        let file = normalize_source_path(file);
+        if file == normalize_source_path(&self.source) {
+            return true;
+        }
+        let target_pattern = normalize_source_path(&self.target_pattern);
+        let (target_dir, file_name_pattern) = target_pattern.rsplit_once('/').unwrap_or(("", target_pattern.as_str()));
+        let (file_dir, file_name) = file.rsplit_once('/').unwrap_or(("", file.as_str()));
+        file_dir == target_dir && match_lang_code(file_name_pattern, file_name, false).is_some()
+    }
+
+    /// Whether the target pattern is in the directory of the source file or a subdirectory of it,
+    /// e.g. `translations/app_<lang>.ts` or `translations/zh/app_<lang>.ts` for `translations/app.ts`.
+    pub fn is_target_under_source_dir(&self) -> bool {
+        let source = normalize_source_path(&self.source);
+        let target_pattern = normalize_source_path(&self.target_pattern);
+        let source_dir = source.rsplit_once('/').map_or("", |(dir, _)| dir);
+        let target_dir = target_pattern.rsplit_once('/').map_or("", |(dir, _)| dir);
+        source_dir.is_empty() || target_dir == source_dir
+            || target_dir.strip_prefix(source_dir).is_some_and(|rest| rest.starts_with('/'))
+    }
+
+    /// The component of this filter, i.e. the first `depth` directories of its normalized source
+    /// path, e.g. `dock` for `dock/translations/dock.ts` with depth 1. Sources with fewer directories
+    /// use all of them, so sources at the project root have the empty component.
+    pub fn source_component(&self, depth: usize) -> String {
+        let source = normalize_source_path(&self.source);
+        let dirs: Vec<&str> = source.split('/').collect();
+        dirs[..dirs.len() - 1].iter().take(depth).copied().collect::<Vec<&str>>().join("/")
+    }
+
+    /// The excluded directory the translation files of this filter are in, if any, see
+    /// [`EXCLUDED_TARGET_DIRS`]. Such filters match no translation files.
+    pub fn excluded_target_dir(&self) -> Option<&'static str> {
+        let target_pattern = normalize_source_path(&self.target_pattern);
+        let target_dir = target_pattern.rsplit_once('/').map_or("", |(dir, _)| dir);
+        target_dir.split('/')
+            .find_map(|component| EXCLUDED_TARGET_DIRS.iter().copied().find(|excluded| *excluded == component))
+    }
+
+    /// Number of directories between the project root and the translation files, e.g. 2 for
+    /// `a/b/app_<lang>.ts` and 0 for `app_<lang>.ts`. The target pattern is normalized first, see
+    /// [`normalize_source_path`].
+    pub fn target_depth(&self) -> usize {
+        normalize_source_path(&self.target_pattern).split('/').count().saturating_sub(1)
+    }
+
+    /// The `r:` segment of slugs synthesized for this filter, i.e. [`Self::resource_slug`] if set,
+    /// or the slugified source file path.
+    pub fn effective_resource_slug(&self) -> String {
+        match &self.resource_slug {
+            Some(resource_slug) => resource_slug.clone(),
+            None => slugify_resource(&self.source),
+        }
+    }
+
+    /// The resource name used when creating the resource, i.e. [`Self::resource_name`] if set, or the
+    /// source file path.
+    pub fn effective_resource_name(&self) -> &str {
+        self.resource_name.as_deref().unwrap_or(&self.source)
+    }
+
+    /// Get the file format of this filter.
+    pub fn file_format(&self) -> FileFormat {
+        FileFormat::from_tx_type(&self.format)
+    }
+
+    /// Resolve the file format of this filter: the explicit [`Self::format`] if set, then the
+    /// default format of `settings`, then the format inferred from the source file extension,
+    /// see [`FileFormat::from_extension`].
+    pub fn effective_format(&self, settings: &Settings) -> Result<FileFormat, FormatError> {
+        if !self.format.is_empty() {
+            return Ok(self.file_format());
+        }
+        if let Some(default_format) = settings.default_format.as_deref().filter(|format| !format.trim().is_empty()) {
+            return Ok(FileFormat::from_tx_type(default_format));
+        }
+        FileFormat::from_extension(&self.source).ok_or_else(|| FormatError::Unresolved(self.source.clone()))
+    }
+
+    /// The `type` of the generated `.tx/config` section, see [`Self::effective_format`].
+    fn effective_tx_type(&self, settings: &Settings) -> Result<String, FormatError> {
+        self.effective_format(settings).map(|format| format.to_tx_type())
     }
+
 }
 
+/// The `settings` section of `transifex.yaml`.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Settings {
+    /// Branch name template of pull requests, see [`BRANCH_UNIQUE_ID_PLACEHOLDER`].
     #[serde(rename = "pr_branch_name")]
     pub branch_template: String,
+    /// Maps Transifex language codes to local language codes.
+    #[serde(rename = "language_mapping", default, skip_serializing_if = "Option::is_none")]
+    pub lang_map: Option<BTreeMap<String, String>>,
+    /// Format of filters without `file_format`, an extension of this tool.
+    #[serde(rename = "default_file_format", default, skip_serializing_if = "Option::is_none")]
+    pub default_format: Option<String>,
+    /// Language of the primary UI if it differs from the source language, e.g. `zh_CN` for a project
+    /// with English sources whose users are mostly Chinese. An extension of this tool.
+    ///
+    /// Only carried through for tooling which treats the primary language specially, it is not part
+    /// of the generated `.tx/config`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub primary_language: Option<String>,
 }
 
+/// Placeholder in `pr_branch_name` which Transifex replaces with a unique id for each pull request.
+pub const BRANCH_UNIQUE_ID_PLACEHOLDER: &str = "<br_unique_id>";
+
+impl Settings {
+    /// Whether the branch template contains [`BRANCH_UNIQUE_ID_PLACEHOLDER`]. Without it, every
+    /// pull request would be created from the same branch.
+    pub fn has_unique_branch_token(&self) -> bool {
+        self.branch_template.contains(BRANCH_UNIQUE_ID_PLACEHOLDER)
+    }
+
+    /// Resolve the branch name for the given unique id.
+    ///
+    /// If the template has no [`BRANCH_UNIQUE_ID_PLACEHOLDER`], a short stable hash of the unique id
+    /// is appended instead, see [`source_path_hash`], so branch names are still unique.
+    pub fn resolve_branch_name(&self, unique_id: &str) -> String {
+        if self.has_unique_branch_token() {
+            return self.branch_template.replace(BRANCH_UNIQUE_ID_PLACEHOLDER, unique_id);
+        }
+        format!("{}_{}", self.branch_template, source_path_hash(unique_id))
+    }
+
+    /// The `lang_map` in the reverse direction, which maps local language codes to Transifex ones.
+    ///
+    /// If several Transifex codes map to the same local code, the first one in sorted order wins, see
+    /// [`Self::transifex_codes_for`] to get all of them.
+    pub fn reverse_lang_map(&self) -> HashMap<String, String> {
+        let mut reversed = HashMap::<String, String>::new();
+        for (remote, local) in self.lang_map.iter().flatten() {
+            reversed.entry(local.clone()).or_insert_with(|| remote.clone());
+        }
+        reversed
+    }
+
+    /// All Transifex language codes the `lang_map` maps to the given local language code, in sorted
+    /// order. A local code which is not mapped is its own Transifex code.
+    pub fn transifex_codes_for(&self, local: &str) -> Vec<String> {
+        let candidates: Vec<String> = self.lang_map.iter().flatten()
+            .filter(|(_, mapped)| *mapped == local)
+            .map(|(remote, _)| remote.clone())
+            .collect();
+        if candidates.is_empty() {
+            vec![local.to_string()]
+        } else {
+            candidates
+        }
+    }
+}
+
+/// Error of matching translation files.
 #[derive(TeError, Debug)]
-pub enum TxYamlLoadError {
+pub enum MatchError {
+    /// A directory can not be read.
+    #[error("Can not read directory {0:?} because: {1}")]
+    ReadDir(PathBuf, #[source] std::io::Error),
+    /// Matching files of the given source file failed.
+    #[error("Fail to match target files of resource {0:?} because: {1}")]
+    Filter(String, #[source] std::io::Error),
+}
+
+/// Error of [`Filter::effective_format`].
+#[derive(TeError, Debug, PartialEq)]
+pub enum FormatError {
+    /// Neither the filter nor the settings set a format, and the extension of the given source file is unknown.
+    #[error("Fail to resolve file format of resource {0:?} because: no format is set and the file extension is unknown")]
+    Unresolved(String),
+}
+
+/// Error of [`TransifexYaml::to_tx_config_strict`].
+#[derive(TeError, Debug)]
+pub enum StrictConvertError<'a> {
+    /// These filters have no lookup table match.
+    #[error("{} resource(s) not found in the lookup table", .0.len())]
+    Missing(Vec<&'a Filter>),
+    /// See [`FormatError`].
+    #[error(transparent)]
+    Format(#[from] FormatError),
+}
+
+/// A problem found by [`TransifexYaml::validate_resource_slugs`].
+#[derive(TeError, Debug, PartialEq)]
+pub enum ResourceSlugError {
+    /// The explicit resource slug of a filter is not a valid slug.
+    #[error("resource slug {slug:?} of {source_file:?} may only contain ASCII letters, digits, '-' and '_'")]
+    Invalid {
+        /// Source file of the offending filter.
+        source_file: String,
+        /// The invalid resource slug.
+        slug: String,
+    },
+    /// Several filters share the same effective resource slug, or the same resolved full slug, see
+    /// [`TransifexYaml::duplicate_resolved_slugs`].
+    #[error("resource slug {slug:?} is used by {}", .sources.join(", "))]
+    Duplicate {
+        /// The shared slug.
+        slug: String,
+        /// Source files of the filters sharing it, in filter order.
+        sources: Vec<String>,
+    },
+}
+
+#[cfg(feature = "miette")]
+impl miette::Diagnostic for ResourceSlugError {
+    fn code<'a>(&'a self) -> Option<Box<dyn std::fmt::Display + 'a>> {
+        let code = match self {
+            Self::Invalid { .. } => "tx_yaml::invalid_resource_slug",
+            Self::Duplicate { .. } => "tx_yaml::duplicate_resource_slug",
+        };
+        Some(Box::new(code))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn std::fmt::Display + 'a>> {
+        let help = match self {
+            Self::Invalid { .. } => "replace other characters of resource_slug with '-' or '_'",
+            Self::Duplicate { .. } => "set a distinct resource_slug on all but one of these filters",
+        };
+        Some(Box::new(help))
+    }
+}
+
+/// Error kind of loading `transifex.yaml` and lookup table files.
+#[derive(TeError, Debug)]
+pub enum TxYamlLoadErrorKind {
+    /// The file doesn't exist.
     #[error("File not found")]
     FileNotFound,
-    #[error("Can not read file")]
-    ReadFile(#[from] std::io::Error),
-    #[error("Fail to deserialize file: {0}")]
-    Serde(#[from] serde_yml::Error),
-    #[error("Fail to convert from .tx/config file: {0:?}")]
-    ConvertError(#[from] TxConfigLoadError),
+    /// No `transifex.yaml` in the project, the path is the project root.
+    #[error("Neither transifex.yaml nor .tx/transifex.yaml found")]
+    NotFoundInProject,
+    /// The project root doesn't exist, the path is the project root.
+    #[error("Project root not found")]
+    ProjectRootNotFound,
+    /// The project root exists but is not a directory, the path is the project root.
+    #[error("Project root is not a directory")]
+    NotADirectory,
+    /// The file or reader can't be read.
+    #[error("Can not read content because: {0}")]
+    Read(#[source] std::io::Error),
+    /// The content is malformed.
+    #[error("Fail to deserialize content because: {0}")]
+    Parse(#[source] serde_yml::Error),
+    /// Converting from `.tx/config` failed.
+    #[error("Fail to convert from .tx/config file because: {0}")]
+    Convert(#[source] TxConfigLoadError),
 }
 
+/// Error of loading `transifex.yaml` and lookup table files.
+pub type TxYamlLoadError = ContextError<TxYamlLoadErrorKind>;
+
+#[cfg(feature = "miette")]
+impl miette::Diagnostic for TxYamlLoadError {
+    fn code<'a>(&'a self) -> Option<Box<dyn std::fmt::Display + 'a>> {
+        let code = match self.kind() {
+            TxYamlLoadErrorKind::FileNotFound => "tx_yaml::file_not_found",
+            TxYamlLoadErrorKind::NotFoundInProject => "tx_yaml::not_found_in_project",
+            TxYamlLoadErrorKind::ProjectRootNotFound => "tx_yaml::project_root_not_found",
+            TxYamlLoadErrorKind::NotADirectory => "tx_yaml::not_a_directory",
+            TxYamlLoadErrorKind::Read(_) => "tx_yaml::read",
+            TxYamlLoadErrorKind::Parse(_) => "tx_yaml::parse",
+            TxYamlLoadErrorKind::Convert(_) => "tx_yaml::convert",
+        };
+        Some(Box::new(code))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn std::fmt::Display + 'a>> {
+        let help = match self.kind() {
+            TxYamlLoadErrorKind::FileNotFound => "check the path of the file",
+            TxYamlLoadErrorKind::NotFoundInProject => "create transifex.yaml, e.g. with the txconfig2yaml subcommand if the project has a .tx/config",
+            TxYamlLoadErrorKind::ProjectRootNotFound | TxYamlLoadErrorKind::NotADirectory => "pass the directory of the project",
+            TxYamlLoadErrorKind::Read(_) => "check the permissions of the file",
+            TxYamlLoadErrorKind::Parse(_) => "fix the YAML syntax or the keys at the reported location",
+            TxYamlLoadErrorKind::Convert(_) => "fix the .tx/config file of the project",
+        };
+        Some(Box::new(help))
+    }
+}
+
+impl From<TxConfigLoadError> for TxYamlLoadError {
+    fn from(e: TxConfigLoadError) -> Self {
+        Self::new(TxYamlLoadErrorKind::Convert(e))
+    }
+}
+
+/// Check that the project root exists and is a directory, so a mistyped path fails early with a clear error.
+pub fn validate_project_root(project_root: &Path) -> Result<(), TxYamlLoadError> {
+    match fs::metadata(project_root) {
+        Ok(metadata) if metadata.is_dir() => Ok(()),
+        Ok(_) => Err(TxYamlLoadError::new(TxYamlLoadErrorKind::NotADirectory).with_path(project_root)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Err(TxYamlLoadError::new(TxYamlLoadErrorKind::ProjectRootNotFound).with_path(project_root)),
+        Err(e) => Err(TxYamlLoadError::new(TxYamlLoadErrorKind::Read(e)).with_path(project_root)),
+    }
+}
+
+/// Load `transifex.yaml` or `.tx/transifex.yaml` of the project, and return its path and content.
 pub fn try_laod_transifex_yaml_file(project_root: &PathBuf) -> Result<(PathBuf, TransifexYaml), TxYamlLoadError> {
+    validate_project_root(project_root)?;
     // try find transifex.yaml in project_root/transifex.yaml and if not found, try project_root/.tx/transifex.yaml. If still not found, return error.
     let transifex_yaml_file = project_root.join("transifex.yaml");
     if transifex_yaml_file.is_file() {
@@ -138,30 +720,270 @@ pub fn try_laod_transifex_yaml_file(project_root: &PathBuf) -> Result<(PathBuf,
         return Ok((transifex_yaml_file, tx_yaml));
     }
 
-    Err(TxYamlLoadError::FileNotFound)
+    Err(TxYamlLoadError::new(TxYamlLoadErrorKind::NotFoundInProject).with_path(project_root))
+}
+
+/// Deserialize YAML content, with the line and column of the error attached if known.
+fn parse_yaml_content<T: serde::de::DeserializeOwned>(content: &str) -> Result<T, TxYamlLoadError> {
+    serde_yml::from_str::<T>(content).map_err(yaml_parse_error)
+}
+
+pub(crate) fn yaml_parse_error(e: serde_yml::Error) -> TxYamlLoadError {
+    let location = e.location();
+    let err = TxYamlLoadError::new(TxYamlLoadErrorKind::Parse(e));
+    match location {
+        Some(location) => err.at(location.line(), Some(location.column())),
+        None => err,
+    }
+}
+
+fn read_yaml_content(mut reader: impl std::io::Read) -> Result<String, TxYamlLoadError> {
+    let mut content = String::new();
+    reader.read_to_string(&mut content).map_err(TxYamlLoadErrorKind::Read)?;
+    Ok(content)
+}
+
+pub(crate) fn read_yaml_file(yaml_file: &Path) -> Result<String, TxYamlLoadError> {
+    if !yaml_file.is_file() {
+        return Err(TxYamlLoadError::new(TxYamlLoadErrorKind::FileNotFound).with_path(yaml_file));
+    }
+    fs::read_to_string(yaml_file)
+        .map_err(TxYamlLoadErrorKind::Read)
+        .map_err(TxYamlLoadError::in_file(yaml_file))
+}
+
+/// Branch name template of generated `transifex.yaml` files.
+pub const DEFAULT_BRANCH_TEMPLATE: &str = "transifex_update_<br_unique_id>";
+
+/// Infer the target pattern of a source file by inserting `_<lang>` before its extension,
+/// e.g. `translations/app_<lang>.ts` for `translations/app.ts`.
+pub fn infer_target_pattern(source: &str) -> String {
+    let file_name_start = source.rfind('/').map_or(0, |idx| idx + 1);
+    match source[file_name_start..].rfind('.').filter(|idx| *idx > 0) {
+        Some(idx) => format!("{}_<lang>{}", &source[..file_name_start + idx], &source[file_name_start + idx..]),
+        None => format!("{source}_<lang>"),
+    }
+}
+
+impl TransifexYaml {
+    /// Create a `transifex.yaml` with a single filter for the given source file, for quick onboarding.
+    ///
+    /// The target pattern is inferred with [`infer_target_pattern`], and the format is canonicalized,
+    /// see [`canonicalize_file_format`].
+    pub fn from_single_source(source: &str, format: &str, source_lang: &str) -> TransifexYaml {
+        TransifexYaml {
+            filters: vec![Filter {
+                type_attr: "file".to_string(),
+                source: source.to_string(),
+                format: canonicalize_file_format(format),
+                source_lang: source_lang.to_string(),
+                target_pattern: infer_target_pattern(source),
+                resource_slug: None,
+                resource_name: None,
+            }],
+            settings: Settings {
+                branch_template: DEFAULT_BRANCH_TEMPLATE.to_string(),
+                lang_map: None,
+                default_format: None,
+                primary_language: None,
+            },
+        }
+    }
+
+    /// Parse `transifex.yaml` content.
+    pub fn from_str(content: &str) -> Result<Self, TxYamlLoadError> {
+        parse_yaml_content(content)
+    }
+
+    /// Parse `transifex.yaml` content from the given reader.
+    pub fn from_reader(reader: impl std::io::Read) -> Result<Self, TxYamlLoadError> {
+        Self::from_str(&read_yaml_content(reader)?)
+    }
+}
+
+impl TxResourceLookupEntry {
+    /// Parse a lookup table, i.e. a YAML list of entries.
+    pub fn table_from_str(content: &str) -> Result<Vec<Self>, TxYamlLoadError> {
+        parse_yaml_content(content)
+    }
+
+    /// Parse a lookup table from the given reader, see [`TxResourceLookupEntry::table_from_str`].
+    pub fn table_from_reader(reader: impl std::io::Read) -> Result<Vec<Self>, TxYamlLoadError> {
+        Self::table_from_str(&read_yaml_content(reader)?)
+    }
+
+    /// Whether this entry belongs to the given GitHub repository. Repositories are compared
+    /// case-insensitively, ignoring a `.git` suffix and trailing slashes, see [`normalize_repository`].
+    pub fn matches_repository(&self, github_repository: &str) -> bool {
+        normalize_repository(&self.repository) == normalize_repository(github_repository)
+    }
+
+    /// Entries of the lookup table which belong to the given GitHub repository, see [`Self::matches_repository`].
+    pub fn filter_entries_for_repo<'a>(lookup_table: &'a [Self], github_repository: &str) -> Vec<&'a Self> {
+        lookup_table.iter().filter(|entry| entry.matches_repository(github_repository)).collect()
+    }
+}
+
+/// Normalize a GitHub repository like `Owner/Repo.git/` for comparison, i.e. `owner/repo`.
+pub fn normalize_repository(repository: &str) -> String {
+    let repository = repository.trim().trim_end_matches('/');
+    let repository = repository.strip_suffix(".git").unwrap_or(repository);
+    repository.trim_end_matches('/').to_lowercase()
 }
 
+/// Load the given `transifex.yaml` file.
 pub fn load_tx_yaml_file(transifex_yaml_file: &PathBuf) -> Result<TransifexYaml, TxYamlLoadError> {
-    if !transifex_yaml_file.is_file() {
-        return Err(TxYamlLoadError::FileNotFound);
+    let source_content = read_yaml_file(transifex_yaml_file)?;
+    TransifexYaml::from_str(&source_content).with_path(transifex_yaml_file)
+}
+
+/// Same as [`load_tx_yaml_file`], but reads the file with `tokio::fs` so it doesn't block the async runtime.
+///
+/// Parsing is still done on the calling task, `transifex.yaml` files are small.
+#[cfg(feature = "async")]
+pub async fn load_tx_yaml_file_async(transifex_yaml_file: &Path) -> Result<TransifexYaml, TxYamlLoadError> {
+    let source_content = match tokio::fs::read_to_string(transifex_yaml_file).await {
+        Ok(source_content) => source_content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(TxYamlLoadError::new(TxYamlLoadErrorKind::FileNotFound).with_path(transifex_yaml_file));
+        },
+        Err(e) => return Err(TxYamlLoadError::new(TxYamlLoadErrorKind::Read(e)).with_path(transifex_yaml_file)),
+    };
+    TransifexYaml::from_str(&source_content).with_path(transifex_yaml_file)
+}
+
+/// Load the `transifex.yaml` of the project and the given lookup table file, and return the resolved
+/// full slugs without placeholders, e.g. for shell completion, see [`TransifexYaml::resource_slugs`].
+pub fn list_resource_slugs(project_root: &PathBuf, github_repository: &str, lookup_table_file: &Path) -> Result<Vec<String>, TxYamlLoadError> {
+    let (_, tx_yaml) = try_laod_transifex_yaml_file(project_root)?;
+    let lookup_table = load_lookup_table_file(lookup_table_file)?;
+    Ok(tx_yaml.resource_slugs(github_repository, &lookup_table, false))
+}
+
+/// Load the given lookup table file, see [`TxResourceLookupEntry::table_from_str`].
+pub fn load_lookup_table_file(lookup_table_file: &Path) -> Result<Vec<TxResourceLookupEntry>, TxYamlLoadError> {
+    let source_content = read_yaml_file(lookup_table_file)?;
+    TxResourceLookupEntry::table_from_str(&source_content).with_path(lookup_table_file)
+}
+
+/// Normalize a project-relative path for comparison: trims whitespaces, uses `/` as separator,
+/// and removes leading `./` and repeated separators.
+pub fn normalize_source_path(path: &str) -> String {
+    path.trim()
+        .replace('\\', "/")
+        .split('/')
+        .filter(|part| !part.is_empty() && *part != ".")
+        .collect::<Vec<&str>>()
+        .join("/")
+}
+
+/// Transifex file formats (i18n types) which are accepted case-insensitively.
+pub const KNOWN_FILE_FORMATS: &[&str] = &[
+    "ANDROID", "CHROME", "DESKTOP", "HTML", "INI", "KEYVALUEJSON", "PO", "PROPERTIES",
+    "QT", "STRINGS", "STRINGSDICT", "STRUCTURED_JSON", "XLIFF", "YAML_GENERIC", "YML",
+];
+
+/// Canonicalize a known file format to its uppercase form, e.g. `qt` or `Qt` to `QT`.
+/// Unknown formats are returned as-is.
+pub fn canonicalize_file_format(format: &str) -> String {
+    let format = format.trim();
+    match KNOWN_FILE_FORMATS.iter().find(|known| known.eq_ignore_ascii_case(format)) {
+        Some(known) => known.to_string(),
+        None => format.to_string(),
     }
-    let source_content = fs::read_to_string(&transifex_yaml_file)?;
-    Ok(serde_yml::from_str::<TransifexYaml>(source_content.as_str())?)
 }
 
-fn create_filter_pattern(pattern: &str) -> Option<Regex> {
-    let parts: Vec<&str> = pattern.split("<lang>").collect();
-    if parts.len() != 2 {
-        return None;
+/// Same as [`canonicalize_file_format`], but reuses the given string if it's canonical already.
+fn into_canonical_file_format(format: String) -> String {
+    let trimmed = format.trim();
+    let canonical = KNOWN_FILE_FORMATS.iter().copied().find(|known| known.eq_ignore_ascii_case(trimmed)).unwrap_or(trimmed);
+    if canonical == format { format } else { canonical.to_string() }
+}
+
+/// File format of a resource, i.e. `file_format` of a filter or `type` of a `.tx/config` resource section.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileFormat {
+    /// Qt Linguist TS.
+    Qt,
+    /// GNU Gettext PO.
+    Po,
+    /// Freedesktop desktop entry.
+    Desktop,
+    /// Key-value JSON.
+    KeyValueJson,
+    /// Structured JSON.
+    StructuredJson,
+    /// Any other format, kept as-is so conversions round-trip.
+    Other(String),
+}
+
+impl FileFormat {
+    /// Parse a Transifex file type, see [`canonicalize_file_format`].
+    pub fn from_tx_type(tx_type: &str) -> FileFormat {
+        match canonicalize_file_format(tx_type).as_str() {
+            "QT" => Self::Qt,
+            "PO" => Self::Po,
+            "DESKTOP" => Self::Desktop,
+            "KEYVALUEJSON" => Self::KeyValueJson,
+            "STRUCTURED_JSON" => Self::StructuredJson,
+            other => Self::Other(other.to_string()),
+        }
     }
 
-    let regex_pattern = format!(
-        r#"^{}([a-z_A-Z]{{2,6}}){}$"#,
-        regex::escape(parts[0]),
-        regex::escape(parts[1])
-    );
+    /// Infer the format from the extension of the given file path, e.g. [`Self::Qt`] for `app.ts`.
+    ///
+    /// JSON files are assumed to be [`Self::KeyValueJson`], since both JSON formats share the extension.
+    pub fn from_extension(path: &str) -> Option<FileFormat> {
+        let (_, extension) = path.rsplit('/').next().unwrap_or(path).rsplit_once('.')?;
+        match extension.to_ascii_lowercase().as_str() {
+            "ts" => Some(Self::Qt),
+            "po" | "pot" => Some(Self::Po),
+            "desktop" => Some(Self::Desktop),
+            "json" => Some(Self::KeyValueJson),
+            _ => None,
+        }
+    }
 
-    Regex::new(&regex_pattern).ok()
+    /// Get the Transifex file type.
+    pub fn to_tx_type(&self) -> String {
+        match self {
+            Self::Qt => "QT".to_string(),
+            Self::Po => "PO".to_string(),
+            Self::Desktop => "DESKTOP".to_string(),
+            Self::KeyValueJson => "KEYVALUEJSON".to_string(),
+            Self::StructuredJson => "STRUCTURED_JSON".to_string(),
+            Self::Other(tx_type) => tx_type.clone(),
+        }
+    }
+}
+
+/// Guess the format of the translation files directly under `dir`, by the most common extension,
+/// e.g. to prefill the format of a new filter.
+///
+/// Only QT, PO and JSON files count, see [`FileFormat::from_extension`]. Ties resolve in that order.
+/// Returns `None` if the directory can't be read or contains none of them.
+pub fn detect_dominant_format(dir: &Path) -> Option<FileFormat> {
+    let mut counts = [(FileFormat::Qt, 0usize), (FileFormat::Po, 0), (FileFormat::KeyValueJson, 0)];
+    for entry in fs::read_dir(dir).ok()?.flatten() {
+        if !entry.file_type().is_ok_and(|file_type| file_type.is_file()) {
+            continue;
+        }
+        let Some(format) = entry.file_name().to_str().and_then(FileFormat::from_extension) else {
+            continue;
+        };
+        if let Some((_, count)) = counts.iter_mut().find(|(known, _)| *known == format) {
+            *count += 1;
+        }
+    }
+    // `max_by_key` returns the last maximum, so reverse to prefer the first one on ties
+    counts.into_iter().rev()
+        .filter(|(_, count)| *count > 0)
+        .max_by_key(|(_, count)| *count)
+        .map(|(format, _)| format)
+}
+
+fn deserialize_file_format<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    String::deserialize(deserializer).map(|format| canonicalize_file_format(&format))
 }
 
 #[cfg(test)]
@@ -179,9 +1001,30 @@ settings:
   pr_branch_name: transifex_update_<br_unique_id>
 "#;
 
+    /// The `settings` section of [`yaml_with_filters`].
+    pub const TEST_SETTINGS: &str = "settings:\n  pr_branch_name: transifex_update_<br_unique_id>\n";
+
+    /// A `transifex.yaml` with a QT filter for each `(source file, target pattern)` pair, in order.
+    pub fn yaml_with_filters(filters: &[(&str, &str)]) -> String {
+        let entries: String = filters.iter()
+            .map(|(source, target_pattern)| format!("\n  - filter_type: file\n    source_file: {source}\n    file_format: QT\n    source_language: en_US\n    translation_files_expression: {target_pattern}\n"))
+            .collect();
+        format!("filters:{entries}{TEST_SETTINGS}")
+    }
+
+    /// Create an empty directory under system temp dir for tests which need real files.
+    pub fn create_test_project_dir(name: &str) -> PathBuf {
+        let project_root = std::env::temp_dir().join("deepin-translation-utils-tests").join(name);
+        if project_root.exists() {
+            fs::remove_dir_all(&project_root).unwrap();
+        }
+        fs::create_dir_all(&project_root).unwrap();
+        project_root
+    }
+
     #[test]
     fn tst_parse_tx_yaml_content() {
-        let tx_yaml: TransifexYaml = serde_yml::from_str::<TransifexYaml>(TEST_TX_YAML_CONTENT).unwrap();
+        let tx_yaml: TransifexYaml = TransifexYaml::from_str(TEST_TX_YAML_CONTENT).unwrap();
         assert_eq!(tx_yaml.filters.len(), 1);
         assert_eq!(tx_yaml.filters[0].type_attr, "file");
         assert_eq!(tx_yaml.filters[0].source, "shell-launcher-applet/translations/org.deepin.ds.dock.launcherapplet.ts");
@@ -191,13 +1034,578 @@ settings:
     }
 
     #[test]
-    fn test_pathbuf() {
-        let path = PathBuf::from("/example/sample_<lang>.ts");
-        assert_eq!(path.file_name(), Some(std::ffi::OsStr::new("sample_<lang>.ts")));
-        let pattern = create_filter_pattern(path.to_str().unwrap()).unwrap();
-        let matched = pattern.captures("/example/sample_zh_CN.ts").and_then(|caps| caps.get(1)).map(|m| {
-            m.as_str().to_string()
-        });
-        assert_eq!(matched, Some("zh_CN".to_string()));
+    fn tst_load_missing_tx_yaml_file() {
+        let project_root = create_test_project_dir("tst_load_missing_tx_yaml_file");
+        let transifex_yaml_file = project_root.join("transifex.yaml");
+        let err = load_tx_yaml_file(&transifex_yaml_file).unwrap_err();
+        assert!(matches!(err.kind(), TxYamlLoadErrorKind::FileNotFound));
+        assert_eq!(err.to_string(), format!("{}: File not found", transifex_yaml_file.display()));
+    }
+
+    #[test]
+    fn tst_validate_project_root() {
+        let project_root = create_test_project_dir("tst_validate_project_root");
+        assert!(validate_project_root(&project_root).is_ok());
+
+        let missing_root = project_root.join("missing");
+        let err = try_laod_transifex_yaml_file(&missing_root).unwrap_err();
+        assert!(matches!(err.kind(), TxYamlLoadErrorKind::ProjectRootNotFound));
+        assert_eq!(err.path(), Some(missing_root.as_path()));
+
+        let file_root = project_root.join("transifex.yaml");
+        fs::write(&file_root, TEST_TX_YAML_CONTENT).unwrap();
+        let err = try_laod_transifex_yaml_file(&file_root).unwrap_err();
+        assert!(matches!(err.kind(), TxYamlLoadErrorKind::NotADirectory));
+        assert_eq!(err.to_string(), format!("{}: Project root is not a directory", file_root.display()));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn tst_load_tx_yaml_file_async() {
+        let project_root = create_test_project_dir("tst_load_tx_yaml_file_async");
+        let transifex_yaml_file = project_root.join("transifex.yaml");
+        fs::write(&transifex_yaml_file, TEST_TX_YAML_CONTENT).unwrap();
+        let tx_yaml = load_tx_yaml_file_async(&transifex_yaml_file).await.unwrap();
+        assert_eq!(tx_yaml.filters.len(), 1);
+
+        let err = load_tx_yaml_file_async(&project_root.join("missing.yaml")).await.unwrap_err();
+        assert!(matches!(err.kind(), TxYamlLoadErrorKind::FileNotFound));
+    }
+
+    #[cfg(feature = "miette")]
+    #[test]
+    fn tst_miette_diagnostic() {
+        use miette::Diagnostic;
+        let err = TransifexYaml::from_str("filters: 1").unwrap_err();
+        assert_eq!(err.code().unwrap().to_string(), "tx_yaml::parse");
+        assert!(err.help().is_some());
+        let err = ResourceSlugError::Invalid { source_file: "app.ts".to_string(), slug: "a b".to_string() };
+        assert_eq!(err.code().unwrap().to_string(), "tx_yaml::invalid_resource_slug");
+        assert!(err.help().unwrap().to_string().contains("resource_slug"));
+    }
+
+    #[test]
+    fn tst_load_from_reader() {
+        let tx_yaml = TransifexYaml::from_reader(TEST_TX_YAML_CONTENT.as_bytes()).unwrap();
+        assert_eq!(tx_yaml.filters.len(), 1);
+        let err = TransifexYaml::from_str("filters: 42").unwrap_err();
+        assert!(matches!(err.kind(), TxYamlLoadErrorKind::Parse(_)));
+        assert_eq!(err.path(), None);
+
+        let project_root = create_test_project_dir("tst_load_from_reader");
+        let transifex_yaml_file = project_root.join("transifex.yaml");
+        std::fs::write(&transifex_yaml_file, "filters: 42").unwrap();
+        let err = load_tx_yaml_file(&transifex_yaml_file).unwrap_err();
+        assert_eq!(err.path(), Some(transifex_yaml_file.as_path()));
+        assert_eq!(err.location().line, Some(1));
+        assert!(err.to_string().starts_with(&format!("{}:1:", transifex_yaml_file.display())));
+
+        let lookup_table = TxResourceLookupEntry::table_from_reader(r#"
+- repository: linuxdeepin/dde-shell
+  branch: master
+  resource: translations/dde-shell.ts
+  transifex_resource_id: o:linuxdeepin:p:deepin-desktop-environment:r:dde-shell
+"#.as_bytes()).unwrap();
+        assert_eq!(lookup_table.len(), 1);
+        assert_eq!(lookup_table[0].branch, "master");
+    }
+
+    #[test]
+    fn tst_merge_into_tx_config() {
+        use crate::transifex::tx_config_file::tests::TEST_TX_CONFIG_CONTENT;
+
+        let tx_yaml = TransifexYaml::from_str(TEST_TX_YAML_CONTENT).unwrap();
+        let mut tx_config = TxConfig::from_str(TEST_TX_CONFIG_CONTENT).unwrap();
+        let lookup_table = vec![TxResourceLookupEntry {
+            repository: "linuxdeepin/dde-shell".to_string(),
+            branch: "master".to_string(),
+            resource: tx_yaml.filters[0].source.clone(),
+            transifex_resource_id: "o:linuxdeepin:p:deepin-desktop-environment:r:launcherapplet".to_string(),
+        }];
+        tx_yaml.merge_into_tx_config(&mut tx_config, "linuxdeepin/dde-shell", &lookup_table).unwrap();
+        assert_eq!(tx_config.resource_sections.len(), 3);
+        // manually-added resources survive the merge
+        assert_eq!(tx_config.resource_sections[0].resource_full_slug, "o:linuxdeepin:p:deepin-desktop-environment:r:dde-control-center");
+        assert_eq!(tx_config.resource_sections[1].resource_full_slug, "o:linuxdeepin:p:deepin-desktop-environment:r:dde-control-center-desktop");
+        assert_eq!(tx_config.resource_sections[2].resource_full_slug, "o:linuxdeepin:p:deepin-desktop-environment:r:launcherapplet");
+
+        // merging again updates the section in place, keeping hand-edited keys
+        tx_config.resource_sections[2].lang_map = Some("zh_CN: zh-Hans".to_string());
+        tx_config.resource_sections[2].keep_translations = Some(true);
+        tx_config.resource_sections[2].file_filter = "stale_<lang>.ts".to_string();
+        tx_yaml.merge_into_tx_config(&mut tx_config, "linuxdeepin/dde-shell", &[]).unwrap();
+        assert_eq!(tx_config.resource_sections.len(), 3);
+        let merged = &tx_config.resource_sections[2];
+        assert_eq!(merged.resource_full_slug, "o:linuxdeepin:p:deepin-desktop-environment:r:launcherapplet");
+        assert_eq!(merged.file_filter, tx_yaml.filters[0].target_pattern);
+        assert_eq!(merged.lang_map.as_deref(), Some("zh_CN: zh-Hans"));
+        assert_eq!(merged.keep_translations, Some(true));
+        assert!(tx_config.to_str().contains("lang_map = zh_CN: zh-Hans"));
+    }
+
+    #[test]
+    fn tst_lookup_entry_matches_repository() {
+        let lookup_table = TxResourceLookupEntry::table_from_str(r#"
+- repository: LinuxDeepin/dde-shell.git
+  branch: master
+  resource: translations/app.ts
+  transifex_resource_id: o:linuxdeepin:p:deepin-desktop-environment:r:app
+- repository: linuxdeepin/dde-control-center
+  branch: master
+  resource: translations/app.ts
+  transifex_resource_id: o:linuxdeepin:p:deepin-desktop-environment:r:dcc
+"#).unwrap();
+        assert!(lookup_table[0].matches_repository("linuxdeepin/dde-shell"));
+        assert!(lookup_table[0].matches_repository("linuxdeepin/DDE-Shell/"));
+        assert!(!lookup_table[0].matches_repository("linuxdeepin/dde-shell-extra"));
+        assert!(lookup_table[1].matches_repository("LinuxDeepin/DDE-Control-Center.git"));
+        let entries = TxResourceLookupEntry::filter_entries_for_repo(&lookup_table, "linuxdeepin/dde-shell");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].transifex_resource_id, "o:linuxdeepin:p:deepin-desktop-environment:r:app");
+
+        // lookups use the normalized comparison as well
+        let tx_yaml = TransifexYaml::from_str(&yaml_with_filters(&[("translations/app.ts", "translations/app_<lang>.ts")])).unwrap();
+        assert_eq!(tx_yaml.resource_slugs("linuxdeepin/dde-shell", &lookup_table, false), vec!["o:linuxdeepin:p:deepin-desktop-environment:r:app".to_string()]);
+    }
+
+    #[test]
+    fn tst_to_tx_config_basename_lookup() {
+        let tx_yaml = TransifexYaml::from_str(TEST_TX_YAML_CONTENT).unwrap();
+        let lookup_entry = |resource: &str, resource_id: &str| TxResourceLookupEntry {
+            repository: "linuxdeepin/dde-shell".to_string(),
+            branch: "master".to_string(),
+            resource: resource.to_string(),
+            transifex_resource_id: resource_id.to_string(),
+        };
+        let tx_config = tx_yaml.to_tx_config("linuxdeepin/dde-shell".to_string(), vec![
+            lookup_entry("org.deepin.ds.dock.launcherapplet.ts", "o:linuxdeepin:p:deepin-desktop-environment:r:by-basename"),
+        ]);
+        assert_eq!(tx_config.resource_sections[0].resource_full_slug, "o:linuxdeepin:p:deepin-desktop-environment:r:by-basename");
+
+        // full path matches win over basename matches
+        let tx_config = tx_yaml.to_tx_config("linuxdeepin/dde-shell".to_string(), vec![
+            lookup_entry("org.deepin.ds.dock.launcherapplet.ts", "o:linuxdeepin:p:deepin-desktop-environment:r:by-basename"),
+            lookup_entry(&tx_yaml.filters[0].source, "o:linuxdeepin:p:deepin-desktop-environment:r:by-path"),
+        ]);
+        assert_eq!(tx_config.resource_sections[0].resource_full_slug, "o:linuxdeepin:p:deepin-desktop-environment:r:by-path");
+    }
+
+    #[test]
+    fn tst_into_tx_config() {
+        // the format of the PO filter is inferred from its extension
+        let content = yaml_with_filters(&[
+            ("translations/app.ts", "translations/app_<lang>.ts"),
+            ("po/dock.po", "po/<lang>.po"),
+        ]).replace("po/dock.po\n    file_format: QT\n", "po/dock.po\n");
+        let mut tx_yaml = TransifexYaml::from_str(&format!("{content}  lang_map: {{zh_CN: zh-Hans}}\n")).unwrap();
+        tx_yaml.filters[0].format = "qt".to_string();
+        let borrowed = tx_yaml.to_tx_config("linuxdeepin/dde-shell".to_string(), Vec::new());
+        let consumed = tx_yaml.into_tx_config("linuxdeepin/dde-shell", &[]).unwrap();
+        assert_eq!(consumed.to_str(), borrowed.to_str());
+        let types: Vec<&str> = consumed.resource_sections.iter().map(|section| section.type_attr.as_str()).collect();
+        assert_eq!(types, vec!["QT", "PO"]);
+    }
+
+    #[test]
+    fn tst_to_tx_config_with_fallback_template() {
+        let tx_yaml = TransifexYaml::from_str(TEST_TX_YAML_CONTENT).unwrap();
+        let tx_config = tx_yaml.to_tx_config_with_fallback_template("linuxdeepin/dde-shell".to_string(), vec![], "o:linuxdeepin:p:dde-shell:r:<resource>").unwrap();
+        assert_eq!(tx_config.resource_sections[0].resource_full_slug, "o:linuxdeepin:p:dde-shell:r:shell-launcher-applet_translations_org_deepin_ds_dock_launcherapplet");
+    }
+
+    #[test]
+    fn tst_to_tx_config_strict() {
+        let tx_yaml = TransifexYaml::from_str(TEST_TX_YAML_CONTENT).unwrap();
+        let Err(StrictConvertError::Missing(missing)) = tx_yaml.to_tx_config_strict("linuxdeepin/dde-shell", &[]) else {
+            panic!("filters without lookup table match should fail");
+        };
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].source, tx_yaml.filters[0].source);
+
+        let lookup_table = vec![TxResourceLookupEntry {
+            repository: "linuxdeepin/dde-shell".to_string(),
+            branch: "master".to_string(),
+            resource: tx_yaml.filters[0].source.clone(),
+            transifex_resource_id: "o:linuxdeepin:p:deepin-desktop-environment:r:dde-shell".to_string(),
+        }];
+        let tx_config = tx_yaml.to_tx_config_strict("linuxdeepin/dde-shell", &lookup_table).unwrap();
+        assert_eq!(tx_config.resource_sections[0].resource_full_slug, "o:linuxdeepin:p:deepin-desktop-environment:r:dde-shell");
+    }
+
+    #[test]
+    fn tst_resource_slug_and_name() {
+        let tx_yaml = TransifexYaml::from_str(&TEST_TX_YAML_CONTENT.replace("    translation_files_expression:",
+            "    resource_slug: dock-launcher-applet\n    resource_name: Dock — Launcher Applet\n    translation_files_expression:")).unwrap();
+        let filter = &tx_yaml.filters[0];
+        assert_eq!(filter.effective_resource_slug(), "dock-launcher-applet");
+        assert_eq!(filter.effective_resource_name(), "Dock — Launcher Applet");
+        let tx_config = tx_yaml.to_tx_config_with_fallback_template("linuxdeepin/dde-shell".to_string(), Vec::new(), "o:linuxdeepin:p:dde-shell:r:<resource>").unwrap();
+        assert_eq!(tx_config.resource_sections[0].resource_full_slug, "o:linuxdeepin:p:dde-shell:r:dock-launcher-applet");
+        assert_eq!(tx_config.resource_sections[0].resource_name.as_deref(), Some("Dock — Launcher Applet"));
+        assert!(tx_yaml.validate_resource_slugs().is_ok());
+
+        // unset fields are not written, so upstream tooling keeps accepting the file
+        let tx_yaml = TransifexYaml::from_str(TEST_TX_YAML_CONTENT).unwrap();
+        assert_eq!(tx_yaml.filters[0].effective_resource_slug(), "shell-launcher-applet_translations_org_deepin_ds_dock_launcherapplet");
+        assert!(!serde_yml::to_string(&tx_yaml).unwrap().contains("resource_"));
+
+        let tx_yaml = TransifexYaml::from_str(r#"
+filters:
+  - filter_type: file
+    source_file: translations/app.ts
+    file_format: QT
+    source_language: en_US
+    translation_files_expression: translations/app_<lang>.ts
+    resource_slug: app.ts
+  - filter_type: file
+    source_file: translations/app.po
+    file_format: PO
+    source_language: en_US
+    translation_files_expression: translations/app_<lang>.po
+    resource_slug: translations_app
+settings:
+  pr_branch_name: transifex_update_<br_unique_id>
+"#).unwrap();
+        assert_eq!(tx_yaml.validate_resource_slugs(), Err(vec![ResourceSlugError::Invalid {
+            source_file: "translations/app.ts".to_string(),
+            slug: "app.ts".to_string(),
+        }]));
+        let mut tx_yaml = tx_yaml;
+        tx_yaml.filters[0].resource_slug = None;
+        assert_eq!(tx_yaml.validate_resource_slugs(), Err(vec![ResourceSlugError::Duplicate {
+            slug: "translations_app".to_string(),
+            sources: vec!["translations/app.ts".to_string(), "translations/app.po".to_string()],
+        }]));
+    }
+
+    #[test]
+    fn tst_list_resource_slugs() {
+        let project_root = create_test_project_dir("tst_list_resource_slugs");
+        let content = yaml_with_filters(&[("translations/app.ts", "translations/app_<lang>.ts"), ("shell-launcher-applet/translations/org.deepin.ds.dock.launcherapplet.ts", "shell-launcher-applet/translations/org.deepin.ds.dock.launcherapplet_<lang>.ts")]);
+        fs::write(project_root.join("transifex.yaml"), content).unwrap();
+        let lookup_table_file = project_root.join("lookup.yaml");
+        fs::write(&lookup_table_file, r#"- repository: linuxdeepin/dde-shell
+  branch: master
+  resource: org.deepin.ds.dock.launcherapplet.ts
+  transifex_resource_id: o:linuxdeepin:p:deepin-desktop-environment:r:dde-shell-launcherapplet
+"#).unwrap();
+        assert_eq!(list_resource_slugs(&project_root, "linuxdeepin/dde-shell", &lookup_table_file).unwrap(), vec![
+            "o:linuxdeepin:p:deepin-desktop-environment:r:dde-shell-launcherapplet".to_string(),
+        ]);
+        let tx_yaml = load_tx_yaml_file(&project_root.join("transifex.yaml")).unwrap();
+        let lookup_table = load_lookup_table_file(&lookup_table_file).unwrap();
+        assert_eq!(tx_yaml.resource_slugs("linuxdeepin/dde-shell", &lookup_table, true), vec![
+            UNKNOWN_RESOURCE_FULL_SLUG.to_string(),
+            "o:linuxdeepin:p:deepin-desktop-environment:r:dde-shell-launcherapplet".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn tst_duplicate_resolved_slugs() {
+        let content = yaml_with_filters(&[("translations/app.ts", "translations/app_<lang>.ts"), ("shell-launcher-applet/translations/org.deepin.ds.dock.launcherapplet.ts", "shell-launcher-applet/translations/org.deepin.ds.dock.launcherapplet_<lang>.ts")]);
+        let tx_yaml = TransifexYaml::from_str(&content).unwrap();
+        let lookup_entry = |resource: &str| TxResourceLookupEntry {
+            repository: "linuxdeepin/dde-shell".to_string(),
+            branch: "master".to_string(),
+            resource: resource.to_string(),
+            transifex_resource_id: "o:linuxdeepin:p:deepin-desktop-environment:r:dde-shell".to_string(),
+        };
+        let lookup_table = vec![
+            lookup_entry("translations/app.ts"),
+            lookup_entry("org.deepin.ds.dock.launcherapplet.ts"),
+        ];
+        assert_eq!(tx_yaml.duplicate_resolved_slugs("linuxdeepin/dde-shell", &lookup_table, None), vec![ResourceSlugError::Duplicate {
+            slug: "o:linuxdeepin:p:deepin-desktop-environment:r:dde-shell".to_string(),
+            sources: vec!["translations/app.ts".to_string(), "shell-launcher-applet/translations/org.deepin.ds.dock.launcherapplet.ts".to_string()],
+        }]);
+        // unknown resources share the placeholder slug
+        assert!(tx_yaml.duplicate_resolved_slugs("linuxdeepin/dde-shell", &[], None).is_empty());
+        assert!(tx_yaml.duplicate_resolved_slugs("linuxdeepin/dde-shell", &lookup_table[..1], None).is_empty());
+    }
+
+    #[test]
+    fn tst_canonicalize_file_format() {
+        let tx_yaml = TransifexYaml::from_str(&TEST_TX_YAML_CONTENT.replace("file_format: QT", "file_format: qt")).unwrap();
+        assert_eq!(tx_yaml.filters[0].format, "QT");
+        assert_eq!(canonicalize_file_format("Structured_Json"), "STRUCTURED_JSON");
+        assert_eq!(canonicalize_file_format("MyFormat"), "MyFormat");
+    }
+
+    #[test]
+    fn tst_file_format_tx_type() {
+        assert_eq!(FileFormat::from_tx_type("QT"), FileFormat::Qt);
+        assert_eq!(FileFormat::from_tx_type("po"), FileFormat::Po);
+        assert_eq!(FileFormat::from_tx_type("FOO"), FileFormat::Other("FOO".to_string()));
+        for tx_type in ["QT", "PO", "FOO"] {
+            assert_eq!(FileFormat::from_tx_type(tx_type).to_tx_type(), tx_type);
+        }
+
+        use crate::transifex::tx_config_file::tests::TEST_TX_CONFIG_CONTENT;
+        let tx_config = TxConfig::from_str(&TEST_TX_CONFIG_CONTENT.replace("type = QT", "type = FOO")).unwrap();
+        let tx_yaml = tx_config.to_transifex_yaml();
+        assert_eq!(tx_yaml.filters[0].file_format(), FileFormat::Other("FOO".to_string()));
+        assert_eq!(tx_yaml.to_tx_config(String::new(), vec![]).resource_sections[0].type_attr, "FOO");
+    }
+
+    #[test]
+    fn tst_primary_language() {
+        let content = TEST_TX_YAML_CONTENT.replace("settings:\n", "settings:\n  primary_language: zh_CN\n");
+        let tx_yaml = TransifexYaml::from_str(&content).unwrap();
+        assert_eq!(tx_yaml.settings.primary_language.as_deref(), Some("zh_CN"));
+        let tx_yaml = TransifexYaml::from_str(&serde_yml::to_string(&tx_yaml).unwrap()).unwrap();
+        assert_eq!(tx_yaml.settings.primary_language.as_deref(), Some("zh_CN"));
+        assert!(!tx_yaml.to_tx_config(String::new(), vec![]).to_str().contains("zh_CN"));
+
+        let tx_yaml = TransifexYaml::from_str(TEST_TX_YAML_CONTENT).unwrap();
+        assert_eq!(tx_yaml.settings.primary_language, None);
+        assert!(!serde_yml::to_string(&tx_yaml).unwrap().contains("primary_language"));
+    }
+
+    #[test]
+    fn tst_effective_format() {
+        let mut tx_yaml = TransifexYaml::from_str(TEST_TX_YAML_CONTENT).unwrap();
+        // explicit format
+        assert_eq!(tx_yaml.filters[0].effective_format(&tx_yaml.settings), Ok(FileFormat::Qt));
+        // inferred from the extension
+        tx_yaml.filters[0].format = String::new();
+        assert_eq!(tx_yaml.filters[0].effective_format(&tx_yaml.settings), Ok(FileFormat::Qt));
+        // settings default wins over the extension
+        tx_yaml.settings.default_format = Some("po".to_string());
+        assert_eq!(tx_yaml.filters[0].effective_format(&tx_yaml.settings), Ok(FileFormat::Po));
+        assert_eq!(tx_yaml.to_tx_config(String::new(), vec![]).resource_sections[0].type_attr, "PO");
+        // nothing resolves
+        tx_yaml.settings.default_format = None;
+        tx_yaml.filters[0].source = "shell-launcher-applet/translations/README".to_string();
+        assert_eq!(tx_yaml.filters[0].effective_format(&tx_yaml.settings), Err(FormatError::Unresolved(tx_yaml.filters[0].source.clone())));
+        assert_eq!(tx_yaml.try_to_tx_config("", &[]).err(), Some(FormatError::Unresolved(tx_yaml.filters[0].source.clone())));
+        assert_eq!(tx_yaml.to_tx_config(String::new(), vec![]).resource_sections[0].type_attr, "");
+        let mut tx_config = TxConfig::default();
+        assert!(tx_yaml.merge_into_tx_config(&mut tx_config, "linuxdeepin/dde-shell", &[]).is_err());
+        assert!(tx_config.resource_sections.is_empty());
+
+        let tx_yaml = TransifexYaml::from_str(&TEST_TX_YAML_CONTENT.replace("file_format: QT", "").replace("settings:", "settings:\n  default_file_format: desktop")).unwrap();
+        assert_eq!(tx_yaml.filters[0].format, "");
+        assert_eq!(tx_yaml.filters[0].effective_format(&tx_yaml.settings), Ok(FileFormat::Desktop));
+    }
+
+    #[test]
+    fn tst_filter_for_source() {
+        let tx_yaml = TransifexYaml::from_str(TEST_TX_YAML_CONTENT).unwrap();
+        let source = "shell-launcher-applet/translations/org.deepin.ds.dock.launcherapplet.ts";
+        let filter = tx_yaml.filter_for_source(source).unwrap();
+        assert_eq!(filter.source, source);
+        let filter = tx_yaml.filter_for_source(r".\shell-launcher-applet\translations\org.deepin.ds.dock.launcherapplet.ts").unwrap();
+        assert_eq!(filter.source, source);
+        assert!(tx_yaml.filter_for_source("translations/org.deepin.ds.dock.launcherapplet.ts").is_none());
+    }
+
+    #[test]
+    fn tst_slug_for_file() {
+        let project_root = create_test_project_dir("tst_slug_for_file");
+        let tx_yaml = TransifexYaml::from_str(&yaml_with_filters(&[
+            ("translations/app.ts", "translations/app_<lang>.ts"),
+            ("translations/desktop.ts", "translations/desktop_<lang>.ts"),
+        ])).unwrap();
+        let lookup_table = vec![TxResourceLookupEntry {
+            repository: "linuxdeepin/dde-shell".to_string(),
+            branch: "master".to_string(),
+            resource: "translations/desktop.ts".to_string(),
+            transifex_resource_id: "o:linuxdeepin:p:deepin-desktop-environment:r:desktop".to_string(),
+        }];
+        let slug_for_file = |file: PathBuf| tx_yaml.slug_for_file("linuxdeepin/dde-shell", &lookup_table, &project_root, &file);
+        assert_eq!(slug_for_file(project_root.join("translations/desktop_zh_CN.ts")).as_deref(), Some("o:linuxdeepin:p:deepin-desktop-environment:r:desktop"));
+        assert_eq!(slug_for_file(PathBuf::from("./translations/desktop.ts")).as_deref(), Some("o:linuxdeepin:p:deepin-desktop-environment:r:desktop"));
+        // owned by a filter missing in the lookup table
+        assert_eq!(slug_for_file(PathBuf::from("translations/app_zh_CN.ts")), None);
+        // owned by no filter
+        assert_eq!(slug_for_file(PathBuf::from("translations/other_zh_CN.ts")), None);
+        assert_eq!(slug_for_file(PathBuf::from("translations/nested/desktop_zh_CN.ts")), None);
+    }
+
+    #[test]
+    fn tst_detect_dominant_format() {
+        let project_root = create_test_project_dir("tst_detect_dominant_format");
+        assert_eq!(detect_dominant_format(&project_root.join("missing")), None);
+        assert_eq!(detect_dominant_format(&project_root), None);
+        for file_name in ["app_zh_CN.ts", "app_ja.ts", "app_de.ts", "app.po", "app.pot", "README.md"] {
+            fs::write(project_root.join(file_name), "").unwrap();
+        }
+        // directories don't count
+        fs::create_dir_all(project_root.join("extra.po")).unwrap();
+        assert_eq!(detect_dominant_format(&project_root), Some(FileFormat::Qt));
+        // ties prefer QT
+        fs::write(project_root.join("app_fr.po"), "").unwrap();
+        assert_eq!(detect_dominant_format(&project_root), Some(FileFormat::Qt));
+        fs::write(project_root.join("app_it.po"), "").unwrap();
+        assert_eq!(detect_dominant_format(&project_root), Some(FileFormat::Po));
+    }
+
+    #[test]
+    fn tst_resolve_branch_name() {
+        let tx_yaml = TransifexYaml::from_str(TEST_TX_YAML_CONTENT).unwrap();
+        assert!(tx_yaml.settings.has_unique_branch_token());
+        assert_eq!(tx_yaml.settings.resolve_branch_name("42"), "transifex_update_42");
+
+        let tx_yaml = TransifexYaml::from_str(&TEST_TX_YAML_CONTENT.replace("transifex_update_<br_unique_id>", "transifex_update")).unwrap();
+        assert!(!tx_yaml.settings.has_unique_branch_token());
+        let first = tx_yaml.settings.resolve_branch_name("42");
+        let second = tx_yaml.settings.resolve_branch_name("43");
+        assert!(first.starts_with("transifex_update_"));
+        assert_eq!(first.len(), "transifex_update_".len() + 8);
+        // the hash doesn't change between releases
+        assert_eq!(first, format!("transifex_update_{}", source_path_hash("42")));
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn tst_parse_historical_keys() {
+        let tx_yaml = TransifexYaml::from_str(r#"filters:
+  - type: file
+    source: translations/app.ts
+    format: qt
+    source_lang: en_US
+    file_filter: translations/app_<lang>.ts
+  - filter_type: file
+    source_file: translations/desktop.ts
+    file_format: QT
+    source_lang: en
+    translation_files: translations/desktop_<lang>.ts
+settings:
+  pr_branch_name: transifex_update_<br_unique_id>
+"#).unwrap();
+        let filter = &tx_yaml.filters[0];
+        assert_eq!(filter.type_attr, "file");
+        assert_eq!(filter.source, "translations/app.ts");
+        assert_eq!(filter.format, "QT");
+        assert_eq!(filter.source_lang, "en_US");
+        assert_eq!(filter.target_pattern, "translations/app_<lang>.ts");
+        assert_eq!(tx_yaml.filters[1].source_lang, "en");
+        assert_eq!(tx_yaml.filters[1].target_pattern, "translations/desktop_<lang>.ts");
+        // current keys are written
+        let content = serde_yml::to_string(&tx_yaml).unwrap();
+        assert!(content.contains("source_language: en_US"));
+        assert!(content.contains("translation_files_expression: translations/app_<lang>.ts"));
+    }
+
+    #[test]
+    fn tst_filter_self_check() {
+        let tx_yaml = TransifexYaml::from_str(TEST_TX_YAML_CONTENT).unwrap();
+        assert_eq!(tx_yaml.filters[0].self_check(), Ok(()));
+        let mut filter = TransifexYaml::from_single_source("translations/app.ts", "QT", "en_US").filters.remove(0);
+        filter.target_pattern = "translations/<lang>/app.ts".to_string();
+        assert_eq!(filter.self_check(), Err("Target pattern \"translations/<lang>/app.ts\" has no <lang> placeholder in its file name".to_string()));
+        filter.target_pattern = "translations/app_<lang>_<lang>.ts".to_string();
+        assert!(filter.self_check().is_err());
+    }
+
+    #[test]
+    fn tst_to_tx_configs_by_component() {
+        let tx_yaml = TransifexYaml::from_str(&yaml_with_filters(&[
+            ("dock/translations/dock.ts", "dock/translations/dock_<lang>.ts"),
+            ("launcher/translations/launcher.ts", "launcher/translations/launcher_<lang>.ts"),
+            ("dock/applet/translations/applet.ts", "dock/applet/translations/applet_<lang>.ts"),
+        ])).unwrap();
+        let configs = tx_yaml.to_tx_configs_by_component("linuxdeepin/dde-shell", &[], 1).unwrap();
+        let sources: Vec<(&str, Vec<&str>)> = configs.iter()
+            .map(|(component, tx_config)| (component.as_str(), tx_config.resource_sections.iter().map(|section| section.source_file.as_str()).collect()))
+            .collect();
+        assert_eq!(sources, vec![
+            ("dock", vec!["dock/translations/dock.ts", "dock/applet/translations/applet.ts"]),
+            ("launcher", vec!["launcher/translations/launcher.ts"]),
+        ]);
+        assert_eq!(tx_yaml.filters[2].source_component(2), "dock/applet");
+        assert_eq!(TransifexYaml::from_single_source("app.ts", "QT", "en_US").filters[0].source_component(1), "");
+    }
+
+    #[test]
+    fn tst_target_depth() {
+        let mut filter = TransifexYaml::from_single_source("a/b/app.ts", "QT", "en_US").filters.remove(0);
+        assert_eq!(filter.target_depth(), 2);
+        filter.target_pattern = "./a//b/app_<lang>.ts".to_string();
+        assert_eq!(filter.target_depth(), 2);
+        filter.target_pattern = "app_<lang>.ts".to_string();
+        assert_eq!(filter.target_depth(), 0);
+    }
+
+    #[test]
+    fn tst_from_single_source() {
+        let tx_yaml = TransifexYaml::from_single_source("app.ts", "qt", "en_US");
+        assert_eq!(tx_yaml.filters.len(), 1);
+        assert_eq!(tx_yaml.filters[0].target_pattern, "app_<lang>.ts");
+        assert_eq!(tx_yaml.filters[0].format, "QT");
+        assert!(tx_yaml.settings.has_unique_branch_token());
+        assert_eq!(infer_target_pattern("translations/v1.0/app.po"), "translations/v1.0/app_<lang>.po");
+        assert_eq!(infer_target_pattern("translations/app"), "translations/app_<lang>");
+    }
+
+    #[test]
+    fn tst_normalize() {
+        let mut tx_yaml = TransifexYaml::from_str(r#"filters:
+  - filter_type: " file "
+    source_file: ./translations\app.ts
+    file_format: " qt "
+    source_language: EN-us
+    translation_files_expression: translations//app_<lang>.ts
+    resource_slug: " app "
+settings:
+  pr_branch_name: " transifex_update_<br_unique_id> "
+  language_mapping:
+    " zh_CN ": " zh-Hans "
+  primary_language: zh-cn
+"#).unwrap();
+        tx_yaml.normalize();
+        let filter = &tx_yaml.filters[0];
+        assert_eq!(filter.type_attr, "file");
+        assert_eq!(filter.source, "translations/app.ts");
+        assert_eq!(filter.format, "QT");
+        assert_eq!(filter.source_lang, "en_US");
+        assert_eq!(filter.target_pattern, "translations/app_<lang>.ts");
+        assert_eq!(filter.resource_slug.as_deref(), Some("app"));
+        assert_eq!(tx_yaml.settings.branch_template, DEFAULT_BRANCH_TEMPLATE);
+        assert_eq!(tx_yaml.settings.lang_map, Some(BTreeMap::from([("zh_CN".to_string(), "zh-Hans".to_string())])));
+        assert_eq!(tx_yaml.settings.primary_language.as_deref(), Some("zh_CN"));
+        // normalizing is idempotent
+        let normalized = serde_yml::to_string(&tx_yaml).unwrap();
+        tx_yaml.normalize();
+        assert_eq!(serde_yml::to_string(&tx_yaml).unwrap(), normalized);
+    }
+
+    #[test]
+    fn tst_reverse_lang_map() {
+        let tx_yaml = TransifexYaml::from_str(r#"
+filters: []
+settings:
+  pr_branch_name: transifex_update_<br_unique_id>
+  language_mapping:
+    zh-Hans: zh_CN
+    zh_CN: zh_CN
+    pt-BR: pt_BR
+"#).unwrap();
+        let settings = &tx_yaml.settings;
+        assert_eq!(settings.reverse_lang_map(), HashMap::from([
+            ("zh_CN".to_string(), "zh-Hans".to_string()),
+            ("pt_BR".to_string(), "pt-BR".to_string()),
+        ]));
+        assert_eq!(settings.transifex_codes_for("zh_CN"), vec!["zh-Hans".to_string(), "zh_CN".to_string()]);
+        assert_eq!(settings.transifex_codes_for("pt_BR"), vec!["pt-BR".to_string()]);
+        assert_eq!(settings.transifex_codes_for("de"), vec!["de".to_string()]);
+    }
+
+    #[test]
+    fn tst_sort_filters() {
+        let mut tx_yaml = TransifexYaml::from_str(&yaml_with_filters(&[
+            ("translations/b.ts", "translations/app_<lang>.ts"),
+            ("./translations/c.ts", "translations/app_<lang>.ts"),
+            ("translations/a.ts", "translations/app_<lang>.ts"),
+        ])).unwrap();
+        tx_yaml.sort_filters();
+        let sources: Vec<&str> = tx_yaml.filters.iter().map(|filter| filter.source.as_str()).collect();
+        assert_eq!(sources, vec!["translations/a.ts", "translations/b.ts", "./translations/c.ts"]);
+    }
+
+    #[test]
+    fn tst_normalize_source_path() {
+        assert_eq!(normalize_source_path(" ./translations//app.ts "), "translations/app.ts");
+        assert_eq!(normalize_source_path("translations\\desktop\\desktop.ts"), "translations/desktop/desktop.ts");
     }
 }
@@ -0,0 +1,84 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+// Loads transifex.yaml files over HTTP(S).
+
+use thiserror::Error as TeError;
+
+use super::yaml_file::{TransifexYaml, TxYamlLoadError};
+
+/// Timeout of the whole request of [`load_tx_yaml_from_url`].
+pub const HTTP_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Error of [`load_tx_yaml_from_url`].
+#[derive(TeError, Debug)]
+pub enum TxYamlFetchError {
+    /// The request failed or timed out.
+    #[error("Fail to fetch transifex.yaml because: {0}")]
+    Request(#[from] ureq::Error),
+    /// The server responded with a non-2xx status code.
+    #[error("Fail to fetch transifex.yaml from {url} because: server responded with HTTP status {status}")]
+    Status {
+        /// The requested URL.
+        url: String,
+        /// The HTTP status code of the response.
+        status: u16,
+    },
+    /// The response is not a valid `transifex.yaml`.
+    #[error(transparent)]
+    Load(#[from] TxYamlLoadError),
+}
+
+/// Fetch and parse a `transifex.yaml` from the given HTTP(S) URL, e.g. from a central config service.
+///
+/// The request fails after [`HTTP_TIMEOUT`]. Redirects are followed, any other non-2xx response is
+/// a [`TxYamlFetchError::Status`] error.
+pub fn load_tx_yaml_from_url(url: &str) -> Result<TransifexYaml, TxYamlFetchError> {
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .timeout_global(Some(HTTP_TIMEOUT))
+        .http_status_as_error(false)
+        .build()
+        .into();
+    let mut resp = agent.get(url).call()?;
+    if !resp.status().is_success() {
+        return Err(TxYamlFetchError::Status { url: url.to_string(), status: resp.status().as_u16() });
+    }
+    let source_content = resp.body_mut().read_to_string()?;
+    Ok(TransifexYaml::from_str(&source_content)?)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::transifex::yaml_file::DEFAULT_BRANCH_TEMPLATE;
+    use crate::transifex::yaml_file::tests::TEST_TX_YAML_CONTENT;
+
+    /// Serve a single HTTP response with the given status and body on a random local port, and
+    /// return the URL to request it.
+    fn serve_once(status: &str, body: &str) -> String {
+        use std::io::{Read, Write};
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/transifex.yaml", listener.local_addr().unwrap());
+        let response = format!("HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}", body.len());
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0u8; 4096];
+            let _ = stream.read(&mut request);
+            stream.write_all(response.as_bytes()).unwrap();
+        });
+        url
+    }
+
+    #[test]
+    fn tst_load_tx_yaml_from_url() {
+        let tx_yaml = load_tx_yaml_from_url(&serve_once("200 OK", TEST_TX_YAML_CONTENT)).unwrap();
+        assert_eq!(tx_yaml.filters.len(), 1);
+        assert_eq!(tx_yaml.settings.branch_template, DEFAULT_BRANCH_TEMPLATE);
+
+        let err = load_tx_yaml_from_url(&serve_once("404 Not Found", "")).unwrap_err();
+        assert!(matches!(err, TxYamlFetchError::Status { status: 404, .. }));
+        let err = load_tx_yaml_from_url(&serve_once("200 OK", "filters: 1")).unwrap_err();
+        assert!(matches!(err, TxYamlFetchError::Load(_)));
+    }
+}
@@ -0,0 +1,202 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+// Restores the quoting of scalars when saving an edited transifex.yaml file.
+
+use std::collections::HashMap;
+
+use super::yaml_file::TransifexYaml;
+
+impl TransifexYaml {
+    /// Serialize to `transifex.yaml` content, keeping the quoting style the given original content
+    /// used for each field, so saving after an edit causes less noisy diffs.
+    ///
+    /// A scalar is quoted again if the original has the same value quoted at the same path, see
+    /// [`restore_scalar_quoting`]. Comments and the layout of the original are not preserved.
+    pub fn to_string_preserving(&self, original: &str) -> Result<String, serde_yml::Error> {
+        Ok(restore_scalar_quoting(original, &serde_yml::to_string(self)?))
+    }
+}
+
+/// Quoting style of a YAML scalar.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum QuoteStyle {
+    Single,
+    Double,
+}
+
+impl QuoteStyle {
+    fn quote(self, value: &str) -> String {
+        match self {
+            Self::Single => format!("'{}'", value.replace('\'', "''")),
+            Self::Double => format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\"")),
+        }
+    }
+}
+
+/// Split a `key: value` line of a block mapping, possibly a sequence entry, into the part up to the
+/// value, the key and the value.
+fn split_yaml_key_line(line: &str) -> Option<(&str, &str, &str)> {
+    let trimmed = line.trim_start();
+    let trimmed = trimmed.strip_prefix("- ").unwrap_or(trimmed);
+    let (key, value) = trimmed.split_once(": ")?;
+    if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        return None;
+    }
+    let value = value.trim_start();
+    Some((&line[..line.len() - value.len()], key, value))
+}
+
+/// Parse a single line quoted scalar, optionally followed by a comment, and return its style and
+/// unescaped value. Only the escapes of quotes and backslashes are handled.
+fn parse_quoted_scalar(value: &str) -> Option<(QuoteStyle, String)> {
+    let mut chars = value.chars();
+    let style = match chars.next()? {
+        '\'' => QuoteStyle::Single,
+        '"' => QuoteStyle::Double,
+        _ => return None,
+    };
+    let mut unquoted = String::new();
+    loop {
+        match (style, chars.next()?) {
+            (QuoteStyle::Single, '\'') => {
+                if chars.clone().next() == Some('\'') {
+                    chars.next();
+                    unquoted.push('\'');
+                } else {
+                    break;
+                }
+            },
+            (QuoteStyle::Double, '"') => break,
+            (QuoteStyle::Double, '\\') => match chars.next()? {
+                escaped @ ('"' | '\\') => unquoted.push(escaped),
+                _ => return None,
+            },
+            (_, c) => unquoted.push(c),
+        }
+    }
+    let rest = chars.as_str().trim_start();
+    (rest.is_empty() || rest.starts_with('#')).then_some((style, unquoted))
+}
+
+/// A mapping key or sequence item enclosing the current line, see [`yaml_key_paths`].
+struct YamlPathSegment {
+    indent: usize,
+    name: String,
+    is_item: bool,
+    next_item: usize,
+}
+
+/// The path of the key of every line of block-style YAML content, e.g. `filters/0/source_file`,
+/// together with the part of the line up to the value and the value, see [`split_yaml_key_line`].
+///
+/// Sequence items are numbered, so the same key of different items gets different paths, whether
+/// the sequence is indented under its key or not. Lines without a `key: value` pair are `None`.
+fn yaml_key_paths(content: &str) -> Vec<Option<(String, &str, &str)>> {
+    let mut stack = Vec::<YamlPathSegment>::new();
+    content.lines().map(|line| {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            return None;
+        }
+        let mut indent = line.len() - trimmed.len();
+        let mut rest = trimmed;
+        if let Some(item) = trimmed.strip_prefix("- ").or_else(|| (trimmed == "-").then_some("")) {
+            while stack.last().is_some_and(|top| top.indent > indent || (top.indent == indent && top.is_item)) {
+                stack.pop();
+            }
+            let index = stack.last_mut().map_or(0, |parent| {
+                parent.next_item += 1;
+                parent.next_item - 1
+            });
+            stack.push(YamlPathSegment { indent, name: index.to_string(), is_item: true, next_item: 0 });
+            indent += 2;
+            rest = item;
+        }
+        let key = rest.split_once(": ").map(|(key, _)| key).or_else(|| rest.strip_suffix(':'))?;
+        if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+            return None;
+        }
+        while stack.last().is_some_and(|top| top.indent >= indent) {
+            stack.pop();
+        }
+        let path = stack.iter().map(|segment| segment.name.as_str()).chain([key]).collect::<Vec<&str>>().join("/");
+        stack.push(YamlPathSegment { indent, name: key.to_string(), is_item: false, next_item: 0 });
+        split_yaml_key_line(line).map(|(prefix, _, value)| (path, prefix, value))
+    }).collect()
+}
+
+/// Quote plain scalars of the serialized YAML content again, if the original content has the same
+/// value quoted at the same path, e.g. `filters/0/source_file`.
+///
+/// This is a post-process of `serde_yml` output, not a format-preserving editor: comments, blank
+/// lines and the indentation of the original are lost, only the quoting of single line scalars is
+/// restored.
+pub fn restore_scalar_quoting(original: &str, serialized: &str) -> String {
+    let mut quoted = HashMap::<(String, String), QuoteStyle>::new();
+    for (path, _, value) in yaml_key_paths(original).into_iter().flatten() {
+        if let Some((style, unquoted)) = parse_quoted_scalar(value) {
+            quoted.insert((path, unquoted), style);
+        }
+    }
+    let mut restored = String::with_capacity(serialized.len());
+    for (line, key_path) in serialized.lines().zip(yaml_key_paths(serialized)) {
+        let requoted = key_path
+            .filter(|(_, _, value)| !value.starts_with(['\'', '"']))
+            .and_then(|(path, prefix, value)| quoted.get(&(path, value.to_string())).map(|style| format!("{prefix}{}", style.quote(value))));
+        restored.push_str(requoted.as_deref().unwrap_or(line));
+        restored.push('\n');
+    }
+    restored
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn tst_to_string_preserving() {
+        let original = r#"filters:
+  - filter_type: file
+    source_file: "translations/app.ts"
+    file_format: QT
+    source_language: 'en_US' # the source language
+    translation_files_expression: translations/app_<lang>.ts
+settings:
+  pr_branch_name: transifex_update_<br_unique_id>
+"#;
+        let mut tx_yaml = TransifexYaml::from_str(original).unwrap();
+        tx_yaml.filters[0].format = "PO".to_string();
+        let content = tx_yaml.to_string_preserving(original).unwrap();
+        assert!(content.contains("  source_file: \"translations/app.ts\"\n"));
+        assert!(content.contains("  source_language: 'en_US'\n"));
+        assert!(content.contains("  translation_files_expression: translations/app_<lang>.ts\n"));
+        let reparsed = TransifexYaml::from_str(&content).unwrap();
+        assert_eq!(reparsed.filters[0].source, "translations/app.ts");
+        assert_eq!(reparsed.filters[0].format, "PO");
+
+        // quoting is restored per path, not per key
+        let original = original.replace("settings:", concat!(
+            "  - filter_type: file\n",
+            "    source_file: translations/app.ts\n",
+            "    file_format: QT\n",
+            "    source_language: en_US\n",
+            "    translation_files_expression: translations/other_<lang>.ts\n",
+            "settings:",
+        ));
+        let tx_yaml = TransifexYaml::from_str(&original).unwrap();
+        let content = tx_yaml.to_string_preserving(&original).unwrap();
+        assert_eq!(content.matches("\"translations/app.ts\"").count(), 1);
+        assert!(content.contains("  source_file: translations/app.ts\n"));
+        let paths = yaml_key_paths(&original).into_iter().flatten().map(|(path, _, _)| path).collect::<Vec<String>>();
+        assert!(paths.contains(&"filters/0/source_file".to_string()));
+        assert!(paths.contains(&"filters/1/source_file".to_string()));
+        assert!(paths.contains(&"settings/pr_branch_name".to_string()));
+
+        assert_eq!(parse_quoted_scalar(r#""a \"b\" \\c""#), Some((QuoteStyle::Double, r#"a "b" \c"#.to_string())));
+        assert_eq!(parse_quoted_scalar("'it''s' # comment"), Some((QuoteStyle::Single, "it's".to_string())));
+        assert_eq!(parse_quoted_scalar("'unterminated"), None);
+        assert_eq!(QuoteStyle::Double.quote(r#"a "b""#), r#""a \"b\"""#);
+    }
+}
@@ -0,0 +1,127 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+// Filesystem walking shared by all scanning features, so directory pruning behaves the same everywhere.
+
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// Entry yielded by [`walk`], also what predicates like
+/// [`crate::transifex::yaml_file::Filter::match_target_files_with_predicate`] receive.
+pub use walkdir::DirEntry;
+
+/// Directories which never contain translation resources we care about, but can be huge.
+pub const DEFAULT_PRUNED_DIRS: &[&str] = &[".git", "build", "target", "node_modules", ".flatpak-builder"];
+
+/// Options of [`walk`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct WalkOptions {
+    /// Maximum depth to descend, the root itself is at depth 0.
+    pub max_depth: Option<usize>,
+    /// Follow symbolic links to directories and files.
+    pub follow_symlinks: bool,
+    /// Names of directories which are not descended into. The root itself is never pruned.
+    pub pruned_dirs: Vec<String>,
+    /// Also prune directories whose names start with `.`.
+    pub prune_hidden: bool,
+}
+
+impl Default for WalkOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: None,
+            follow_symlinks: true,
+            pruned_dirs: DEFAULT_PRUNED_DIRS.iter().map(|dir| dir.to_string()).collect(),
+            prune_hidden: false,
+        }
+    }
+}
+
+/// Iterator returned by [`walk`].
+pub struct Walk {
+    inner: walkdir::IntoIter,
+    pruned_dirs: Vec<String>,
+    prune_hidden: bool,
+}
+
+impl Walk {
+    fn is_pruned(&self, entry: &DirEntry) -> bool {
+        if entry.depth() == 0 || !entry.file_type().is_dir() {
+            return false;
+        }
+        let Some(name) = entry.file_name().to_str() else {
+            return false;
+        };
+        (self.prune_hidden && name.starts_with('.')) || self.pruned_dirs.iter().any(|pruned| pruned == name)
+    }
+}
+
+impl Iterator for Walk {
+    type Item = Result<DirEntry, walkdir::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let entry = match self.inner.next()? {
+                Ok(entry) => entry,
+                Err(e) => return Some(Err(e)),
+            };
+            if self.is_pruned(&entry) {
+                self.inner.skip_current_dir();
+                continue;
+            }
+            return Some(Ok(entry));
+        }
+    }
+}
+
+/// Walk `root` recursively, including `root` itself.
+///
+/// Entries of the same directory are yielded sorted by file name, so the order is deterministic.
+/// Pruned directories are neither yielded nor descended into.
+pub fn walk(root: &Path, opts: &WalkOptions) -> Walk {
+    let mut walk_dir = WalkDir::new(root)
+        .follow_links(opts.follow_symlinks)
+        .sort_by_file_name();
+    if let Some(max_depth) = opts.max_depth {
+        walk_dir = walk_dir.max_depth(max_depth);
+    }
+    Walk {
+        inner: walk_dir.into_iter(),
+        pruned_dirs: opts.pruned_dirs.clone(),
+        prune_hidden: opts.prune_hidden,
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use std::fs;
+    use crate::transifex::yaml_file::tests::create_test_project_dir;
+
+    #[test]
+    fn tst_walk() {
+        let root = create_test_project_dir("tst_walk");
+        fs::create_dir_all(root.join("node_modules/pkg")).unwrap();
+        fs::create_dir_all(root.join(".hidden")).unwrap();
+        fs::create_dir_all(root.join("translations/nested")).unwrap();
+        fs::write(root.join("node_modules/pkg/app_zh_CN.ts"), "").unwrap();
+        fs::write(root.join(".hidden/app_zh_CN.ts"), "").unwrap();
+        fs::write(root.join("translations/b.ts"), "").unwrap();
+        fs::write(root.join("translations/a.ts"), "").unwrap();
+        fs::write(root.join("translations/nested/c.ts"), "").unwrap();
+
+        let files = |opts: &WalkOptions| -> Vec<String> {
+            walk(&root, opts).map(|entry| entry.unwrap())
+                .filter(|entry| entry.file_type().is_file())
+                .map(|entry| entry.path().strip_prefix(&root).unwrap().to_string_lossy().replace('\\', "/"))
+                .collect()
+        };
+        let opts = WalkOptions::default();
+        assert_eq!(files(&opts), vec![".hidden/app_zh_CN.ts", "translations/a.ts", "translations/b.ts", "translations/nested/c.ts"]);
+        let opts = WalkOptions { prune_hidden: true, max_depth: Some(2), ..WalkOptions::default() };
+        assert_eq!(files(&opts), vec!["translations/a.ts", "translations/b.ts"]);
+        let opts = WalkOptions { pruned_dirs: Vec::new(), prune_hidden: true, ..WalkOptions::default() };
+        assert!(files(&opts).contains(&"node_modules/pkg/app_zh_CN.ts".to_string()));
+    }
+}
@@ -0,0 +1,124 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+//! Export of `transifex.yaml` filters as Weblate components, to evaluate a migration to Weblate.
+//!
+//! Each filter becomes one component, with the source file as template and the target pattern as
+//! file mask. Only the fields Weblate needs to create the component are exported.
+
+use std::path::PathBuf;
+use serde::Serialize;
+
+use crate::transifex::yaml_file::{FileFormat, Filter, Settings, TransifexYaml};
+
+/// A Weblate component, i.e. a translatable resource.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WeblateComponent {
+    /// Component name, see [`Filter::effective_resource_name`].
+    pub name: String,
+    /// Component slug, see [`Filter::effective_resource_slug`].
+    pub slug: String,
+    /// Source file, relative to the project root.
+    pub template: String,
+    /// Translation file glob, i.e. the target pattern with `<lang>` replaced by `*`.
+    pub filemask: String,
+    /// Weblate file format, `None` if Weblate has no equivalent or the format of the filter can't be
+    /// resolved, see [`weblate_file_format`] and [`Filter::effective_format`].
+    pub file_format: Option<String>,
+    /// Source language code.
+    pub source_language: String,
+    /// Languages of the translation files currently matched, sorted.
+    pub languages: Vec<String>,
+}
+
+/// Map a file format to Weblate's identifier of it, e.g. `ts` for [`FileFormat::Qt`].
+pub fn weblate_file_format(format: &FileFormat) -> Option<&'static str> {
+    match format {
+        FileFormat::Qt => Some("ts"),
+        FileFormat::Po => Some("po"),
+        FileFormat::KeyValueJson => Some("json"),
+        FileFormat::StructuredJson => Some("json-nested"),
+        // Weblate can't translate desktop entries
+        FileFormat::Desktop => None,
+        FileFormat::Other(tx_type) => match tx_type.as_str() {
+            "ANDROID" => Some("aresource"),
+            "CHROME" => Some("webextension"),
+            "HTML" => Some("html"),
+            "INI" => Some("ini"),
+            "PROPERTIES" => Some("properties"),
+            "STRINGS" => Some("strings"),
+            "STRINGSDICT" => Some("stringsdict"),
+            "XLIFF" => Some("xliff"),
+            "YAML_GENERIC" | "YML" => Some("yaml"),
+            _ => None,
+        },
+    }
+}
+
+impl WeblateComponent {
+    /// Create the component of the given filter, with the languages matched under `project_root`.
+    /// `settings` are the settings of the filter's `transifex.yaml`, for the default file format.
+    ///
+    /// Filters whose translation files can't be matched, e.g. because the target directory doesn't
+    /// exist yet, have no languages.
+    pub fn from_filter(filter: &Filter, settings: &Settings, project_root: &PathBuf) -> WeblateComponent {
+        let mut languages = filter.match_target_files(project_root)
+            .map(|matched| matched.into_iter().map(|(lang_code, _)| lang_code).collect::<Vec<String>>())
+            .unwrap_or_default();
+        languages.sort();
+        languages.dedup();
+        WeblateComponent {
+            name: filter.effective_resource_name().to_string(),
+            slug: filter.effective_resource_slug(),
+            template: filter.source.clone(),
+            filemask: filter.target_pattern.replace("<lang>", "*"),
+            file_format: filter.effective_format(settings).ok().as_ref().and_then(weblate_file_format).map(str::to_string),
+            source_language: filter.source_lang.clone(),
+            languages,
+        }
+    }
+}
+
+impl TransifexYaml {
+    /// Convert every filter to a Weblate component, in filter order, see [`WeblateComponent::from_filter`].
+    pub fn to_weblate_components(&self, project_root: &PathBuf) -> Vec<WeblateComponent> {
+        self.filters.iter().map(|filter| WeblateComponent::from_filter(filter, &self.settings, project_root)).collect()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::fs;
+    use super::*;
+    use crate::transifex::yaml_file::tests::{TEST_TX_YAML_CONTENT, create_test_project_dir};
+
+    #[test]
+    fn tst_to_weblate_components() {
+        let project_root = create_test_project_dir("tst_to_weblate_components");
+        let translations_dir = project_root.join("shell-launcher-applet/translations");
+        fs::create_dir_all(&translations_dir).unwrap();
+        fs::write(translations_dir.join("org.deepin.ds.dock.launcherapplet_zh_CN.ts"), "<TS/>").unwrap();
+        fs::write(translations_dir.join("org.deepin.ds.dock.launcherapplet_ja.ts"), "<TS/>").unwrap();
+        let tx_yaml = TransifexYaml::from_str(TEST_TX_YAML_CONTENT).unwrap();
+        let components = tx_yaml.to_weblate_components(&project_root);
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].template, "shell-launcher-applet/translations/org.deepin.ds.dock.launcherapplet.ts");
+        assert_eq!(components[0].filemask, "shell-launcher-applet/translations/org.deepin.ds.dock.launcherapplet_*.ts");
+        assert_eq!(components[0].file_format.as_deref(), Some("ts"));
+        assert_eq!(components[0].source_language, "en_US");
+        assert_eq!(components[0].languages, vec!["ja", "zh_CN"]);
+    }
+
+    #[test]
+    fn tst_weblate_component_effective_format() {
+        let project_root = create_test_project_dir("tst_weblate_component_effective_format");
+        let mut tx_yaml = TransifexYaml::from_str(TEST_TX_YAML_CONTENT).unwrap();
+        tx_yaml.filters[0].format = String::new();
+        tx_yaml.filters[0].source = "messages/app.lang".to_string();
+        // nothing resolves
+        assert_eq!(tx_yaml.to_weblate_components(&project_root)[0].file_format, None);
+        tx_yaml.settings.default_format = Some("PO".to_string());
+        assert_eq!(tx_yaml.to_weblate_components(&project_root)[0].file_format.as_deref(), Some("po"));
+    }
+}
@@ -0,0 +1,54 @@
+// SPDX-FileCopyrightText: 2025 UnionTech Software Technology Co., Ltd.
+//
+// SPDX-License-Identifier: MIT
+
+// Allocation counts of the .tx/config conversions. This is its own test binary, so the counting
+// allocator doesn't affect the unit tests of the crate.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use deepin_translation_utils::transifex::yaml_file::TransifexYaml;
+
+/// Counts allocations made by the current thread, so tests running in parallel don't disturb each other.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn count_allocations<T>(f: impl FnOnce() -> T) -> (usize, T) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = f();
+    (ALLOCATIONS.with(Cell::get) - before, result)
+}
+
+#[test]
+fn tst_into_tx_config_allocations() {
+    let filters: String = (0..100)
+        .map(|index| format!("\n  - filter_type: file\n    source_file: translations/app{index}.ts\n    file_format: QT\n    source_language: en_US\n    translation_files_expression: translations/app{index}_<lang>.ts\n"))
+        .collect();
+    let content = format!("filters:{filters}settings:\n  pr_branch_name: transifex_update_<br_unique_id>\n");
+
+    let tx_yaml = TransifexYaml::from_str(&content).unwrap();
+    let (borrowed_allocations, borrowed) = count_allocations(|| tx_yaml.to_tx_config("linuxdeepin/dde-shell".to_string(), Vec::new()));
+    let tx_yaml = TransifexYaml::from_str(&content).unwrap();
+    let (consumed_allocations, consumed) = count_allocations(|| tx_yaml.into_tx_config("linuxdeepin/dde-shell", &[]));
+    assert_eq!(consumed.unwrap().to_str(), borrowed.to_str());
+    // only the placeholder slug of each resource is allocated, instead of every field
+    assert!(consumed_allocations * 3 < borrowed_allocations, "{consumed_allocations} vs {borrowed_allocations}");
+}